@@ -4,7 +4,7 @@ defmodule TantivyEx.Aggregation do
 
   This module provides a complete aggregation system supporting:
   - Bucket aggregations (terms, histogram, date_histogram, range)
-  - Metric aggregations (avg, min, max, sum, count, stats, percentiles)
+  - Metric aggregations (avg, min, max, sum, count, stats, percentiles, percentile_ranks)
   - Nested/sub-aggregations
   - Elasticsearch-compatible JSON request/response format
   - Advanced aggregation options and configurations
@@ -101,6 +101,10 @@ defmodule TantivyEx.Aggregation do
 
       # Search with aggregations
       {:ok, result} = Aggregation.search_with_aggregations(searcher, query, aggregations, 20)
+
+      # Filtered hits with facet counts over the whole index
+      {:ok, result} =
+        Aggregation.search_with_aggregations(searcher, filter, aggregations, 20, global: true)
   """
 
   alias TantivyEx.Native
@@ -110,7 +114,11 @@ defmodule TantivyEx.Aggregation do
   @type aggregation_options :: [
           validate: boolean(),
           memory_limit: pos_integer(),
-          timeout: pos_integer()
+          timeout: pos_integer(),
+          global: boolean(),
+          agg_query: term(),
+          sort_field: String.t(),
+          order: :asc | :desc
         ]
 
   @default_options [
@@ -122,6 +130,10 @@ defmodule TantivyEx.Aggregation do
   @doc """
   Runs aggregations on search results without returning documents.
 
+  Results are built as Elixir terms directly in the NIF rather than
+  serialized to JSON and decoded, which keeps frequent polling cheap. Counts
+  are integers and metric values floats, exactly as decoded JSON would be.
+
   ## Parameters
 
   - `searcher`: SearcherResource from TantivyEx.Searcher
@@ -166,8 +178,7 @@ defmodule TantivyEx.Aggregation do
 
     with {:ok, validated_aggs} <- validate_aggregations(aggregations, opts),
          {:ok, json_request} <- encode_aggregations(validated_aggs),
-         {:ok, json_response} <- run_native_aggregations(searcher, query, json_request),
-         {:ok, result} <- decode_aggregation_result(json_response) do
+         {:ok, result} <- run_native_aggregations(searcher, query, json_request) do
       {:ok, result}
     else
       {:error, reason} -> {:error, reason}
@@ -185,6 +196,20 @@ defmodule TantivyEx.Aggregation do
   - `search_limit`: Maximum number of documents to return (default: 10)
   - `options`: Aggregation options (optional)
 
+  ## Options
+
+  By default hits and aggregations use the same query. For faceted navigation,
+  where facet counts shouldn't shrink as the user filters the hits:
+
+  - `:global` - compute aggregations over all documents (default: false)
+  - `:agg_query` - compute aggregations over this query instead of `query`
+
+  Hits are ordered by relevance unless a sort is given, e.g. newest first for an
+  analytics page:
+
+  - `:sort_field` - fast numeric or date field to order the hits by
+  - `:order` - `:asc` or `:desc` (default: :asc)
+
   ## Returns
 
   - `{:ok, %{hits: search_results, aggregations: aggregation_results}}` on success
@@ -200,6 +225,12 @@ defmodule TantivyEx.Aggregation do
 
       {:ok, result} = Aggregation.search_with_aggregations(searcher, query, aggregations, 20)
 
+      {:ok, newest} =
+        Aggregation.search_with_aggregations(searcher, query, aggregations, 20,
+          sort_field: "published_date",
+          order: :desc
+        )
+
       # Result format:
       %{
         "hits" => %{
@@ -227,8 +258,17 @@ defmodule TantivyEx.Aggregation do
 
     with {:ok, validated_aggs} <- validate_aggregations(aggregations, opts),
          {:ok, json_request} <- encode_aggregations(validated_aggs),
+         {:ok, agg_query} <- aggregation_query(opts),
+         {:ok, sort} <- hits_sort(opts),
          {:ok, json_response} <-
-           run_native_search_with_aggregations(searcher, query, json_request, search_limit),
+           run_native_search_with_aggregations(
+             searcher,
+             query,
+             agg_query,
+             json_request,
+             search_limit,
+             sort
+           ),
          {:ok, result} <- decode_search_with_aggregations_result(json_response) do
       {:ok, result}
     else
@@ -236,6 +276,53 @@ defmodule TantivyEx.Aggregation do
     end
   end
 
+  @doc """
+  Validates an aggregation request against a schema without running it.
+
+  Checks that every aggregated field exists, is a fast field, and has a type the
+  aggregation supports, including sub-aggregations. Useful for UI builders to
+  catch mistakes before running an expensive search.
+
+  ## Parameters
+
+  - `schema`: The schema the aggregations will run against
+  - `aggregations`: Map of aggregation definitions
+
+  ## Returns
+
+  - `:ok` when the request is valid
+  - `{:error, errors}` with a list of maps holding `"aggregation"` (dotted path),
+    `"field"`, `"error"` (`"invalid_request"`, `"unknown_field"`, `"not_fast"` or
+    `"unsupported_field_type"`) and a human readable `"message"`
+
+  ## Examples
+
+      aggregations = %{
+        "categories" => %{
+          "terms" => %{"field" => "category"},
+          "aggs" => %{"avg_price" => %{"avg" => %{"field" => "title"}}}
+        }
+      }
+
+      {:error, [%{"aggregation" => "categories.avg_price", "error" => error}]} =
+        Aggregation.validate(schema, aggregations)
+
+      # error == "unsupported_field_type"
+  """
+  @spec validate(term(), aggregation_request()) :: :ok | {:error, [map()] | String.t()}
+  def validate(schema, aggregations) do
+    with {:ok, json_request} <- encode_aggregations(aggregations),
+         json when is_binary(json) <- Native.validate_aggregations(schema, json_request),
+         {:ok, %{"valid" => valid, "errors" => errors}} <- Jason.decode(json) do
+      if valid, do: :ok, else: {:error, errors}
+    else
+      {:error, reason} when is_binary(reason) -> {:error, reason}
+      other -> {:error, "Failed to validate aggregations: #{inspect(other)}"}
+    end
+  rescue
+    e -> {:error, "Failed to validate aggregations: #{inspect(e)}"}
+  end
+
   @doc """
   Creates a terms aggregation for grouping documents by field values.
 
@@ -247,7 +334,12 @@ defmodule TantivyEx.Aggregation do
   ## Options
 
   - `:size` - Maximum number of buckets to return (default: 10)
-  - `:min_doc_count` - Minimum document count for buckets (default: 1)
+  - `:shard_size` - Number of candidate terms each segment returns before the results are
+    merged (default: `size * 10`). Larger values make counts more accurate on
+    multi-segment indexes at the cost of more work; `doc_count_error_upper_bound`
+    reports the remaining worst-case error
+  - `:min_doc_count` - Minimum document count for buckets (default: 1). Use `0` to also
+    return terms that have no matching documents for the query
   - `:missing` - Value to use for documents missing the field
   - `:order` - Sort order for buckets
 
@@ -263,7 +355,8 @@ defmodule TantivyEx.Aggregation do
       "size" => Keyword.get(options, :size, 10)
     }
 
-    config = add_optional_params(base_config, options, [:min_doc_count, :missing, :order])
+    config =
+      add_optional_params(base_config, options, [:shard_size, :min_doc_count, :missing, :order])
     %{"terms" => config}
   end
 
@@ -280,11 +373,20 @@ defmodule TantivyEx.Aggregation do
 
   - `:min_doc_count` - Minimum document count for buckets (default: 1)
   - `:keyed` - Return buckets as a map instead of array (default: false)
+  - `:extended_bounds` - `%{min: min, max: max}` range of buckets to return even when
+    empty (combine with `min_doc_count: 0`)
+  - `:hard_bounds` - `%{min: min, max: max}` range outside of which no buckets are created
+  - `:offset` - Shifts bucket boundaries, so buckets become `[offset + interval * k, ...)`
 
   ## Examples
 
       hist_agg = Aggregation.histogram("price", 10.0, min_doc_count: 2)
       # Returns: %{"histogram" => %{"field" => "price", "interval" => 10.0, "min_doc_count" => 2}}
+
+      continuous = Aggregation.histogram("price", 100.0,
+        min_doc_count: 0,
+        extended_bounds: %{min: 0, max: 1000}
+      )
   """
   @spec histogram(String.t(), float(), keyword()) :: map()
   def histogram(field, interval, options \\ []) do
@@ -293,7 +395,15 @@ defmodule TantivyEx.Aggregation do
       "interval" => interval
     }
 
-    config = add_optional_params(base_config, options, [:min_doc_count, :keyed])
+    config =
+      add_optional_params(base_config, options, [
+        :min_doc_count,
+        :keyed,
+        :extended_bounds,
+        :hard_bounds,
+        :offset
+      ])
+
     %{"histogram" => config}
   end
 
@@ -303,22 +413,33 @@ defmodule TantivyEx.Aggregation do
   ## Parameters
 
   - `field`: Date field name to aggregate on
-  - `interval`: Time interval (e.g., "day", "month", "year", "1h", "30m")
+  - `interval`: Calendar interval ("minute", "hour", "day", "week" or "1m", "1h", "1d", "1w")
   - `options`: Date histogram aggregation options
 
+  Calendar weeks start on Monday. Variable-length calendar units such as "month"
+  or "year" are rejected by the underlying engine; use a `"fixed_interval"`
+  definition (e.g. `"30d"`) for other durations.
+
   ## Options
 
   - `:min_doc_count` - Minimum document count for buckets (default: 1)
   - `:keyed` - Return buckets as a map instead of array (default: false)
   - `:time_zone` - Time zone for bucket calculation
   - `:format` - Date format for bucket keys
+  - `:extended_bounds` - `%{min: min, max: max}` range of buckets to return even when
+    empty, as epoch milliseconds or RFC3339 strings (combine with `min_doc_count: 0`)
+  - `:hard_bounds` - `%{min: min, max: max}` range outside of which no buckets are created
+  - `:offset` - Signed duration shifting bucket boundaries, such as `"-4h"` or `"+30m"`.
+    Daily buckets for a UTC-4 timezone start at 04:00 UTC, i.e. `offset: "+4h"`
 
   ## Examples
 
-      date_hist = Aggregation.date_histogram("timestamp", "month")
-      # Returns: %{"date_histogram" => %{"field" => "timestamp", "calendar_interval" => "month"}}
+      date_hist = Aggregation.date_histogram("timestamp", "week")
+      # Returns: %{"date_histogram" => %{"field" => "timestamp", "calendar_interval" => "week"}}
 
       hourly_hist = Aggregation.date_histogram("created_at", "1h", time_zone: "America/New_York")
+
+      new_york_days = Aggregation.date_histogram("created_at", "day", offset: "+4h")
   """
   @spec date_histogram(String.t(), String.t(), keyword()) :: map()
   def date_histogram(field, interval, options \\ []) do
@@ -328,7 +449,15 @@ defmodule TantivyEx.Aggregation do
     }
 
     config =
-      add_optional_params(base_config, options, [:min_doc_count, :keyed, :time_zone, :format])
+      add_optional_params(base_config, options, [
+        :min_doc_count,
+        :keyed,
+        :time_zone,
+        :format,
+        :extended_bounds,
+        :hard_bounds,
+        :offset
+      ])
 
     %{"date_histogram" => config}
   end
@@ -387,7 +516,8 @@ defmodule TantivyEx.Aggregation do
 
   ## Parameters
 
-  - `type`: Type of metric (:avg, :min, :max, :sum, :count, :stats, :percentiles)
+  - `type`: Type of metric (:avg, :min, :max, :sum, :count, :stats, :percentiles,
+    :percentile_ranks)
   - `field`: Field name to calculate metrics on
   - `options`: Metric-specific options
 
@@ -400,12 +530,17 @@ defmodule TantivyEx.Aggregation do
   - `:count` - Count of values
   - `:stats` - All basic statistics (min, max, avg, sum, count)
   - `:percentiles` - Percentile calculations
+  - `:percentile_ranks` - Percentage of values at or below each given value
 
   ## Options for :percentiles
 
   - `:percents` - List of percentiles to calculate (default: [1, 5, 25, 50, 75, 95, 99])
   - `:keyed` - Return as map instead of array (default: true)
 
+  ## Options for :percentile_ranks
+
+  - `:values` - List of values to rank (required)
+
   ## Examples
 
       avg_agg = Aggregation.metric(:avg, "price")
@@ -415,6 +550,9 @@ defmodule TantivyEx.Aggregation do
       # Returns: %{"stats" => %{"field" => "rating"}}
 
       percentiles_agg = Aggregation.metric(:percentiles, "response_time", percents: [50, 95, 99])
+
+      ranks_agg = Aggregation.metric(:percentile_ranks, "response_time", values: [200, 500])
+      # Result: %{"values" => %{"200.0" => 92.0, "500.0" => 99.5}}
   """
   @spec metric(atom(), String.t(), keyword()) :: map()
   def metric(type, field, options \\ [])
@@ -430,6 +568,10 @@ defmodule TantivyEx.Aggregation do
     %{"percentiles" => config}
   end
 
+  def metric(:percentile_ranks, field, options) do
+    %{"percentile_ranks" => %{"field" => field, "values" => Keyword.fetch!(options, :values)}}
+  end
+
   def metric(type, field, options) when type in [:avg, :min, :max, :sum, :count, :stats] do
     base_config = %{"field" => field}
     config = add_optional_params(base_config, options, [:missing])
@@ -581,6 +723,16 @@ defmodule TantivyEx.Aggregation do
     end
   end
 
+  defp validate_aggregation_type("percentile_ranks", config) do
+    unless Map.has_key?(config, "field") do
+      throw({:error, "percentile_ranks aggregation requires 'field' parameter"})
+    end
+
+    unless is_list(config["values"]) and config["values"] != [] do
+      throw({:error, "percentile_ranks aggregation requires a non-empty 'values' list"})
+    end
+  end
+
   defp validate_aggregation_type(type, _config) do
     throw({:error, "Unknown aggregation type: #{type}"})
   end
@@ -592,13 +744,6 @@ defmodule TantivyEx.Aggregation do
     end
   end
 
-  defp decode_aggregation_result(json_response) do
-    case Jason.decode(json_response) do
-      {:ok, result} -> {:ok, result}
-      {:error, reason} -> {:error, "Failed to decode aggregation result: #{inspect(reason)}"}
-    end
-  end
-
   defp decode_search_with_aggregations_result(json_response) do
     case Jason.decode(json_response) do
       {:ok, result} ->
@@ -609,22 +754,54 @@ defmodule TantivyEx.Aggregation do
     end
   end
 
+  # Results come back as terms, so there is no JSON to decode
   defp run_native_aggregations(searcher, query, json_request) do
-    case Native.run_aggregations(searcher, query, json_request) do
-      result when is_binary(result) ->
-        if String.starts_with?(result, "Error") do
-          {:error, result}
-        else
-          {:ok, result}
-        end
+    case Native.run_aggregations_terms(searcher, query, json_request) do
+      {:error, reason} -> {:error, reason}
+      result when is_map(result) -> {:ok, result}
+      error -> {:error, "Native aggregation call failed: #{inspect(error)}"}
+    end
+  end
 
-      error ->
-        {:error, "Native aggregation call failed: #{inspect(error)}"}
+  defp aggregation_query(opts) do
+    cond do
+      opts[:agg_query] != nil -> {:ok, opts[:agg_query]}
+      opts[:global] == true -> TantivyEx.Query.all()
+      true -> {:ok, nil}
+    end
+  end
+
+  defp hits_sort(opts) do
+    case {opts[:sort_field], Keyword.get(opts, :order, :asc)} do
+      {nil, _order} -> {:ok, nil}
+      {field, order} when order in [:asc, :desc] -> {:ok, {field, Atom.to_string(order)}}
+      {_field, order} -> {:error, "Invalid :order option: #{inspect(order)}"}
     end
   end
 
-  defp run_native_search_with_aggregations(searcher, query, json_request, search_limit) do
-    case Native.run_search_with_aggregations(searcher, query, json_request, search_limit) do
+  defp run_native_search_with_aggregations(
+         searcher,
+         query,
+         agg_query,
+         json_request,
+         search_limit,
+         sort
+       ) do
+    result =
+      if agg_query == nil do
+        Native.run_search_with_aggregations(searcher, query, json_request, search_limit, sort)
+      else
+        Native.run_search_with_separate_aggregations(
+          searcher,
+          query,
+          agg_query,
+          json_request,
+          search_limit,
+          sort
+        )
+      end
+
+    case result do
       result when is_binary(result) ->
         if String.starts_with?(result, "Error") do
           {:error, result}
@@ -84,6 +84,7 @@ defmodule TantivyEx.CustomCollector do
            query_str
          ) do
       result when is_binary(result) -> {:ok, result}
+      {:error, reason} -> {:error, reason}
       error -> {:error, error}
     end
   end
@@ -29,7 +29,7 @@ defmodule TantivyEx.Document do
 
   Supports all Tantivy field types with proper type conversion:
 
-  - **Text**: String values with optional tokenization
+  - **Text**: String values with optional tokenization, or `pre_tokenized/2` values
   - **U64/I64/F64**: Numeric values with range validation
   - **Bool**: Boolean true/false values
   - **Date**: DateTime values (Unix timestamps or ISO strings)
@@ -68,9 +68,37 @@ defmodule TantivyEx.Document do
   require Logger
 
   @type document :: map()
+  @type token ::
+          {text :: String.t(), start :: non_neg_integer(), stop :: non_neg_integer(),
+           position :: non_neg_integer()}
+  @type pre_tokenized :: {:pre_tokenized, String.t(), [token()]}
   @type validation_error :: {:error, String.t()}
   @type batch_result :: {:ok, [any()]} | {:error, [{integer(), any()}]}
 
+  @doc """
+  Wraps externally tokenized text so it is indexed without tantivy's tokenizer.
+
+  Use it as the value of a text field, e.g. with tokens produced by an ML
+  pipeline. The tokens are indexed exactly as given while `text` is what gets
+  stored and returned in search results.
+
+  ## Parameters
+
+  - `text`: The original text
+  - `tokens`: `{token_text, start, end, position}` tuples, where `start` and
+    `end` are byte offsets into `text` and positions never decrease
+
+  ## Examples
+
+      iex> tokens = [{"new york", 0, 8, 0}, {"pizza", 9, 14, 1}]
+      iex> doc = %{"body" => TantivyEx.Document.pre_tokenized("New York pizza", tokens)}
+      iex> :ok = TantivyEx.IndexWriter.add_document(writer, doc)
+  """
+  @spec pre_tokenized(String.t(), [token()]) :: pre_tokenized()
+  def pre_tokenized(text, tokens) when is_binary(text) and is_list(tokens) do
+    {:pre_tokenized, text, tokens}
+  end
+
   # Document validation functions
 
   @doc """
@@ -149,6 +177,47 @@ defmodule TantivyEx.Document do
     end
   end
 
+  @doc """
+  Checks a document against the schema without converting or adding it.
+
+  Unlike `validate/2`, values must already have the type the index expects.
+  Fields listed as required must be present and not `nil`, which is what form
+  validation needs to report a missing field rather than only invalid ones.
+
+  ## Parameters
+
+  - `document`: Map containing field names and values
+  - `schema`: Schema reference to validate against
+  - `opts`: Validation options
+
+  ## Options
+
+  - `:required` - Field names that must be present in the document (default: [])
+
+  ## Returns
+
+  - `:ok` - Document is valid
+  - `{:error, reason}` - All problems found, joined with `"; "`
+
+  ## Examples
+
+      iex> TantivyEx.Document.check(%{"title" => "Test"}, schema, required: ["title"])
+      :ok
+      iex> TantivyEx.Document.check(%{}, schema, required: ["title"])
+      {:error, "Missing required field: 'title'"}
+  """
+  @spec check(document(), Schema.t(), keyword()) :: :ok | {:error, String.t()}
+  def check(document, schema, opts \\ []) when is_map(document) do
+    required = opts |> Keyword.get(:required, []) |> Enum.map(&to_string/1)
+
+    case Native.validate_document_against_schema(document, schema, required) do
+      :ok -> :ok
+      {:error, reason} -> {:error, reason}
+    end
+  rescue
+    e -> {:error, "Failed to check document: #{inspect(e)}"}
+  end
+
   # Document addition functions
 
   @doc """
@@ -533,6 +602,11 @@ defmodule TantivyEx.Document do
   end
 
   defp convert_to_string(value) when is_binary(value), do: {:ok, value}
+
+  defp convert_to_string({:pre_tokenized, text, tokens} = value)
+       when is_binary(text) and is_list(tokens),
+       do: {:ok, value}
+
   defp convert_to_string(value), do: {:ok, to_string(value)}
 
   defp convert_to_u64(value) when is_integer(value) and value >= 0, do: {:ok, value}
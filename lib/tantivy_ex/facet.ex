@@ -53,6 +53,7 @@ defmodule TantivyEx.Facet do
     case Native.facet_collector_for_field(field_name) do
       {:ok, collector_ref} -> {:ok, collector_ref}
       {:error, reason} -> {:error, reason}
+      collector_ref when is_reference(collector_ref) -> {:ok, collector_ref}
     end
   rescue
     e -> {:error, "Failed to create facet collector: #{inspect(e)}"}
@@ -84,6 +85,33 @@ defmodule TantivyEx.Facet do
     e -> {:error, "Failed to add facet: #{inspect(e)}"}
   end
 
+  @doc """
+  Adds several facet paths to the collector in one call.
+
+  All paths are validated first; if any is invalid, none are added.
+
+  ## Parameters
+  - `collector_ref` - Reference to the facet collector
+  - `facet_paths` - List of hierarchical facet paths
+
+  ## Returns
+  - `:ok` on success
+  - `{:error, reason}` on failure
+
+  ## Example
+      :ok = TantivyEx.Facet.add_facets(collector, ["/electronics", "/books", "/music"])
+  """
+  @spec add_facets(reference(), [String.t()]) :: :ok | {:error, String.t()}
+  def add_facets(collector_ref, facet_paths)
+      when is_reference(collector_ref) and is_list(facet_paths) do
+    case Native.facet_collector_add_facets(collector_ref, facet_paths) do
+      :ok -> :ok
+      {:error, reason} -> {:error, reason}
+    end
+  rescue
+    e -> {:error, "Failed to add facets: #{inspect(e)}"}
+  end
+
   @doc """
   Performs a search with facet collection.
 
@@ -116,6 +144,85 @@ defmodule TantivyEx.Facet do
     e -> {:error, "Failed to perform faceted search: #{inspect(e)}"}
   end
 
+  @doc """
+  Performs a search with facet collection, limited to `max_depth` levels.
+
+  Facets deeper than `max_depth` are not returned. When the collector did not
+  count their ancestor at `max_depth` itself, their counts are rolled up into
+  it, so a navigation UI can show one level and drill down on demand.
+
+  ## Parameters
+  - `searcher_ref` - Reference to the searcher
+  - `query_ref` - Reference to the query
+  - `collector_ref` - Reference to the facet collector
+  - `max_depth` - Maximum number of facet levels to return (at least 1)
+
+  ## Returns
+  - `{:ok, facet_counts}` on success where facet_counts maps facet paths to counts
+  - `{:error, reason}` on failure
+
+  ## Example
+      {:ok, %{"/electronics" => 12}} =
+        TantivyEx.Facet.search_depth(searcher, query, collector, 1)
+  """
+  @spec search_depth(reference(), reference(), reference(), pos_integer()) ::
+          {:ok, map()} | {:error, String.t()}
+  def search_depth(searcher_ref, query_ref, collector_ref, max_depth)
+      when is_reference(searcher_ref) and is_reference(query_ref) and is_reference(collector_ref) and
+             is_integer(max_depth) and max_depth >= 1 do
+    case Native.facet_search_depth(searcher_ref, query_ref, collector_ref, max_depth) do
+      {:error, reason} ->
+        {:error, reason}
+
+      results_json when is_binary(results_json) ->
+        case Jason.decode(results_json) do
+          {:ok, results} -> {:ok, results}
+          {:error, _} -> {:error, "Failed to parse facet results"}
+        end
+    end
+  rescue
+    e -> {:error, "Failed to perform faceted search: #{inspect(e)}"}
+  end
+
+  @doc """
+  Counts the direct children of a facet path in a single call.
+
+  Builds the facet collector internally, covering the common case of showing
+  the counts under one facet without creating and configuring a collector.
+
+  ## Parameters
+  - `searcher_ref` - Reference to the searcher
+  - `query_ref` - Reference to the query
+  - `field_name` - Name of the facet field
+  - `base_path` - Facet path whose children are counted (default: `"/"`)
+
+  ## Returns
+  - `{:ok, facet_counts}` on success where facet_counts maps child facet paths to counts
+  - `{:error, reason}` on failure
+
+  ## Example
+      {:ok, %{"/category/books" => 3, "/category/electronics" => 12}} =
+        TantivyEx.Facet.counts(searcher, query, "category", "/category")
+  """
+  @spec counts(reference(), reference(), String.t(), String.t()) ::
+          {:ok, map()} | {:error, String.t()}
+  def counts(searcher_ref, query_ref, field_name, base_path \\ "/")
+      when is_reference(searcher_ref) and is_reference(query_ref) and is_binary(field_name) and
+             is_binary(base_path) do
+    case Native.facet_counts(searcher_ref, query_ref, field_name, base_path) do
+      {:error, reason} ->
+        {:error, reason}
+
+      results_json when is_binary(results_json) ->
+        case Jason.decode(results_json) do
+          {:ok, results} -> {:ok, results}
+          {:error, _} -> {:error, "Failed to parse facet results"}
+        end
+    end
+  rescue
+    e -> {:error, "Failed to count facets: #{inspect(e)}"}
+  end
+
   @doc """
   Gets the top K facets for a given facet path.
 
@@ -319,4 +426,57 @@ defmodule TantivyEx.Facet do
   rescue
     e -> {:error, "Failed to convert facet to string: #{inspect(e)}"}
   end
+
+  @doc """
+  Builds a facet path from unescaped segments.
+
+  Segments may contain `/` (and `\\`), which are escaped so they stay within a
+  single level instead of silently splitting the category. The result can be used
+  anywhere a facet path is accepted, including documents and facet queries.
+
+  ## Parameters
+  - `segments` - List of non-empty segment strings, from the top level down
+
+  ## Returns
+  - `{:ok, facet_path}` on success
+  - `{:error, reason}` on failure
+
+  ## Example
+      {:ok, path} = TantivyEx.Facet.encode(["music", "AC/DC"])
+      # path == "/music/AC\\\\/DC"
+  """
+  @spec encode([String.t()]) :: {:ok, String.t()} | {:error, String.t()}
+  def encode(segments) when is_list(segments) do
+    case Native.facet_encode(segments) do
+      {:error, reason} -> {:error, reason}
+      facet_path when is_binary(facet_path) -> {:ok, facet_path}
+    end
+  rescue
+    e -> {:error, "Failed to encode facet: #{inspect(e)}"}
+  end
+
+  @doc """
+  Splits a facet path into its unescaped segments, the inverse of `encode/1`.
+
+  The root facet `"/"` decodes to an empty list.
+
+  ## Parameters
+  - `facet_path` - The escaped facet path string
+
+  ## Returns
+  - `{:ok, segments}` on success
+  - `{:error, reason}` on failure
+
+  ## Example
+      {:ok, ["music", "AC/DC"]} = TantivyEx.Facet.decode("/music/AC\\\\/DC")
+  """
+  @spec decode(String.t()) :: {:ok, [String.t()]} | {:error, String.t()}
+  def decode(facet_path) when is_binary(facet_path) do
+    case Native.facet_decode(facet_path) do
+      {:error, reason} -> {:error, reason}
+      segments when is_list(segments) -> {:ok, segments}
+    end
+  rescue
+    e -> {:error, "Failed to decode facet: #{inspect(e)}"}
+  end
 end
@@ -10,6 +10,10 @@ defmodule TantivyEx.Index do
 
   @type t :: reference()
 
+  @sort_by_field_unsupported "Index-time sorting (sort_by_field) is not supported: " <>
+                               "tantivy 0.24 removed index sorting, sort on a fast field " <>
+                               "at query time instead"
+
   @doc """
   Creates a new index in the specified directory.
 
@@ -39,6 +43,51 @@ defmodule TantivyEx.Index do
     e -> {:error, "Failed to create index: #{inspect(e)}"}
   end
 
+  @doc """
+  Creates a new index in the specified directory with custom index settings.
+
+  Stored fields are compressed in blocks by the document store. For text-heavy
+  indexes a higher-ratio codec can save significant disk space at the cost of
+  CPU time when storing and retrieving documents.
+
+  ## Parameters
+
+  - `path`: The filesystem path where the index should be created
+  - `schema`: The schema defining the structure of documents
+  - `opts`: Index settings
+
+  ## Options
+
+  - `:docstore_compression` - Compression codec for stored fields:
+    `:lz4` (default), `:zstd`, `{:zstd, level}` or `:none`
+  - `:docstore_blocksize` - Size in bytes of each compressed block (default: 16384).
+    Larger blocks compress better but make single document retrieval slower.
+  - `:sort_by_field` - Not supported. Index-time sorting was removed in tantivy
+    0.24, so passing it returns an error; sort at query time on a fast field
+    instead (e.g. `TantivyEx.Searcher.search_collapsed/4` or an aggregation).
+
+  ## Examples
+
+      iex> {:ok, index} =
+      ...>   TantivyEx.Index.create_in_dir("/tmp/my_index", schema,
+      ...>     docstore_compression: {:zstd, 9},
+      ...>     docstore_blocksize: 65_536
+      ...>   )
+      iex> is_reference(index)
+      true
+  """
+  @spec create_in_dir(String.t(), Schema.t(), keyword()) :: {:ok, t()} | {:error, String.t()}
+  def create_in_dir(path, schema, opts) when is_list(opts) do
+    with {:ok, settings} <- build_settings(opts) do
+      case Native.index_create_in_dir_with_settings(path, schema, Jason.encode!(settings)) do
+        {:error, reason} -> {:error, reason}
+        index -> {:ok, index}
+      end
+    end
+  rescue
+    e -> {:error, "Failed to create index: #{inspect(e)}"}
+  end
+
   @doc """
   Creates a new index in RAM.
 
@@ -93,6 +142,58 @@ defmodule TantivyEx.Index do
     e -> {:error, "Failed to open index: #{inspect(e)}"}
   end
 
+  @doc """
+  Opens an existing index at the specified path for searching only.
+
+  The index never creates or takes lock files, so search-only replicas on a
+  shared (possibly read-only) volume can open it even while an indexer node
+  holds the writer lock. Creating an `IndexWriter` on the returned index fails.
+
+  ## Parameters
+
+  - `path`: The filesystem path where the existing index is located
+
+  ## Examples
+
+      iex> {:ok, index} = TantivyEx.Index.open_read_only("/mnt/replica/index")
+      iex> {:ok, searcher} = TantivyEx.Searcher.new(index)
+  """
+  @spec open_read_only(String.t()) :: {:ok, t()} | {:error, String.t()}
+  def open_read_only(path) when is_binary(path) do
+    case Native.index_open_read_only(path) do
+      {:error, reason} -> {:error, reason}
+      index -> {:ok, index}
+    end
+  rescue
+    e -> {:error, "Failed to open index read-only: #{inspect(e)}"}
+  end
+
+  @doc """
+  Checks whether an index exists at the specified path.
+
+  This is a cheap, non-raising probe that only looks for the index metadata
+  file. It returns `false` when the directory doesn't exist or isn't an index,
+  which makes it suitable for deciding between `open/1` and `create_in_dir/2`.
+
+  ## Parameters
+
+  - `path`: The filesystem path to check
+
+  ## Examples
+
+      iex> TantivyEx.Index.exists?("/tmp/my_index")
+      true
+
+      iex> TantivyEx.Index.exists?("/tmp/not_an_index")
+      false
+  """
+  @spec exists?(String.t()) :: boolean()
+  def exists?(path) when is_binary(path) do
+    Native.index_exists_in_dir(path)
+  rescue
+    _ -> false
+  end
+
   @doc """
   Opens an existing index at the specified path, or creates it if it doesn't exist.
 
@@ -127,4 +228,183 @@ defmodule TantivyEx.Index do
   rescue
     e -> {:error, "Failed to open or create index: #{inspect(e)}"}
   end
+
+  @doc """
+  Serializes the committed state of an index into a binary.
+
+  The binary contains the index metadata and all files of the searchable
+  segments. Uncommitted documents are not included. Together with
+  `open_from_bytes/2` this allows shipping a small prebuilt index (for example
+  a synonym or stopword dictionary) inside an application release.
+
+  ## Parameters
+
+  - `index`: The index to serialize
+
+  ## Examples
+
+      iex> {:ok, bytes} = TantivyEx.Index.save_to_bytes(index)
+      iex> is_binary(bytes)
+      true
+  """
+  @spec save_to_bytes(t()) :: {:ok, binary()} | {:error, String.t()}
+  def save_to_bytes(index) do
+    case Native.index_save_to_bytes(index) do
+      {:error, reason} -> {:error, reason}
+      bytes when is_binary(bytes) -> {:ok, bytes}
+    end
+  rescue
+    e -> {:error, "Failed to save index to bytes: #{inspect(e)}"}
+  end
+
+  @doc """
+  Opens an index from a binary produced by `save_to_bytes/1`.
+
+  The index is loaded into RAM and never touches the disk. The schema stored
+  in the snapshot must match the given schema.
+
+  ## Parameters
+
+  - `bytes`: The serialized index
+  - `schema`: The schema the index was created with
+
+  ## Examples
+
+      iex> {:ok, bytes} = TantivyEx.Index.save_to_bytes(index)
+      iex> {:ok, restored} = TantivyEx.Index.open_from_bytes(bytes, schema)
+      iex> is_reference(restored)
+      true
+  """
+  @spec open_from_bytes(binary(), Schema.t()) :: {:ok, t()} | {:error, String.t()}
+  def open_from_bytes(bytes, schema) when is_binary(bytes) do
+    case Native.index_open_from_bytes(bytes, schema) do
+      {:error, reason} -> {:error, reason}
+      index -> {:ok, index}
+    end
+  rescue
+    e -> {:error, "Failed to open index from bytes: #{inspect(e)}"}
+  end
+
+  @doc """
+  Copies the last committed state of an index into another directory.
+
+  Only the files referenced by the current commit are copied, and `meta.json`
+  is written last, so the destination is a consistent index that can be opened
+  with `open/1` even if merges or commits run concurrently. Use it for backups
+  instead of copying the index directory by hand.
+
+  ## Parameters
+
+  - `index`: The index to snapshot (on disk or in RAM)
+  - `dest_path`: Directory to copy the index into; created if missing and must
+    not already contain an index
+
+  ## Examples
+
+      iex> {:ok, snapshot} = TantivyEx.Index.snapshot(index, "/backups/products-2024-05-01")
+      iex> snapshot["segment_count"]
+      3
+      iex> {:ok, backup} = TantivyEx.Index.open("/backups/products-2024-05-01")
+  """
+  @spec snapshot(t(), String.t()) :: {:ok, map()} | {:error, String.t()}
+  def snapshot(index, dest_path) when is_binary(dest_path) do
+    case Native.index_snapshot(index, dest_path) do
+      {:error, reason} ->
+        {:error, reason}
+
+      json when is_binary(json) ->
+        case Jason.decode(json) do
+          {:ok, snapshot} -> {:ok, snapshot}
+          {:error, _} -> {:error, "Failed to parse snapshot result"}
+        end
+    end
+  rescue
+    e -> {:error, "Failed to snapshot index: #{inspect(e)}"}
+  end
+
+  @doc """
+  Describes an opened index.
+
+  Useful when loading an index directory of unknown shape: the returned map
+  lists the schema fields, the index settings, the opstamp of the last commit
+  and the number of live documents.
+
+  ## Parameters
+
+  - `index`: The index to inspect
+
+  ## Examples
+
+      iex> {:ok, metadata} = TantivyEx.Index.metadata(index)
+      iex> metadata["directory_type"]
+      "ram"
+      iex> Enum.map(metadata["fields"], & &1["name"])
+      ["title", "body"]
+  """
+  @spec metadata(t()) :: {:ok, map()} | {:error, String.t()}
+  def metadata(index) do
+    case Native.index_metadata(index) do
+      {:error, reason} -> {:error, reason}
+      json when is_binary(json) -> Jason.decode(json)
+    end
+  rescue
+    e -> {:error, "Failed to read index metadata: #{inspect(e)}"}
+  end
+
+  @doc """
+  Reads the committed index metas.
+
+  Returns the opstamp of the last commit along with the ids of the committed
+  segments. Both change whenever a new commit lands, so a replica or cache can
+  poll this cheaply and reload only when the generation moved.
+
+  ## Parameters
+
+  - `index`: The index to inspect
+
+  ## Examples
+
+      iex> {:ok, metas} = TantivyEx.Index.load_metas(index)
+      iex> Map.keys(metas)
+      ["opstamp", "segment_count", "segment_ids"]
+  """
+  @spec load_metas(t()) :: {:ok, map()} | {:error, String.t()}
+  def load_metas(index) do
+    case Native.index_load_metas(index) do
+      {:error, reason} -> {:error, reason}
+      json when is_binary(json) -> Jason.decode(json)
+    end
+  rescue
+    e -> {:error, "Failed to load index metas: #{inspect(e)}"}
+  end
+
+  defp build_settings(opts) do
+    Enum.reduce_while(opts, {:ok, %{}}, fn
+      {:docstore_compression, compression}, {:ok, acc} ->
+        case compression_to_string(compression) do
+          {:ok, value} -> {:cont, {:ok, Map.put(acc, "docstore_compression", value)}}
+          {:error, _} = error -> {:halt, error}
+        end
+
+      {:docstore_blocksize, blocksize}, {:ok, acc}
+      when is_integer(blocksize) and blocksize > 0 ->
+        {:cont, {:ok, Map.put(acc, "docstore_blocksize", blocksize)}}
+
+      {:sort_by_field, _sort}, _acc ->
+        {:halt, {:error, @sort_by_field_unsupported}}
+
+      {key, value}, _acc ->
+        {:halt, {:error, "Invalid index setting #{inspect(key)}: #{inspect(value)}"}}
+    end)
+  end
+
+  defp compression_to_string(:none), do: {:ok, "none"}
+  defp compression_to_string(:lz4), do: {:ok, "lz4"}
+  defp compression_to_string(:zstd), do: {:ok, "zstd"}
+
+  defp compression_to_string({:zstd, level}) when is_integer(level),
+    do: {:ok, "zstd(compression_level=#{level})"}
+
+  defp compression_to_string(other),
+    do: {:error, "Unsupported docstore compression: #{inspect(other)}"}
 end
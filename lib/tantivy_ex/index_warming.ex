@@ -85,6 +85,26 @@ defmodule TantivyEx.IndexWarming do
     end
   end
 
+  @spec warm_fast_fields(warming_resource(), reference(), [String.t()], String.t()) ::
+          {:ok, non_neg_integer()} | {:error, term()}
+  def warm_fast_fields(warming_resource, index_resource, field_names, cache_key) do
+    try do
+      case Native.index_warming_warm_fast_fields(
+             warming_resource,
+             index_resource,
+             field_names,
+             cache_key
+           ) do
+        bytes_warmed when is_integer(bytes_warmed) -> {:ok, bytes_warmed}
+        {:error, reason} -> {:error, reason}
+        error -> {:error, error}
+      end
+    rescue
+      ArgumentError -> {:error, :invalid_parameters}
+      ErlangError -> {:error, :not_implemented}
+    end
+  end
+
   @spec get_searcher(warming_resource(), String.t()) :: {:ok, reference()} | {:error, term()}
   def get_searcher(warming_resource, cache_key) do
     try do
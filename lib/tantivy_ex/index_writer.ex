@@ -13,10 +13,20 @@ defmodule TantivyEx.IndexWriter do
   @doc """
   Creates a new IndexWriter for the given index.
 
+  Only one writer can hold an index's writer lock at a time. By default a busy
+  lock fails immediately; set `:lock_timeout_ms` to keep retrying, for example
+  when a restarted indexer races the shutdown of its previous writer.
+
   ## Parameters
 
   - `index`: The index to write to
   - `memory_budget`: Memory budget in bytes (default: 50MB)
+  - `opts`: Keyword list of options
+
+  ## Options
+
+  - `:lock_timeout_ms` - How long to retry acquiring the writer lock before
+    failing (default: no retry)
 
   ## Examples
 
@@ -26,14 +36,72 @@ defmodule TantivyEx.IndexWriter do
       iex> {:ok, writer} = TantivyEx.IndexWriter.new(index)
       iex> is_reference(writer)
       true
+
+      iex> {:ok, writer} = TantivyEx.IndexWriter.new(index, 50_000_000, lock_timeout_ms: 5_000)
   """
-  @spec new(Index.t(), pos_integer()) :: {:ok, t()} | {:error, String.t()}
-  def new(index, memory_budget \\ 50_000_000) do
+  @spec new(Index.t(), pos_integer(), keyword()) :: {:ok, t()} | {:error, String.t()}
+  def new(index, memory_budget \\ 50_000_000, opts \\ []) do
     # Ensure minimum memory budget (Tantivy requires at least 15MB)
     min_budget = 15_000_000
     actual_budget = max(memory_budget, min_budget)
 
-    case Native.index_writer(index, actual_budget) do
+    with {:ok, lock_timeout_ms} <- lock_timeout_option(opts) do
+      case Native.index_writer(index, actual_budget, lock_timeout_ms) do
+        {:error, reason} -> {:error, reason}
+        writer -> {:ok, writer}
+      end
+    end
+  rescue
+    e -> {:error, "Failed to create index writer: #{inspect(e)}"}
+  end
+
+  defp lock_timeout_option(opts) do
+    case Keyword.get(opts, :lock_timeout_ms) do
+      nil -> {:ok, nil}
+      timeout_ms when is_integer(timeout_ms) and timeout_ms >= 0 -> {:ok, timeout_ms}
+      other -> {:error, "Invalid :lock_timeout_ms option: #{inspect(other)}"}
+    end
+  end
+
+  @doc """
+  Creates a new IndexWriter with an explicit number of indexing threads.
+
+  By default Tantivy picks the thread count from the number of available cores.
+  Use this to pin indexing to a single thread in small containers, or to raise
+  parallelism for bulk loads.
+
+  The memory budget is divided evenly across threads: each thread receives
+  `memory_budget / num_threads` bytes and flushes a segment when it fills up.
+  Tantivy requires at least 15MB per thread, so the budget is raised to
+  `num_threads * 15MB` when it is lower than that.
+
+  ## Parameters
+
+  - `index`: The index to write to
+  - `num_threads`: Number of indexing threads (must be at least 1)
+  - `memory_budget`: Total memory budget in bytes, shared by all threads (default: 50MB)
+
+  ## Examples
+
+      iex> {:ok, writer} = TantivyEx.IndexWriter.new_with_threads(index, 1)
+      iex> is_reference(writer)
+      true
+
+      iex> {:ok, writer} = TantivyEx.IndexWriter.new_with_threads(index, 4, 200_000_000)
+      iex> is_reference(writer)
+      true
+  """
+  @spec new_with_threads(Index.t(), pos_integer(), pos_integer()) ::
+          {:ok, t()} | {:error, String.t()}
+  def new_with_threads(index, num_threads, memory_budget \\ 50_000_000)
+
+  def new_with_threads(index, num_threads, memory_budget)
+      when is_integer(num_threads) and num_threads >= 1 do
+    # Tantivy requires at least 15MB per indexing thread
+    min_budget = 15_000_000 * num_threads
+    actual_budget = max(memory_budget, min_budget)
+
+    case Native.index_writer_with_threads(index, actual_budget, num_threads) do
       {:error, reason} -> {:error, reason}
       writer -> {:ok, writer}
     end
@@ -41,6 +109,10 @@ defmodule TantivyEx.IndexWriter do
     e -> {:error, "Failed to create index writer: #{inspect(e)}"}
   end
 
+  def new_with_threads(_index, num_threads, _memory_budget) do
+    {:error, "Number of indexing threads must be at least 1, got: #{inspect(num_threads)}"}
+  end
+
   @doc """
   Adds a document to the index.
 
@@ -71,6 +143,42 @@ defmodule TantivyEx.IndexWriter do
     e -> {:error, "Failed to add document: #{inspect(e)}"}
   end
 
+  @doc """
+  Adds a document, returning an error for any value that does not fit its field type.
+
+  `add_document/2` skips values of the wrong type; this variant reports them
+  instead. With `coerce: true`, strings are parsed for numeric fields and
+  `"true"`/`"false"` are accepted for boolean fields before giving up, which
+  helps when ingesting loosely typed data such as CSV rows.
+
+  ## Parameters
+
+  - `writer`: The IndexWriter
+  - `document`: Map of field names to values
+  - `opts`: Options
+
+  ## Options
+
+  - `:coerce` - Parse string values into numbers and booleans (default: false)
+
+  ## Examples
+
+      iex> doc = %{"title" => "Hello", "id" => "42", "published" => "true"}
+      iex> :ok = TantivyEx.IndexWriter.add_document_checked(writer, doc, coerce: true)
+      iex> {:error, reason} = TantivyEx.IndexWriter.add_document_checked(writer, doc)
+  """
+  @spec add_document_checked(t(), map(), keyword()) :: :ok | {:error, String.t()}
+  def add_document_checked(writer, document, opts \\ []) when is_map(document) do
+    coerce = Keyword.get(opts, :coerce, false)
+
+    case Native.writer_add_document_checked(writer, document, coerce) do
+      :ok -> :ok
+      {:error, reason} -> {:error, reason}
+    end
+  rescue
+    e -> {:error, "Failed to add document: #{inspect(e)}"}
+  end
+
   @doc """
   Commits all pending changes to the index.
 
@@ -98,12 +206,114 @@ defmodule TantivyEx.IndexWriter do
     e -> {:error, "Failed to commit: #{inspect(e)}"}
   end
 
+  @doc """
+  Commits all pending changes and returns the commit opstamp.
+
+  The opstamp identifies the commit and increases with every operation, which
+  makes it possible to tell whether a searcher reflects a given write. Use
+  `TantivyEx.Searcher.reload/1` after committing to read your own writes.
+
+  ## Parameters
+
+  - `writer`: The IndexWriter
+
+  ## Examples
+
+      iex> {:ok, opstamp} = TantivyEx.IndexWriter.commit_with_opstamp(writer)
+      iex> is_integer(opstamp)
+      true
+  """
+  @spec commit_with_opstamp(t()) :: {:ok, non_neg_integer()} | {:error, String.t()}
+  def commit_with_opstamp(writer) do
+    case Native.writer_commit_with_opstamp(writer) do
+      {:error, reason} -> {:error, reason}
+      opstamp when is_integer(opstamp) -> {:ok, opstamp}
+    end
+  rescue
+    e -> {:error, "Failed to commit: #{inspect(e)}"}
+  end
+
+  @doc """
+  Commits pending changes without waiting for them to be persisted.
+
+  Pending documents are flushed into segments before this returns, but
+  publishing the commit, including the fsync of the index metadata, happens
+  in the background. This cuts commit latency for high-ingest workloads at the
+  cost of durability: a crash before the background step finishes loses the
+  commit. The next `commit/1` or `commit_with_opstamp/1` also waits for it.
+
+  When the background step finishes, `pid` receives
+  `{:tantivy_commit, opstamp, :ok}`, or `{:tantivy_commit, opstamp, {:error, reason}}`
+  if the commit failed. The documents are visible to readers reloaded after the
+  `:ok` message.
+
+  Tantivy has no commit mode that skips fsync entirely, so this trades waiting
+  time rather than I/O.
+
+  ## Parameters
+
+  - `writer`: The IndexWriter
+  - `pid`: Process notified when the commit completes (default: `self()`)
+
+  ## Examples
+
+      iex> {:ok, opstamp} = TantivyEx.IndexWriter.commit_soft(writer)
+      iex> receive do
+      ...>   {:tantivy_commit, ^opstamp, result} -> result
+      ...> end
+      :ok
+  """
+  @spec commit_soft(t(), pid()) :: {:ok, non_neg_integer()} | {:error, String.t()}
+  def commit_soft(writer, pid \\ self()) when is_pid(pid) do
+    case Native.writer_commit_soft(writer, pid) do
+      {:error, reason} -> {:error, reason}
+      opstamp when is_integer(opstamp) -> {:ok, opstamp}
+    end
+  rescue
+    e -> {:error, "Failed to commit: #{inspect(e)}"}
+  end
+
+  @doc """
+  Commits all pending changes and returns a searcher that sees them.
+
+  The index's shared reader (the one behind `TantivyEx.Searcher.acquire/1`) is
+  reloaded before the writer is released, so the returned searcher always
+  includes this commit. Prefer it over committing and creating a searcher in
+  two steps, which can search a stale state.
+
+  ## Parameters
+
+  - `writer`: The IndexWriter
+  - `index`: The index the writer belongs to
+
+  ## Examples
+
+      iex> :ok = TantivyEx.IndexWriter.add_document(writer, %{"title" => "Fresh"})
+      iex> {:ok, searcher} = TantivyEx.IndexWriter.commit_and_searcher(writer, index)
+      iex> {:ok, query} = TantivyEx.Query.term(schema, "title", "fresh")
+      iex> {:ok, [_]} = TantivyEx.Searcher.search(searcher, query, 10)
+  """
+  @spec commit_and_searcher(t(), TantivyEx.Index.t()) ::
+          {:ok, TantivyEx.Searcher.t()} | {:error, String.t()}
+  def commit_and_searcher(writer, index) do
+    case Native.writer_commit_and_searcher(writer, index) do
+      {:error, reason} -> {:error, reason}
+      searcher -> {:ok, searcher}
+    end
+  rescue
+    e -> {:error, "Failed to commit: #{inspect(e)}"}
+  end
+
   @doc """
   Deletes all documents matching the given query.
 
   This operation marks documents for deletion but does not make the
   deletions visible until the writer is committed.
 
+  Returns the number of documents matching the query in the last commit,
+  which is the number of documents removed once the writer is committed.
+  Documents added since the last commit are deleted too but not counted.
+
   ## Parameters
 
   - `writer`: The IndexWriter
@@ -111,21 +321,122 @@ defmodule TantivyEx.IndexWriter do
 
   ## Examples
 
-      iex> query = TantivyEx.Query.term(schema, "status", "inactive")
-      iex> :ok = TantivyEx.IndexWriter.delete_documents(writer, query)
-      :ok
+      iex> {:ok, query} = TantivyEx.Query.term(schema, "status", "inactive")
+      iex> {:ok, deleted_count} = TantivyEx.IndexWriter.delete_documents(writer, query)
+      iex> is_integer(deleted_count)
+      true
   """
-  @spec delete_documents(t(), Query.t()) :: :ok | {:error, String.t()}
+  @spec delete_documents(t(), Query.t()) :: {:ok, non_neg_integer()} | {:error, String.t()}
   def delete_documents(writer, query) do
     case Native.writer_delete_documents(writer, query) do
-      :ok -> :ok
       {:error, reason} -> {:error, reason}
-      _ -> :ok
+      deleted_count when is_integer(deleted_count) -> {:ok, deleted_count}
     end
   rescue
     e -> {:error, "Failed to delete documents: #{inspect(e)}"}
   end
 
+  @doc """
+  Deletes all documents whose date field is strictly before `timestamp`.
+
+  A retention helper: builds a range query with no lower bound and an excluded
+  upper bound at `timestamp` on `date_field`, which must be an indexed or fast
+  date field. Returns the number of matching documents in the last commit, like
+  `delete_documents/2`. Deletions become visible once the writer is committed,
+  so documents already queued for deletion by an earlier uncommitted call are
+  counted again.
+
+  ## Parameters
+
+  - `writer`: The IndexWriter
+  - `date_field`: Name of the date field to compare
+  - `timestamp`: A `DateTime` or unix timestamp in seconds
+
+  ## Examples
+
+      iex> cutoff = DateTime.add(DateTime.utc_now(), -30, :day)
+      iex> {:ok, deleted_count} = TantivyEx.IndexWriter.delete_before(writer, "published_at", cutoff)
+      iex> is_integer(deleted_count)
+      true
+  """
+  @spec delete_before(t(), String.t(), DateTime.t() | integer()) ::
+          {:ok, non_neg_integer()} | {:error, String.t()}
+  def delete_before(writer, date_field, %DateTime{} = timestamp) do
+    delete_before(writer, date_field, DateTime.to_unix(timestamp))
+  end
+
+  def delete_before(writer, date_field, timestamp) when is_integer(timestamp) do
+    case Native.writer_delete_before(writer, date_field, timestamp) do
+      {:error, reason} -> {:error, reason}
+      deleted_count when is_integer(deleted_count) -> {:ok, deleted_count}
+    end
+  rescue
+    e -> {:error, "Failed to delete documents before timestamp: #{inspect(e)}"}
+  end
+
+  @doc """
+  Deletes all documents with a term in `field_name` matching a regex.
+
+  The pattern must match a whole indexed term, so for tokenized text fields it is
+  matched against individual lowercased tokens. To match whole values such as ids,
+  index the field with the `"raw"` tokenizer. Returns the number of matching
+  documents in the last commit, like `delete_documents/2`. Deletions become
+  visible once the writer is committed.
+
+  ## Parameters
+
+  - `writer`: The IndexWriter
+  - `field_name`: Name of the indexed text field to match
+  - `pattern`: Regular expression a term must fully match
+
+  ## Examples
+
+      iex> {:ok, deleted_count} = TantivyEx.IndexWriter.delete_regex(writer, "sku", "test-.*")
+      iex> is_integer(deleted_count)
+      true
+  """
+  @spec delete_regex(t(), String.t(), String.t()) ::
+          {:ok, non_neg_integer()} | {:error, String.t()}
+  def delete_regex(writer, field_name, pattern) when is_binary(pattern) do
+    case Native.writer_delete_regex(writer, field_name, pattern) do
+      {:error, reason} -> {:error, reason}
+      deleted_count when is_integer(deleted_count) -> {:ok, deleted_count}
+    end
+  rescue
+    e -> {:error, "Failed to delete documents by regex: #{inspect(e)}"}
+  end
+
+  @doc """
+  Deletes all documents matching any of the given values of a field.
+
+  All deletions are queued under a single writer lock, which is much cheaper
+  than deleting one value at a time when purging many records by primary key.
+  Values are coerced to the field type the same way as for single-term deletion.
+  Deletions become visible once the writer is committed.
+
+  ## Parameters
+
+  - `writer`: The IndexWriter
+  - `term_field`: Field name to match (e.g., "id")
+  - `term_values`: List of values to delete
+
+  ## Examples
+
+      iex> :ok = TantivyEx.IndexWriter.delete_terms(writer, "id", [1, 2, 3])
+      :ok
+      iex> :ok = TantivyEx.IndexWriter.commit(writer)
+      :ok
+  """
+  @spec delete_terms(t(), String.t(), [term()]) :: :ok | {:error, String.t()}
+  def delete_terms(writer, term_field, term_values) when is_list(term_values) do
+    case Native.writer_delete_terms(writer, term_field, term_values) do
+      :ok -> :ok
+      {:error, reason} -> {:error, reason}
+    end
+  rescue
+    e -> {:error, "Failed to delete terms: #{inspect(e)}"}
+  end
+
   @doc """
   Deletes all documents in the index.
 
@@ -179,4 +490,135 @@ defmodule TantivyEx.IndexWriter do
   rescue
     e -> {:error, "Failed to rollback: #{inspect(e)}"}
   end
+
+  @doc """
+  Deletes index files that are no longer referenced.
+
+  Merges and deletions leave orphaned segment files on disk. Tantivy cleans them
+  up opportunistically, but long-running indexing processes (especially those
+  using `TantivyEx.MergePolicy.no_merge_policy/0`) can call this to reclaim space
+  without restarting. Files still held open by a searcher are reported as
+  failed and will be removed by a later collection.
+
+  ## Parameters
+
+  - `writer`: The IndexWriter
+
+  ## Examples
+
+      iex> {:ok, %{"deleted_count" => count}} = TantivyEx.IndexWriter.garbage_collect_files(writer)
+      iex> is_integer(count)
+      true
+  """
+  @spec garbage_collect_files(t()) :: {:ok, map()} | {:error, String.t()}
+  def garbage_collect_files(writer) do
+    case Native.index_garbage_collect_files(writer) do
+      {:error, reason} ->
+        {:error, reason}
+
+      result_json when is_binary(result_json) ->
+        case Jason.decode(result_json) do
+          {:ok, result} -> {:ok, result}
+          {:error, _} -> {:error, "Failed to parse garbage collection result"}
+        end
+    end
+  rescue
+    e -> {:error, "Failed to garbage collect files: #{inspect(e)}"}
+  end
+
+  @doc """
+  Reports the writer's memory budget and how much work is buffered.
+
+  Tantivy does not expose how full its indexing arena is, so buffered work is
+  reported as the number of documents added since the last commit or
+  rollback. Bulk importers can use it to commit adaptively, e.g. once
+  `pending_documents` crosses a threshold derived from `memory_budget`.
+
+  Returns a map with:
+
+  - `"memory_budget"` - bytes the writer was created with (shared by all threads)
+  - `"num_threads"` - indexing threads, or `nil` when tantivy chose the count
+  - `"pending_documents"` - documents added since the last commit or rollback
+  - `"commit_opstamp"` - opstamp of the last commit
+
+  ## Parameters
+
+  - `writer`: The IndexWriter
+
+  ## Examples
+
+      iex> {:ok, %{"pending_documents" => pending}} = TantivyEx.IndexWriter.status(writer)
+      iex> is_integer(pending)
+      true
+  """
+  @spec status(t()) :: {:ok, map()} | {:error, String.t()}
+  def status(writer) do
+    case Native.writer_status(writer) do
+      {:error, reason} -> {:error, reason}
+      json when is_binary(json) -> Jason.decode(json)
+    end
+  rescue
+    e -> {:error, "Failed to get writer status: #{inspect(e)}"}
+  end
+
+  @doc """
+  Optimizes the index in one step.
+
+  Commits pending changes, merges the smallest segments together until at most
+  `max_segments` remain, waits for the merge to finish and then deletes files
+  that are no longer referenced.
+
+  ## Parameters
+
+  - `writer`: The IndexWriter
+  - `max_segments`: Maximum number of segments to keep (default: 1)
+
+  ## Examples
+
+      iex> {:ok, summary} = TantivyEx.IndexWriter.optimize(writer, 1)
+      iex> summary["segments_after"]
+      1
+  """
+  @spec optimize(t(), pos_integer()) :: {:ok, map()} | {:error, String.t()}
+  def optimize(writer, max_segments \\ 1) when is_integer(max_segments) and max_segments >= 1 do
+    case Native.index_optimize(writer, max_segments) do
+      {:error, reason} -> {:error, reason}
+      json when is_binary(json) -> Jason.decode(json)
+    end
+  rescue
+    e -> {:error, "Failed to optimize index: #{inspect(e)}"}
+  end
+
+  @doc """
+  Reclaims the space held by deleted documents in one step.
+
+  Commits pending changes, then merges every segment whose share of deleted
+  documents exceeds `min_deleted_ratio` into a segment holding only its live
+  documents, waits for the merge and deletes files that are no longer
+  referenced. Use it after a large `delete_documents/2`, where tantivy would
+  otherwise keep the deleted documents on disk until a merge happens to pick
+  their segments.
+
+  ## Parameters
+
+  - `writer`: The IndexWriter
+  - `min_deleted_ratio`: Deleted/total documents ratio a segment must exceed to
+    be rewritten, between `0.0` and `1.0` (default: `0.1`)
+
+  ## Examples
+
+      iex> {:ok, summary} = TantivyEx.IndexWriter.expunge_deleted(writer)
+      iex> summary["deleted_docs_reclaimed"]
+      1200
+  """
+  @spec expunge_deleted(t(), float()) :: {:ok, map()} | {:error, String.t()}
+  def expunge_deleted(writer, min_deleted_ratio \\ 0.1)
+      when is_number(min_deleted_ratio) and min_deleted_ratio >= 0 and min_deleted_ratio <= 1 do
+    case Native.writer_expunge_deleted(writer, min_deleted_ratio / 1) do
+      {:error, reason} -> {:error, reason}
+      json when is_binary(json) -> Jason.decode(json)
+    end
+  rescue
+    e -> {:error, "Failed to expunge deleted documents: #{inspect(e)}"}
+  end
 end
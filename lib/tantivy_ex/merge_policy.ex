@@ -323,4 +323,33 @@ defmodule TantivyEx.MergePolicy do
       error -> error
     end
   end
+
+  @doc """
+  Lists the document counts of every searchable segment in an index.
+
+  The distribution is what drives a merge decision: many tiny segments call for a merge,
+  while a few large ones usually do not.
+
+  ## Parameters
+
+  - `index` - The Index reference
+
+  ## Returns
+
+  - `{:ok, [{segment_id, num_docs, num_deleted}]}` - `num_docs` counts live documents only
+  - `{:error, reason}` - If a segment cannot be opened
+
+  ## Examples
+
+      {:ok, counts} = TantivyEx.MergePolicy.segment_doc_counts(index)
+      tiny = Enum.count(counts, fn {_id, num_docs, _deleted} -> num_docs < 1_000 end)
+  """
+  @spec segment_doc_counts(reference()) ::
+          {:ok, [{String.t(), non_neg_integer(), non_neg_integer()}]} | {:error, term()}
+  def segment_doc_counts(index) do
+    case Native.index_segment_doc_counts(index) do
+      {:error, reason} -> {:error, reason}
+      counts when is_list(counts) -> {:ok, counts}
+    end
+  end
 end
@@ -58,62 +58,132 @@ defmodule TantivyEx.Native do
   def schema_validate(_schema),
     do: :erlang.nif_error(:nif_not_loaded)
 
+  def schema_build(_fields_json), do: :erlang.nif_error(:nif_not_loaded)
+  def schema_remove_field(_schema, _field_name),
+    do: :erlang.nif_error(:nif_not_loaded)
+
   # Index functions
   def index_create_in_dir(_path, _schema), do: :erlang.nif_error(:nif_not_loaded)
+
+  def index_create_in_dir_with_settings(_path, _schema, _settings_json),
+    do: :erlang.nif_error(:nif_not_loaded)
+
   def index_create_in_ram(_schema), do: :erlang.nif_error(:nif_not_loaded)
   def index_open_in_dir(_path), do: :erlang.nif_error(:nif_not_loaded)
+  def index_open_read_only(_path), do: :erlang.nif_error(:nif_not_loaded)
+  def index_exists_in_dir(_path), do: :erlang.nif_error(:nif_not_loaded)
   def index_open_or_create_in_dir(_path, _schema), do: :erlang.nif_error(:nif_not_loaded)
-  def index_writer(_index, _memory_budget), do: :erlang.nif_error(:nif_not_loaded)
+  def index_save_to_bytes(_index), do: :erlang.nif_error(:nif_not_loaded)
+  def index_open_from_bytes(_bytes, _schema), do: :erlang.nif_error(:nif_not_loaded)
+  def index_snapshot(_index, _dest_path), do: :erlang.nif_error(:nif_not_loaded)
+  def index_metadata(_index), do: :erlang.nif_error(:nif_not_loaded)
+  def index_load_metas(_index), do: :erlang.nif_error(:nif_not_loaded)
+
+  def index_writer(_index, _memory_budget, _lock_timeout_ms),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def index_writer_with_threads(_index, _memory_budget, _num_threads),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def index_garbage_collect_files(_writer), do: :erlang.nif_error(:nif_not_loaded)
 
   # Writer functions
   def writer_add_document(_writer, _document_json), do: :erlang.nif_error(:nif_not_loaded)
   def writer_commit(_writer), do: :erlang.nif_error(:nif_not_loaded)
+  def writer_commit_with_opstamp(_writer), do: :erlang.nif_error(:nif_not_loaded)
+  def writer_commit_soft(_writer, _pid), do: :erlang.nif_error(:nif_not_loaded)
+  def writer_commit_and_searcher(_writer, _index), do: :erlang.nif_error(:nif_not_loaded)
   def writer_delete_documents(_writer, _query), do: :erlang.nif_error(:nif_not_loaded)
   def writer_delete_all_documents(_writer), do: :erlang.nif_error(:nif_not_loaded)
   def writer_rollback(_writer), do: :erlang.nif_error(:nif_not_loaded)
+  def writer_status(_writer), do: :erlang.nif_error(:nif_not_loaded)
+  def index_optimize(_writer, _max_segments), do: :erlang.nif_error(:nif_not_loaded)
+  def writer_expunge_deleted(_writer, _min_deleted_ratio), do: :erlang.nif_error(:nif_not_loaded)
 
   def writer_delete_term(_writer, _term_field, _term_value),
     do: :erlang.nif_error(:nif_not_loaded)
 
+  def writer_delete_terms(_writer, _term_field, _term_values),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def writer_delete_before(_writer, _date_field, _timestamp),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def writer_delete_regex(_writer, _field_name, _pattern),
+    do: :erlang.nif_error(:nif_not_loaded)
+
   # Enhanced document operations
   def writer_add_document_with_schema(_writer, _document, _schema),
     do: :erlang.nif_error(:nif_not_loaded)
 
+  def writer_add_document_checked(_writer, _document, _coerce),
+    do: :erlang.nif_error(:nif_not_loaded)
+
   def writer_add_document_batch(_writer, _documents, _schema),
     do: :erlang.nif_error(:nif_not_loaded)
 
-  def validate_document_against_schema(_document, _schema), do: :erlang.nif_error(:nif_not_loaded)
+  def validate_document_against_schema(_document, _schema, _required_fields),
+    do: :erlang.nif_error(:nif_not_loaded)
 
   # Search functions
   def index_reader(_index), do: :erlang.nif_error(:nif_not_loaded)
+  def index_reader_with_policy(_index, _policy), do: :erlang.nif_error(:nif_not_loaded)
+
+  def reader_wait_for_opstamp(_index, _opstamp, _timeout_ms),
+    do: :erlang.nif_error(:nif_not_loaded)
+  def reader_reload_and_searcher(_index), do: :erlang.nif_error(:nif_not_loaded)
+  def index_acquire_searcher(_index), do: :erlang.nif_error(:nif_not_loaded)
 
   def searcher_search(_searcher, _query, _limit, _include_docs),
     do: :erlang.nif_error(:nif_not_loaded)
 
   # Query Parser functions
-  def query_parser_new(_schema, _default_fields), do: :erlang.nif_error(:nif_not_loaded)
+  def query_parser_new(_index, _default_fields, _strict, _field_tokenizers),
+    do: :erlang.nif_error(:nif_not_loaded)
   def query_parser_parse(_parser, _query_str), do: :erlang.nif_error(:nif_not_loaded)
 
+  def query_parser_parse_with_boosts(_parser, _query_str, _field_boosts),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def query_parser_parse_on_fields(_index, _query_str, _fields),
+    do: :erlang.nif_error(:nif_not_loaded)
+
   # Query building functions
   def query_term(_schema, _field_name, _term_value), do: :erlang.nif_error(:nif_not_loaded)
+  def query_json_path(_schema, _field_name, _json_path, _value),
+    do: :erlang.nif_error(:nif_not_loaded)
+
   def query_phrase(_schema, _field_name, _phrase_terms), do: :erlang.nif_error(:nif_not_loaded)
+
+  def query_phrase_text(_schema, _field_name, _text, _slop),
+    do: :erlang.nif_error(:nif_not_loaded)
   def query_range_u64(_schema, _field_name, _start, _end), do: :erlang.nif_error(:nif_not_loaded)
   def query_range_i64(_schema, _field_name, _start, _end), do: :erlang.nif_error(:nif_not_loaded)
   def query_range_f64(_schema, _field_name, _start, _end), do: :erlang.nif_error(:nif_not_loaded)
 
+  def query_geo_bounding_box(_schema, _lat, _lon, _min_lat, _max_lat, _min_lon, _max_lon),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def query_range_text(_schema, _field_name, _start, _end, _include_bounds),
+    do: :erlang.nif_error(:nif_not_loaded)
+
   def query_boolean(_must_queries, _should_queries, _must_not_queries),
     do: :erlang.nif_error(:nif_not_loaded)
 
-  def query_fuzzy(_schema, _field_name, _term_value, _distance, _prefix),
+  def query_disjunction_max(_queries, _tie_breaker), do: :erlang.nif_error(:nif_not_loaded)
+  def query_weighted_terms(_schema, _terms), do: :erlang.nif_error(:nif_not_loaded)
+
+  def query_fuzzy(_schema, _field_name, _term_value, _distance, _prefix, _transposition_cost_one),
     do: :erlang.nif_error(:nif_not_loaded)
 
   def query_wildcard(_schema, _field_name, _pattern), do: :erlang.nif_error(:nif_not_loaded)
-  def query_regex(_schema, _field_name, _pattern), do: :erlang.nif_error(:nif_not_loaded)
+  def query_regex(_schema, _field_name, _pattern, _case_insensitive),
+    do: :erlang.nif_error(:nif_not_loaded)
 
   def query_phrase_prefix(_schema, _field_name, _phrase_terms, _max_expansions),
     do: :erlang.nif_error(:nif_not_loaded)
 
-  def query_exists(_schema, _field_name), do: :erlang.nif_error(:nif_not_loaded)
+  def query_exists(_schema, _field_name, _json_subpaths), do: :erlang.nif_error(:nif_not_loaded)
   def query_all(), do: :erlang.nif_error(:nif_not_loaded)
   def query_empty(), do: :erlang.nif_error(:nif_not_loaded)
 
@@ -130,10 +200,86 @@ defmodule TantivyEx.Native do
       ),
       do: :erlang.nif_error(:nif_not_loaded)
 
+  def query_more_like_this_doc(
+        _searcher,
+        _segment_ord,
+        _doc_id,
+        _min_doc_frequency,
+        _max_doc_frequency,
+        _min_term_frequency,
+        _max_query_terms,
+        _min_word_length,
+        _max_word_length,
+        _boost_factor
+      ),
+      do: :erlang.nif_error(:nif_not_loaded)
+
   def query_extract_terms(_query, _schema), do: :erlang.nif_error(:nif_not_loaded)
+  def query_to_string(_query), do: :erlang.nif_error(:nif_not_loaded)
+  def query_to_json(_query, _schema), do: :erlang.nif_error(:nif_not_loaded)
+  def query_normalized_key(_query, _schema), do: :erlang.nif_error(:nif_not_loaded)
 
   # Enhanced search function
-  def searcher_search_with_query(_searcher, _query, _limit, _include_docs),
+  def searcher_search_with_query(
+        _searcher,
+        _query,
+        _limit,
+        _include_docs,
+        _timeout_ms,
+        _explain
+      ),
+      do: :erlang.nif_error(:nif_not_loaded)
+
+  def searcher_search_with_totals(_searcher, _query, _limit, _include_docs),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def searcher_multi_search(_searcher, _queries, _limit, _include_docs),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def searcher_count(_searcher, _query), do: :erlang.nif_error(:nif_not_loaded)
+
+  def searcher_search_unscored(_searcher, _query, _limit, _include_docs),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def searcher_get_by_term(_searcher, _field_name, _value, _include_fields),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def searcher_scroll(_searcher, _query, _batch_size, _after),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def searcher_search_after(_searcher, _query, _limit, _sort_field, _order, _after_value),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def searcher_search_with_facets(_searcher, _query, _limit, _facet_field, _facet_paths),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def searcher_search_collapsed(_searcher, _query, _limit, _collapse_field),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def searcher_stats(_searcher), do: :erlang.nif_error(:nif_not_loaded)
+  def searcher_bytes_fields(_searcher), do: :erlang.nif_error(:nif_not_loaded)
+
+  def searcher_fast_field_values(_searcher, _field_name, _doc_addresses),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def searcher_field_range(_searcher, _field_name), do: :erlang.nif_error(:nif_not_loaded)
+
+  def searcher_highlight_multi(_searcher, _query, _field_names, _doc_address, _max_chars),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def searcher_match_offsets(_searcher, _query, _field_name, _doc_address),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def searcher_field_terms(_searcher, _field_name, _prefix, _limit),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def searcher_autocomplete(_searcher, _field_name, _prefix, _limit),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def searcher_suggest(_searcher, _field_name, _term, _max_distance, _limit),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def searcher_term_stats(_searcher, _field_name, _term_value),
     do: :erlang.nif_error(:nif_not_loaded)
 
   # Tokenizer functions
@@ -141,6 +287,7 @@ defmodule TantivyEx.Native do
   def register_simple_tokenizer(_name), do: :erlang.nif_error(:nif_not_loaded)
   def register_whitespace_tokenizer(_name), do: :erlang.nif_error(:nif_not_loaded)
   def register_regex_tokenizer(_name, _pattern), do: :erlang.nif_error(:nif_not_loaded)
+  def register_delimiter_tokenizer(_name, _delimiters), do: :erlang.nif_error(:nif_not_loaded)
 
   def register_ngram_tokenizer(_name, _min_gram, _max_gram, _prefix_only),
     do: :erlang.nif_error(:nif_not_loaded)
@@ -155,8 +302,15 @@ defmodule TantivyEx.Native do
       ),
       do: :erlang.nif_error(:nif_not_loaded)
 
+  def register_custom_analyzer(_name, _base_tokenizer, _filters),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def register_html_tokenizer(_name, _base_tokenizer, _lowercase),
+    do: :erlang.nif_error(:nif_not_loaded)
+
   def list_tokenizers(), do: :erlang.nif_error(:nif_not_loaded)
   def tokenize_text(_tokenizer_name, _text), do: :erlang.nif_error(:nif_not_loaded)
+  def tokenize_texts(_tokenizer_name, _texts), do: :erlang.nif_error(:nif_not_loaded)
   def tokenize_text_detailed(_tokenizer_name, _text), do: :erlang.nif_error(:nif_not_loaded)
   def process_pre_tokenized_text(_tokens), do: :erlang.nif_error(:nif_not_loaded)
   def register_default_tokenizers(), do: :erlang.nif_error(:nif_not_loaded)
@@ -164,11 +318,21 @@ defmodule TantivyEx.Native do
   # Facet functions
   def facet_collector_for_field(_field_name), do: :erlang.nif_error(:nif_not_loaded)
   def facet_collector_add_facet(_collector, _facet_path), do: :erlang.nif_error(:nif_not_loaded)
+  def facet_collector_add_facets(_collector, _facet_paths),
+    do: :erlang.nif_error(:nif_not_loaded)
   def facet_search(_searcher, _query, _collector), do: :erlang.nif_error(:nif_not_loaded)
+
+  def facet_search_depth(_searcher, _query, _collector, _max_depth),
+    do: :erlang.nif_error(:nif_not_loaded)
+  def facet_counts(_searcher, _query, _field_name, _base_path),
+    do: :erlang.nif_error(:nif_not_loaded)
+
   def facet_term_query(_schema, _field_name, _facet_path), do: :erlang.nif_error(:nif_not_loaded)
   def facet_multi_query(_field_name, _facet_paths, _occur), do: :erlang.nif_error(:nif_not_loaded)
   def facet_from_text(_facet_path), do: :erlang.nif_error(:nif_not_loaded)
   def facet_to_string(_facet), do: :erlang.nif_error(:nif_not_loaded)
+  def facet_encode(_segments), do: :erlang.nif_error(:nif_not_loaded)
+  def facet_decode(_facet_path), do: :erlang.nif_error(:nif_not_loaded)
 
   # Merge Policy functions
   def log_merge_policy_new(), do: :erlang.nif_error(:nif_not_loaded)
@@ -189,14 +353,30 @@ defmodule TantivyEx.Native do
   def index_writer_wait_merging_threads(_writer), do: :erlang.nif_error(:nif_not_loaded)
   def index_get_searchable_segment_ids(_index), do: :erlang.nif_error(:nif_not_loaded)
   def index_get_num_segments(_index), do: :erlang.nif_error(:nif_not_loaded)
+  def index_segment_doc_counts(_index), do: :erlang.nif_error(:nif_not_loaded)
 
   # Aggregation functions
   def run_aggregations(_searcher, _query, _aggregations_json),
     do: :erlang.nif_error(:nif_not_loaded)
 
-  def run_search_with_aggregations(_searcher, _query, _aggregations_json, _search_limit),
+  def run_aggregations_terms(_searcher, _query, _aggregations_json),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def run_search_with_separate_aggregations(
+        _searcher,
+        _query,
+        _agg_query,
+        _aggregations_json,
+        _search_limit,
+        _sort
+      ),
+      do: :erlang.nif_error(:nif_not_loaded)
+
+  def run_search_with_aggregations(_searcher, _query, _aggregations_json, _search_limit, _sort),
     do: :erlang.nif_error(:nif_not_loaded)
 
+  def validate_aggregations(_schema, _aggregations_json), do: :erlang.nif_error(:nif_not_loaded)
+
   # Index Warming functions
   def index_warming_new(), do: :erlang.nif_error(:nif_not_loaded)
 
@@ -216,6 +396,9 @@ defmodule TantivyEx.Native do
   def index_warming_warm_index(_warming, _index, _cache_key),
     do: :erlang.nif_error(:nif_not_loaded)
 
+  def index_warming_warm_fast_fields(_warming, _index, _field_names, _cache_key),
+    do: :erlang.nif_error(:nif_not_loaded)
+
   def index_warming_get_searcher(_warming, _cache_key), do: :erlang.nif_error(:nif_not_loaded)
   def index_warming_evict_cache(_warming, _force_all), do: :erlang.nif_error(:nif_not_loaded)
   def index_warming_get_stats(_warming), do: :erlang.nif_error(:nif_not_loaded)
@@ -332,6 +515,8 @@ defmodule TantivyEx.Native do
   def reader_manager_shutdown(_manager), do: :erlang.nif_error(:nif_not_loaded)
   def reader_manager_dispose_reader(_manager, _reader_id), do: :erlang.nif_error(:nif_not_loaded)
   def reader_manager_clear_all(_manager), do: :erlang.nif_error(:nif_not_loaded)
+  def reader_manager_subscribe(_manager, _pid), do: :erlang.nif_error(:nif_not_loaded)
+  def reader_manager_unsubscribe(_manager), do: :erlang.nif_error(:nif_not_loaded)
 
   # Note: Performance, Memory, and Resource management functions are implemented
   # in pure Elixir in their respective modules and do not require native implementations.
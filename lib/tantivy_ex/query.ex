@@ -45,6 +45,8 @@ defmodule TantivyEx.Query do
       {:ok, query} = TantivyEx.Query.fuzzy(schema, "title", "hello", 2)
   """
 
+  require Logger
+
   alias TantivyEx.{Native, Schema}
 
   @type t :: reference()
@@ -55,6 +57,10 @@ defmodule TantivyEx.Query do
 
   Term queries match documents where the specified field contains the exact term.
 
+  The value is coerced to the field type: numeric fields require a number (or a
+  numeric string), and date fields accept a unix timestamp in seconds, an
+  RFC3339 string or a `DateTime`. Values that can't be coerced return an error.
+
   ## Parameters
 
   - `schema`: The schema containing the field
@@ -66,11 +72,18 @@ defmodule TantivyEx.Query do
       iex> {:ok, query} = TantivyEx.Query.term(schema, "title", "hello")
       iex> is_reference(query)
       true
+
+      iex> {:ok, query} = TantivyEx.Query.term(schema, "created_at", "2023-01-01T00:00:00Z")
   """
   @spec term(Schema.t(), String.t(), any()) :: {:ok, t()} | {:error, String.t()}
   def term(schema, field_name, term_value) when is_binary(field_name) do
     # Convert the term value to a string if it's not already a string
-    term_str = if is_binary(term_value), do: term_value, else: to_string(term_value)
+    term_str =
+      case term_value do
+        value when is_binary(value) -> value
+        %DateTime{} = value -> DateTime.to_iso8601(value)
+        value -> to_string(value)
+      end
 
     case Native.query_term(schema, field_name, term_str) do
       {:error, error_reason} -> {:error, "Failed to create term query: #{error_reason}"}
@@ -80,6 +93,44 @@ defmodule TantivyEx.Query do
     e -> {:error, "Failed to create term query: #{inspect(e)}"}
   end
 
+  @doc """
+  Creates a query matching a value at a sub-path of a JSON object field.
+
+  Nested keys are addressed with dotted paths, e.g. `"status"` or `"author.name"`.
+  Text values are normalized like `term/3`; numbers, booleans and RFC3339 dates
+  also match values indexed with those types.
+
+  ## Parameters
+
+  - `schema`: The schema containing the JSON field
+  - `field_name`: The name of the JSON field
+  - `json_path`: The dotted path inside the JSON object
+  - `value`: The value to match at that path
+
+  ## Examples
+
+      iex> {:ok, query} = TantivyEx.Query.json_path(schema, "metadata", "status", "active")
+      iex> {:ok, query} = TantivyEx.Query.json_path(schema, "metadata", "stats.views", 42)
+  """
+  @spec json_path(Schema.t(), String.t(), String.t(), any()) ::
+          {:ok, t()} | {:error, String.t()}
+  def json_path(schema, field_name, json_path, value)
+      when is_binary(field_name) and is_binary(json_path) do
+    value_str =
+      case value do
+        value when is_binary(value) -> value
+        %DateTime{} = value -> DateTime.to_iso8601(value)
+        value -> to_string(value)
+      end
+
+    case Native.query_json_path(schema, field_name, json_path, value_str) do
+      {:error, reason} -> {:error, "Failed to create JSON path query: #{reason}"}
+      query_ref -> {:ok, query_ref}
+    end
+  rescue
+    e -> {:error, "Failed to create JSON path query: #{inspect(e)}"}
+  end
+
   # Query Parser Functions
 
   @doc """
@@ -91,18 +142,47 @@ defmodule TantivyEx.Query do
 
   - `index`: The index to use for field resolution
   - `default_fields`: List of field names to search by default
+  - `opts`: Parser options
+
+  ## Options
+
+  - `:strict` - When `true` (default) any unknown default field is an error.
+    When `false`, unknown fields are dropped with a logged warning and the
+    parser is built from the remaining ones; it only fails if none are valid.
+  - `:field_tokenizers` - Map of text field name to the tokenizer used to
+    analyze query text for that field, instead of the tokenizer it was indexed
+    with. Enables asymmetric analysis, e.g. a field indexed with an edge n-gram
+    tokenizer for autocomplete but queried with `"default"` so the typed
+    prefix is not itself split into n-grams. The tokenizer can be a built-in
+    one or any registered through `TantivyEx.Tokenizer`.
 
   ## Examples
 
       iex> {:ok, parser} = TantivyEx.Query.parser(index, ["title", "body"])
       iex> is_reference(parser)
       true
+
+      iex> {:ok, parser} = TantivyEx.Query.parser(index, ["title", "optional"], strict: false)
+
+      iex> opts = [field_tokenizers: %{"title_ngram" => "default"}]
+      iex> {:ok, parser} = TantivyEx.Query.parser(index, ["title_ngram"], opts)
   """
-  @spec parser(TantivyEx.Index.t(), [String.t()]) :: {:ok, parser()} | {:error, String.t()}
-  def parser(index, default_fields) when is_list(default_fields) do
-    case Native.query_parser_new(index, default_fields) do
-      {:error, reason} -> {:error, reason}
-      parser_ref -> {:ok, parser_ref}
+  @spec parser(TantivyEx.Index.t(), [String.t()], keyword()) ::
+          {:ok, parser()} | {:error, String.t()}
+  def parser(index, default_fields, opts \\ []) when is_list(default_fields) do
+    strict = Keyword.get(opts, :strict, true)
+    field_tokenizers = Keyword.get(opts, :field_tokenizers, %{})
+
+    case Native.query_parser_new(index, default_fields, strict, field_tokenizers) do
+      {:error, reason} ->
+        {:error, reason}
+
+      {parser_ref, []} ->
+        {:ok, parser_ref}
+
+      {parser_ref, skipped_fields} ->
+        Logger.warning("Query parser ignored unknown fields: #{Enum.join(skipped_fields, ", ")}")
+        {:ok, parser_ref}
     end
   rescue
     e -> {:error, "Failed to create query parser: #{inspect(e)}"}
@@ -140,10 +220,74 @@ defmodule TantivyEx.Query do
     e -> {:error, "Failed to parse query: #{inspect(e)}"}
   end
 
+  @doc """
+  Parses a query string with per-query field boosts.
+
+  The boosts only apply to this query; the parser itself is left unchanged, so
+  the same parser can serve requests with different relevance tuning.
+
+  ## Parameters
+
+  - `parser`: The query parser
+  - `query_str`: The query string to parse
+  - `field_boosts`: Map of field name to boost factor
+
+  ## Examples
+
+      iex> {:ok, query} = TantivyEx.Query.parse_with_boosts(parser, "hello", %{"title" => 2.0})
+      iex> is_reference(query)
+      true
+  """
+  @spec parse_with_boosts(parser(), String.t(), %{String.t() => number()}) ::
+          {:ok, t()} | {:error, String.t()}
+  def parse_with_boosts(parser, query_str, field_boosts)
+      when is_binary(query_str) and is_map(field_boosts) do
+    boosts = Map.new(field_boosts, fn {field, boost} -> {to_string(field), boost / 1} end)
+
+    case Native.query_parser_parse_with_boosts(parser, query_str, boosts) do
+      {:error, reason} -> {:error, reason}
+      query_ref -> {:ok, query_ref}
+    end
+  rescue
+    e -> {:error, "Failed to parse query: #{inspect(e)}"}
+  end
+
+  @doc """
+  Parses a query string against the given default fields in a single call.
+
+  No parser resource is kept around, which suits search scopes that change per
+  request (e.g. all fields vs title only). Unknown fields are an error, as with
+  a strict `parser/3`.
+
+  ## Parameters
+
+  - `index`: The index to use for field resolution
+  - `query_str`: The query string to parse
+  - `fields`: Default fields for terms without an explicit field
+
+  ## Examples
+
+      iex> {:ok, query} = TantivyEx.Query.parse_on_fields(index, "hello", ["title"])
+      iex> is_reference(query)
+      true
+  """
+  @spec parse_on_fields(TantivyEx.Index.t(), String.t(), [String.t()]) ::
+          {:ok, t()} | {:error, String.t()}
+  def parse_on_fields(index, query_str, fields) when is_binary(query_str) and is_list(fields) do
+    case Native.query_parser_parse_on_fields(index, query_str, fields) do
+      {:error, reason} -> {:error, reason}
+      query_ref -> {:ok, query_ref}
+    end
+  rescue
+    e -> {:error, "Failed to parse query: #{inspect(e)}"}
+  end
+
   @doc """
   Creates a phrase query for exact phrase matching.
 
   Phrase queries match documents where the specified field contains the exact sequence of terms.
+  The field must be indexed with positions (e.g. a `:fast_stored` text field); otherwise
+  an error is returned instead of a query that can never match.
 
   ## Parameters
 
@@ -178,6 +322,37 @@ defmodule TantivyEx.Query do
     e -> {:error, "Failed to create phrase query: #{inspect(e)}"}
   end
 
+  @doc """
+  Creates a phrase query from raw text, analyzed with the field's tokenizer.
+
+  Unlike `phrase/3`, the text is run through the same analyzer the field was
+  indexed with, so casing and stemming match the indexed terms. A `slop`
+  greater than zero allows that many intervening positions between terms.
+
+  ## Parameters
+
+  - `schema`: The schema containing the field
+  - `field_name`: The name of an indexed text field
+  - `text`: The phrase text
+  - `slop`: Allowed distance between the phrase terms (default: 0)
+
+  ## Examples
+
+      iex> {:ok, query} = TantivyEx.Query.phrase_text(schema, "title", "Functional Programming")
+      iex> {:ok, query} = TantivyEx.Query.phrase_text(schema, "title", "quick fox", 1)
+  """
+  @spec phrase_text(Schema.t(), String.t(), String.t(), non_neg_integer()) ::
+          {:ok, t()} | {:error, String.t()}
+  def phrase_text(schema, field_name, text, slop \\ 0)
+      when is_binary(field_name) and is_binary(text) and is_integer(slop) and slop >= 0 do
+    case Native.query_phrase_text(schema, field_name, text, slop) do
+      {:error, reason} -> {:error, "Failed to create phrase query: #{reason}"}
+      query_ref -> {:ok, query_ref}
+    end
+  rescue
+    e -> {:error, "Failed to create phrase query: #{inspect(e)}"}
+  end
+
   # Range Queries
 
   @doc """
@@ -251,6 +426,89 @@ defmodule TantivyEx.Query do
     e -> {:error, "Failed to create f64 range query: #{inspect(e)}"}
   end
 
+  @doc """
+  Creates a bounding-box query over a geo-point stored in two f64 fast fields.
+
+  Matches documents whose latitude lies within `{min_lat, max_lat}` and whose
+  longitude lies within `{min_lon, max_lon}`, bounds included, e.g. the results
+  inside the current map view. When `min_lon` is greater than `max_lon` the box
+  crosses the antimeridian and wraps around it.
+
+  ## Parameters
+
+  - `schema`: The schema containing the fields
+  - `lat_field`: Name of the f64 fast field holding latitudes
+  - `lon_field`: Name of the f64 fast field holding longitudes
+  - `lat_range`: `{min_lat, max_lat}`, within -90..90
+  - `lon_range`: `{min_lon, max_lon}`, within -180..180
+
+  ## Examples
+
+      iex> {:ok, query} =
+      ...>   TantivyEx.Query.geo_bounding_box(schema, "lat", "lon", {48.8, 48.9}, {2.25, 2.42})
+  """
+  @spec geo_bounding_box(
+          Schema.t(),
+          String.t(),
+          String.t(),
+          {number(), number()},
+          {number(), number()}
+        ) :: {:ok, t()} | {:error, String.t()}
+  def geo_bounding_box(schema, lat_field, lon_field, {min_lat, max_lat}, {min_lon, max_lon})
+      when is_binary(lat_field) and is_binary(lon_field) and is_number(min_lat) and
+             is_number(max_lat) and is_number(min_lon) and is_number(max_lon) do
+    case Native.query_geo_bounding_box(
+           schema,
+           lat_field,
+           lon_field,
+           min_lat / 1,
+           max_lat / 1,
+           min_lon / 1,
+           max_lon / 1
+         ) do
+      {:error, reason} -> {:error, reason}
+      query_ref -> {:ok, query_ref}
+    end
+  rescue
+    e -> {:error, "Failed to create geo bounding box query: #{inspect(e)}"}
+  end
+
+  @doc """
+  Creates a lexicographic range query over a text field.
+
+  Bounds are compared byte-wise against the indexed terms and are not
+  analyzed, so this is intended for raw/keyword fields holding sortable keys.
+
+  ## Parameters
+
+  - `schema`: The schema containing the field
+  - `field_name`: The name of an indexed text field
+  - `start_value`: The start of the range (nil for unbounded)
+  - `end_value`: The end of the range (nil for unbounded)
+  - `include_bounds`: `{include_start, include_end}` (default: `{true, true}`)
+
+  ## Examples
+
+      iex> {:ok, query} = TantivyEx.Query.range_text(schema, "sku", "A100", "A200", {true, false})
+      iex> {:ok, query} = TantivyEx.Query.range_text(schema, "sku", "B", nil)  # >= "B"
+  """
+  @spec range_text(
+          Schema.t(),
+          String.t(),
+          String.t() | nil,
+          String.t() | nil,
+          {boolean(), boolean()}
+        ) :: {:ok, t()} | {:error, String.t()}
+  def range_text(schema, field_name, start_value, end_value, include_bounds \\ {true, true})
+      when is_binary(field_name) do
+    case Native.query_range_text(schema, field_name, start_value, end_value, include_bounds) do
+      {:error, reason} -> {:error, reason}
+      query_ref -> {:ok, query_ref}
+    end
+  rescue
+    e -> {:error, "Failed to create text range query: #{inspect(e)}"}
+  end
+
   # Boolean Queries
 
   @doc """
@@ -285,6 +543,80 @@ defmodule TantivyEx.Query do
     e -> {:error, "Failed to create boolean query: #{inspect(e)}"}
   end
 
+  @doc """
+  Creates a disjunction max query that scores documents by their best matching query.
+
+  A document matching strongly in one field outranks a document matching weakly
+  in several. The scores of the other matching queries are added after being
+  multiplied by `tie_breaker`.
+
+  ## Parameters
+
+  - `queries`: List of queries to combine
+  - `tie_breaker`: Weight between 0.0 and 1.0 for non-maximum scores (default: 0.0)
+
+  ## Examples
+
+      iex> {:ok, title_query} = TantivyEx.Query.term(schema, "title", "elixir")
+      iex> {:ok, body_query} = TantivyEx.Query.term(schema, "body", "elixir")
+      iex> {:ok, query} = TantivyEx.Query.disjunction_max([title_query, body_query], 0.1)
+  """
+  @spec disjunction_max([t()], float()) :: {:ok, t()} | {:error, String.t()}
+  def disjunction_max(queries, tie_breaker \\ 0.0) when is_list(queries) do
+    case Native.query_disjunction_max(queries, tie_breaker / 1) do
+      {:error, reason} -> {:error, reason}
+      query_ref -> {:ok, query_ref}
+    end
+  rescue
+    e -> {:error, "Failed to create disjunction max query: #{inspect(e)}"}
+  end
+
+  @doc """
+  Creates a query from weighted terms, each with its own boost.
+
+  Every `{field, value, boost}` entry is matched like `term/3` and its score is
+  multiplied by `boost`. Documents match if any entry matches and score the
+  sum of their matching entries, which suits injecting expansion terms from a
+  re-ranking or learned-relevance layer.
+
+  ## Parameters
+
+  - `schema`: The schema containing the fields
+  - `terms`: Non-empty list of `{field_name, value, boost}` tuples, with
+    non-negative boosts
+
+  ## Examples
+
+      iex> {:ok, query} =
+      ...>   TantivyEx.Query.weighted_terms(schema, [
+      ...>     {"title", "elixir", 2.0},
+      ...>     {"title", "erlang", 0.5},
+      ...>     {"body", "beam", 1}
+      ...>   ])
+  """
+  @spec weighted_terms(Schema.t(), [{String.t(), any(), number()}]) ::
+          {:ok, t()} | {:error, String.t()}
+  def weighted_terms(schema, terms) when is_list(terms) do
+    native_terms =
+      Enum.map(terms, fn {field_name, value, boost} ->
+        value =
+          case value do
+            value when is_binary(value) -> value
+            %DateTime{} = value -> DateTime.to_iso8601(value)
+            value -> to_string(value)
+          end
+
+        {field_name, value, boost / 1}
+      end)
+
+    case Native.query_weighted_terms(schema, native_terms) do
+      {:error, reason} -> {:error, reason}
+      query_ref -> {:ok, query_ref}
+    end
+  rescue
+    e -> {:error, "Failed to create weighted terms query: #{inspect(e)}"}
+  end
+
   # Advanced Query Types
 
   @doc """
@@ -293,26 +625,50 @@ defmodule TantivyEx.Query do
   Fuzzy queries match terms that are similar to the specified term, allowing for typos
   and minor spelling differences.
 
+  The number of candidate terms grows quickly with the edit distance: a distance of 2
+  can expand to a large share of the term dictionary and is noticeably slower than 1,
+  especially for short terms. Distances above 2 are not supported.
+
   ## Parameters
 
   - `schema`: The schema containing the field
   - `field_name`: The name of the field to search
   - `term_value`: The term to match approximately
-  - `distance`: Maximum edit distance (default: 2)
-  - `prefix`: Whether to require exact prefix match (default: true)
+  - `distance`: Maximum edit distance, from 0 to 2 (default: 2)
+  - `prefix`: Match `term_value` as a fuzzy prefix of longer terms, e.g. for
+    type-ahead (default: false)
+  - `opts`: Keyword list of options
+
+  ## Options
+
+  - `:transposition_cost_one` - Count swapped adjacent characters (`"teh"` for
+    `"the"`) as one edit instead of two (default: true)
 
   ## Examples
 
-      iex> {:ok, query} = TantivyEx.Query.fuzzy(schema, "title", "hello", 2, true)
+      iex> {:ok, query} = TantivyEx.Query.fuzzy(schema, "title", "hello", 2)
       iex> is_reference(query)
       true
+
+      # Strict Levenshtein distance, "teh" no longer matches "the"
+      iex> opts = [transposition_cost_one: false]
+      iex> {:ok, query} = TantivyEx.Query.fuzzy(schema, "title", "teh", 1, false, opts)
   """
-  @spec fuzzy(Schema.t(), String.t(), String.t(), non_neg_integer(), boolean()) ::
+  @spec fuzzy(Schema.t(), String.t(), String.t(), non_neg_integer(), boolean(), keyword()) ::
           {:ok, t()} | {:error, String.t()}
-  def fuzzy(schema, field_name, term_value, distance \\ 2, prefix \\ true)
+  def fuzzy(schema, field_name, term_value, distance \\ 2, prefix \\ false, opts \\ [])
       when is_binary(field_name) and is_binary(term_value) and is_integer(distance) and
-             is_boolean(prefix) do
-    case Native.query_fuzzy(schema, field_name, term_value, distance, prefix) do
+             is_boolean(prefix) and is_list(opts) do
+    transposition_cost_one = Keyword.get(opts, :transposition_cost_one, true)
+
+    case Native.query_fuzzy(
+           schema,
+           field_name,
+           term_value,
+           distance,
+           prefix,
+           transposition_cost_one
+         ) do
       {:error, reason} -> {:error, reason}
       query_ref -> {:ok, query_ref}
     end
@@ -351,21 +707,30 @@ defmodule TantivyEx.Query do
   Creates a regex query for advanced pattern matching.
 
   Regex queries allow full regular expression matching against field values.
+  The pattern is anchored to the whole term: `"hel"` only matches the term
+  `hel`, use `"hel.*"` to match terms starting with it. Text fields are matched
+  against their indexed (usually lowercased) tokens.
 
   ## Parameters
 
   - `schema`: The schema containing the field
   - `field_name`: The name of the field to search
   - `pattern`: The regular expression pattern
+  - `opts`: Options
+    - `:case_insensitive` - Match regardless of case (default: false)
 
   ## Examples
 
       iex> {:ok, query} = TantivyEx.Query.regex(schema, "title", "h[ae]llo")
       iex> {:ok, query} = TantivyEx.Query.regex(schema, "email", ".*@example\\.com")
+      iex> {:ok, query} = TantivyEx.Query.regex(schema, "sku", "ab-[0-9]+", case_insensitive: true)
   """
-  @spec regex(Schema.t(), String.t(), String.t()) :: {:ok, t()} | {:error, String.t()}
-  def regex(schema, field_name, pattern) when is_binary(field_name) and is_binary(pattern) do
-    case Native.query_regex(schema, field_name, pattern) do
+  @spec regex(Schema.t(), String.t(), String.t(), keyword()) :: {:ok, t()} | {:error, String.t()}
+  def regex(schema, field_name, pattern, opts \\ [])
+      when is_binary(field_name) and is_binary(pattern) and is_list(opts) do
+    case_insensitive = Keyword.get(opts, :case_insensitive, false)
+
+    case Native.query_regex(schema, field_name, pattern, case_insensitive) do
       {:error, reason} -> {:error, reason}
       query_ref -> {:ok, query_ref}
     end
@@ -378,6 +743,7 @@ defmodule TantivyEx.Query do
 
   Phrase prefix queries match phrases where the last term is treated as a prefix.
   Useful for autocomplete functionality.
+  Like `phrase/3`, it requires a text field indexed with positions.
 
   ## Parameters
 
@@ -406,31 +772,29 @@ defmodule TantivyEx.Query do
   Creates an exists query to check if a field has any value.
 
   Exists queries match documents where the specified field contains any value.
+  The field must be a fast field. For JSON fields, `field_name` may be a dotted
+  sub-path such as `"metadata.promoted"`.
 
   ## Parameters
 
   - `schema`: The schema containing the field
-  - `field_name`: The name of the field to check
+  - `field_name`: The name of the field, or a dotted path inside a JSON field
+  - `opts`: Options
+    - `:json_subpaths` - also match values nested below the path (default: false)
 
   ## Examples
 
       iex> {:ok, query} = TantivyEx.Query.exists(schema, "email")
+      iex> {:ok, query} = TantivyEx.Query.exists(schema, "metadata.promoted")
+      iex> {:ok, query} = TantivyEx.Query.exists(schema, "metadata", json_subpaths: true)
   """
-  @spec exists(Schema.t(), String.t()) :: {:ok, t()} | {:error, String.t()}
-  def exists(schema, field_name) when is_binary(field_name) do
-    # Check if the field exists in the schema first
-    try do
-      if Schema.field_exists?(schema, field_name) do
-        case Native.query_exists(schema, field_name) do
-          {:error, reason} -> {:error, reason}
-          query_ref -> {:ok, query_ref}
-        end
-      else
-        {:error, "Field '#{field_name}' not found in schema"}
-      end
-    catch
-      # Fallback for any errors
-      _, e -> {:error, "Failed to create exists query: #{inspect(e)}"}
+  @spec exists(Schema.t(), String.t(), keyword()) :: {:ok, t()} | {:error, String.t()}
+  def exists(schema, field_name, opts \\ []) when is_binary(field_name) do
+    json_subpaths = Keyword.get(opts, :json_subpaths, false)
+
+    case Native.query_exists(schema, field_name, json_subpaths) do
+      {:error, reason} -> {:error, reason}
+      query_ref -> {:ok, query_ref}
     end
   rescue
     e -> {:error, "Failed to create exists query: #{inspect(e)}"}
@@ -519,6 +883,47 @@ defmodule TantivyEx.Query do
     e -> {:error, "Failed to create more-like-this query: #{inspect(e)}"}
   end
 
+  @doc """
+  Creates a more-like-this query from a document already in the index.
+
+  Terms are taken from the stored fields of the document at the given address,
+  which is only valid for the segments of `searcher` (for example as returned
+  by `TantivyEx.Searcher.scroll/4`). The source document itself also matches.
+
+  ## Parameters
+
+  - `searcher`: The searcher the document address belongs to
+  - `segment_ord`: The segment ordinal of the document
+  - `doc_id`: The document id within the segment
+  - `options`: Same options as `more_like_this/3`
+
+  ## Examples
+
+      iex> {:ok, query} = TantivyEx.Query.more_like_this_doc(searcher, 0, 3, min_doc_frequency: 1)
+      iex> {:ok, related} = TantivyEx.Searcher.search(searcher, query, 5)
+  """
+  @spec more_like_this_doc(reference(), non_neg_integer(), non_neg_integer(), keyword()) ::
+          {:ok, t()} | {:error, String.t()}
+  def more_like_this_doc(searcher, segment_ord, doc_id, options \\ []) when is_list(options) do
+    case Native.query_more_like_this_doc(
+           searcher,
+           segment_ord,
+           doc_id,
+           Keyword.get(options, :min_doc_frequency),
+           Keyword.get(options, :max_doc_frequency),
+           Keyword.get(options, :min_term_frequency),
+           Keyword.get(options, :max_query_terms),
+           Keyword.get(options, :min_word_length),
+           Keyword.get(options, :max_word_length),
+           Keyword.get(options, :boost_factor)
+         ) do
+      {:error, reason} -> {:error, reason}
+      query_ref -> {:ok, query_ref}
+    end
+  rescue
+    e -> {:error, "Failed to create more-like-this query: #{inspect(e)}"}
+  end
+
   # Facet Queries
 
   @doc """
@@ -548,4 +953,119 @@ defmodule TantivyEx.Query do
   rescue
     e -> {:error, "Failed to create facet term query: #{inspect(e)}"}
   end
+
+  # Query Inspection
+
+  @doc """
+  Returns a stable textual representation of a query.
+
+  Useful for logging and for diffing queries in regression tests.
+
+  ## Parameters
+
+  - `query`: The query to describe
+
+  ## Examples
+
+      iex> {:ok, query} = TantivyEx.Query.all()
+      iex> {:ok, "AllQuery"} = TantivyEx.Query.to_debug_string(query)
+  """
+  @spec to_debug_string(t()) :: {:ok, String.t()} | {:error, String.t()}
+  def to_debug_string(query) do
+    case Native.query_to_string(query) do
+      {:error, reason} -> {:error, reason}
+      description -> {:ok, description}
+    end
+  rescue
+    e -> {:error, "Failed to describe query: #{inspect(e)}"}
+  end
+
+  @doc """
+  Extracts the terms a query matches on, together with their field names.
+
+  Returns an empty list for queries without terms, such as `all/0`.
+
+  ## Parameters
+
+  - `query`: The query to inspect
+  - `schema`: The schema used to resolve field names
+
+  ## Examples
+
+      iex> {:ok, query} = TantivyEx.Query.term(schema, "title", "hello")
+      iex> {:ok, [{"title", "hello"}]} = TantivyEx.Query.extract_terms(query, schema)
+  """
+  @spec extract_terms(t(), Schema.t()) :: {:ok, [{String.t(), String.t()}]} | {:error, String.t()}
+  def extract_terms(query, schema) do
+    case Native.query_extract_terms(query, schema) do
+      {:error, reason} -> {:error, reason}
+      terms when is_list(terms) -> {:ok, terms}
+    end
+  rescue
+    e -> {:error, "Failed to extract query terms: #{inspect(e)}"}
+  end
+
+  @doc """
+  Returns a structured description of a query.
+
+  Term, phrase, boolean, all and empty queries are described field by field.
+  Other query types are returned as `%{"type" => "other", "debug" => ...}`.
+
+  ## Parameters
+
+  - `query`: The query to describe
+  - `schema`: The schema used to resolve field names
+
+  ## Examples
+
+      iex> {:ok, query} = TantivyEx.Query.term(schema, "title", "hello")
+      iex> {:ok, %{"type" => "term", "field" => "title", "value" => "hello"}} =
+      ...>   TantivyEx.Query.to_json(query, schema)
+  """
+  @spec to_json(t(), Schema.t()) :: {:ok, map()} | {:error, String.t()}
+  def to_json(query, schema) do
+    case Native.query_to_json(query, schema) do
+      {:error, reason} ->
+        {:error, reason}
+
+      json when is_binary(json) ->
+        case Jason.decode(json) do
+          {:ok, description} -> {:ok, description}
+          {:error, _} -> {:error, "Failed to parse query description"}
+        end
+    end
+  rescue
+    e -> {:error, "Failed to describe query: #{inspect(e)}"}
+  end
+
+  @doc """
+  Returns a deterministic key identifying a query, for use as a cache key.
+
+  Unlike `to_debug_string/1`, the key doesn't depend on the order in which
+  boolean clauses were added: clauses are sorted at every nesting level,
+  including inside `disjunction_max/2` and `weighted_terms/2` queries, so
+  logically identical searches share a key. Hash it (e.g. with
+  `:crypto.hash/2`) when a shorter key is needed.
+
+  ## Parameters
+
+  - `query`: The query to identify
+  - `schema`: The schema used to resolve field names
+
+  ## Examples
+
+      iex> {:ok, a} = TantivyEx.Query.boolean([title_query], [body_query], [])
+      iex> {:ok, b} = TantivyEx.Query.boolean([title_query], [body_query], [])
+      iex> TantivyEx.Query.normalized_key(a, schema) == TantivyEx.Query.normalized_key(b, schema)
+      true
+  """
+  @spec normalized_key(t(), Schema.t()) :: {:ok, String.t()} | {:error, String.t()}
+  def normalized_key(query, schema) do
+    case Native.query_normalized_key(query, schema) do
+      {:error, reason} -> {:error, reason}
+      key when is_binary(key) -> {:ok, key}
+    end
+  rescue
+    e -> {:error, "Failed to compute query key: #{inspect(e)}"}
+  end
 end
@@ -1,6 +1,13 @@
 defmodule TantivyEx.ReaderManager do
   @moduledoc """
   Advanced index reader management and reload policies for TantivyEx.
+
+  A process registered with `subscribe/2` receives reader lifecycle events as
+  `{:tantivy_reader_event, %{event: event, reader_id: id}}` messages, where
+  `event` is one of `:created`, `:reloaded`, `:reload_failed`,
+  `:search_performed` or `:disposed`. Timestamps (unix seconds), durations
+  and reload errors are included in the map when relevant. Only one process
+  is subscribed at a time.
   """
 
   alias TantivyEx.Native
@@ -125,6 +132,22 @@ defmodule TantivyEx.ReaderManager do
     end
   end
 
+  @spec subscribe(manager_resource(), pid()) :: :ok | {:error, term()}
+  def subscribe(manager_resource, pid \\ self()) when is_pid(pid) do
+    case Native.reader_manager_subscribe(manager_resource, pid) do
+      :ok -> :ok
+      error -> {:error, error}
+    end
+  end
+
+  @spec unsubscribe(manager_resource()) :: :ok | {:error, term()}
+  def unsubscribe(manager_resource) do
+    case Native.reader_manager_unsubscribe(manager_resource) do
+      :ok -> :ok
+      error -> {:error, error}
+    end
+  end
+
   @spec shutdown(manager_resource()) :: :ok | {:error, term()}
   def shutdown(manager_resource) do
     try do
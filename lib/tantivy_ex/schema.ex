@@ -69,6 +69,64 @@ defmodule TantivyEx.Schema do
     Native.schema_builder_new()
   end
 
+  @doc """
+  Builds a schema from a complete field list in a single call.
+
+  Equivalent to chaining the `add_*_field` functions, but the schema is
+  constructed in one pass instead of being rebuilt for every added field,
+  which matters for schemas with many fields.
+
+  ## Parameters
+
+  - `fields`: A list of `{name, type}`, `{name, type, options}` tuples or maps
+    with `:name`, `:type` and optional `:options` and `:tokenizer` keys
+
+  Supported types are `:text`, `:u64`, `:i64`, `:f64`, `:bool`, `:date`,
+  `:facet`, `:bytes`, `:json` and `:ip_addr`. Options take the same values as
+  the matching `add_*_field` function and default to that function's default.
+
+  Raises on unknown types or duplicate field names.
+
+  ## Examples
+
+      iex> schema =
+      ...>   TantivyEx.Schema.build([
+      ...>     {"title", :text, :text_stored},
+      ...>     %{name: "body", type: :text, options: :text, tokenizer: "en_stem"},
+      ...>     {"price", :u64, :fast_stored},
+      ...>     {"category", :facet}
+      ...>   ])
+      iex> TantivyEx.Schema.get_field_names(schema)
+      ["title", "body", "price", "category"]
+  """
+  @spec build([tuple() | map()]) :: t()
+  def build(fields) when is_list(fields) do
+    fields_json = fields |> Enum.map(&field_spec/1) |> Jason.encode!()
+
+    case Native.schema_build(fields_json) do
+      {:error, reason} -> raise "Failed to build schema: #{reason}"
+      schema -> schema
+    end
+  end
+
+  defp field_spec({name, type}), do: field_spec(%{name: name, type: type})
+  defp field_spec({name, type, options}),
+    do: field_spec(%{name: name, type: type, options: options})
+
+  defp field_spec(%{name: name, type: type} = spec) do
+    %{
+      name: to_string(name),
+      type: to_string(type),
+      options: spec |> Map.get(:options, "") |> field_options_string(),
+      tokenizer: Map.get(spec, :tokenizer)
+    }
+  end
+
+  defp field_options_string(options) when is_atom(options),
+    do: options |> Atom.to_string() |> String.upcase()
+
+  defp field_options_string(options) when is_binary(options), do: options
+
   @doc """
   Adds a text field to the schema.
 
@@ -421,6 +479,8 @@ defmodule TantivyEx.Schema do
   Adds a JSON object field to the schema.
 
   JSON fields allow indexing and searching within structured JSON data.
+  Use `:fast` or `:fast_stored` to keep columnar values for every sub-path, which
+  existence checks such as `TantivyEx.Query.exists/3` require.
 
   ## Examples
 
@@ -429,13 +489,15 @@ defmodule TantivyEx.Schema do
       iex> is_reference(schema)
       true
   """
-  @spec add_json_field(t(), String.t(), text_field_options()) :: t()
+  @spec add_json_field(t(), String.t(), text_field_options() | :fast | :fast_stored) :: t()
   def add_json_field(schema, field_name, options) when is_atom(options) do
     options_str =
       case options do
         :text -> "TEXT"
         :text_stored -> "TEXT_STORED"
         :stored -> "STORED"
+        :fast -> "FAST"
+        :fast_stored -> "FAST_STORED"
         _ -> "TEXT"
       end
 
@@ -477,6 +539,33 @@ defmodule TantivyEx.Schema do
     end
   end
 
+  @doc """
+  Removes a field from the schema.
+
+  The schema is rebuilt from all remaining fields, preserving their types and
+  options. This is useful when building schemas programmatically from user
+  configuration where a previously added field gets deselected.
+
+  Raises if the field does not exist in the schema.
+
+  ## Examples
+
+      iex> schema = TantivyEx.Schema.new()
+      iex> schema = TantivyEx.Schema.add_text_field(schema, "title", :text_stored)
+      iex> schema = TantivyEx.Schema.add_u64_field(schema, "price", :indexed)
+      iex> schema = TantivyEx.Schema.remove_field(schema, "price")
+      iex> TantivyEx.Schema.get_field_names(schema)
+      ["title"]
+  """
+  @spec remove_field(t(), String.t()) :: t()
+  def remove_field(schema, field_name) when is_binary(field_name) do
+    case Native.schema_remove_field(schema, field_name) do
+      {:ok, new_schema} -> new_schema
+      {:error, reason} -> raise "Failed to remove field: #{reason}"
+      new_schema -> new_schema
+    end
+  end
+
   @doc """
   Returns a list of all field names in the schema.
 
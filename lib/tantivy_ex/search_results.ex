@@ -577,8 +577,11 @@ defmodule TantivyEx.SearchResults do
   defp extract_query_terms_private(query, schema) do
     # For Query objects, use the NIF to extract terms
     case TantivyEx.Native.query_extract_terms(query, schema) do
-      terms when is_list(terms) -> {:ok, terms}
-      error -> {:error, "Failed to extract query terms: #{inspect(error)}"}
+      terms when is_list(terms) ->
+        {:ok, terms |> Enum.map(fn {_field, value} -> value end) |> Enum.uniq() |> Enum.sort()}
+
+      error ->
+        {:error, "Failed to extract query terms: #{inspect(error)}"}
     end
   end
 
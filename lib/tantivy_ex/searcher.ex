@@ -41,18 +41,147 @@ defmodule TantivyEx.Searcher do
     e -> {:error, "Failed to create searcher: #{inspect(e)}"}
   end
 
+  @doc """
+  Creates a new Searcher using an explicit reader reload policy.
+
+  `new/1` uses `:on_commit`, which starts a background watcher that reloads
+  the reader when a commit lands. Batch jobs that commit once and then search
+  many times can use `:manual` to avoid that thread.
+
+  ## Parameters
+
+  - `index`: The index to search
+  - `policy`: `:manual` or `:on_commit`
+
+  ## Examples
+
+      iex> {:ok, searcher} = TantivyEx.Searcher.new_with_policy(index, :manual)
+      iex> is_reference(searcher)
+      true
+  """
+  @spec new_with_policy(Index.t(), :manual | :on_commit) :: {:ok, t()} | {:error, String.t()}
+  def new_with_policy(index, policy) when policy in [:manual, :on_commit] do
+    case Native.index_reader_with_policy(index, Atom.to_string(policy)) do
+      {:error, reason} -> {:error, reason}
+      searcher -> {:ok, searcher}
+    end
+  rescue
+    e -> {:error, "Failed to create searcher: #{inspect(e)}"}
+  end
+
+  def new_with_policy(_index, policy) do
+    {:error, "Unknown reload policy: #{inspect(policy)}. Use :manual or :on_commit"}
+  end
+
+  @doc """
+  Reloads the index and returns a searcher over its latest commit.
+
+  A searcher is a snapshot: it keeps seeing the documents that were committed
+  when it was created. Call this after a commit to read your own writes.
+
+  ## Parameters
+
+  - `index`: The index to search
+
+  ## Examples
+
+      iex> :ok = TantivyEx.IndexWriter.add_document(writer, %{"title" => "New"})
+      iex> {:ok, _opstamp} = TantivyEx.IndexWriter.commit_with_opstamp(writer)
+      iex> {:ok, searcher} = TantivyEx.Searcher.reload(index)
+  """
+  @spec reload(Index.t()) :: {:ok, t()} | {:error, String.t()}
+  def reload(index) do
+    case Native.reader_reload_and_searcher(index) do
+      {:error, reason} -> {:error, reason}
+      searcher -> {:ok, searcher}
+    end
+  rescue
+    e -> {:error, "Failed to reload searcher: #{inspect(e)}"}
+  end
+
+  @doc """
+  Returns a searcher over the latest commit from the index's shared reader.
+
+  Unlike `new/1` and `reload/1`, which build a fresh reader on every call, this
+  keeps one long-lived reader per index and only reloads it when a new commit
+  has landed since the previous call.
+
+  ## Parameters
+
+  - `index`: The index to search
+
+  ## Examples
+
+      iex> {:ok, _opstamp} = TantivyEx.IndexWriter.commit_with_opstamp(writer)
+      iex> {:ok, searcher} = TantivyEx.Searcher.acquire(index)
+  """
+  @spec acquire(Index.t()) :: {:ok, t()} | {:error, String.t()}
+  def acquire(index) do
+    case Native.index_acquire_searcher(index) do
+      {:error, reason} -> {:error, reason}
+      searcher -> {:ok, searcher}
+    end
+  rescue
+    e -> {:error, "Failed to acquire searcher: #{inspect(e)}"}
+  end
+
+  @doc """
+  Waits until the index's last commit has reached the given opstamp.
+
+  Pair it with `TantivyEx.IndexWriter.commit_with_opstamp/1` for deterministic
+  read-your-writes: once this returns `:ok`, a searcher from `reload/1` sees
+  every operation up to `opstamp`. This is useful when the commit happens in
+  another process.
+
+  ## Parameters
+
+  - `index`: The index to watch
+  - `opstamp`: The commit opstamp to wait for
+  - `timeout_ms`: Maximum time to wait in milliseconds (default: 5000)
+
+  ## Examples
+
+      iex> {:ok, opstamp} = TantivyEx.IndexWriter.commit_with_opstamp(writer)
+      iex> :ok = TantivyEx.Searcher.wait_for_opstamp(index, opstamp, 1_000)
+      iex> {:ok, searcher} = TantivyEx.Searcher.reload(index)
+  """
+  @spec wait_for_opstamp(Index.t(), non_neg_integer(), non_neg_integer()) ::
+          :ok | {:error, :timeout | String.t()}
+  def wait_for_opstamp(index, opstamp, timeout_ms \\ 5_000)
+      when is_integer(opstamp) and opstamp >= 0 and is_integer(timeout_ms) and timeout_ms >= 0 do
+    Native.reader_wait_for_opstamp(index, opstamp, timeout_ms)
+  rescue
+    e -> {:error, "Failed to wait for opstamp: #{inspect(e)}"}
+  end
+
   @doc """
   Searches the index with the given query.
 
   This function supports both string queries (which are parsed using AllQuery for now)
   and Query objects created with TantivyEx.Query functions.
 
+  Stored fields are returned by name. Facet fields are always returned as a
+  list of path strings, even when a document holds a single path, so a
+  product in several categories keeps all of them.
+
   ## Parameters
 
   - `searcher`: The Searcher
   - `query`: The search query (string or Query.t())
   - `limit`: Maximum number of results to return (default: 10)
   - `include_docs`: Whether to include full document content (default: true)
+  - `opts`: Keyword list of options
+
+  ## Options
+
+  - `:bytes_as` - How bytes field values are returned: `:base64` strings
+    (default) or raw Elixir binaries with `:binary`
+  - `:timeout_ms` - Stop searching further segments once this many milliseconds
+    have elapsed and return `{:error, :timeout}`. A segment already being
+    searched is finished first. Only applies to `Query.t()` queries
+  - `:explain` - Attach each hit's score explanation under `"explanation"`
+    (default: false). Explaining costs about one extra query evaluation per hit,
+    so keep it for debugging. Only applies to `Query.t()` queries
 
   ## Examples
 
@@ -68,50 +197,202 @@ defmodule TantivyEx.Searcher do
       iex> {:ok, term2} = TantivyEx.Query.term(schema, "body", "world")
       iex> {:ok, boolean_query} = TantivyEx.Query.boolean([term1], [term2], [])
       iex> {:ok, results} = TantivyEx.Searcher.search(searcher, boolean_query, 10)
+
+      # Thumbnails stored in a bytes field, without base64 decoding
+      iex> {:ok, [hit]} = TantivyEx.Searcher.search(searcher, query, 1, true, bytes_as: :binary)
+      iex> is_binary(hit["thumbnail"])
+      true
+
+      # Bound the cost of an expensive query
+      iex> {:ok, query} = TantivyEx.Query.regex(schema, "title", ".*ing.*")
+      iex> TantivyEx.Searcher.search(searcher, query, 10, true, timeout_ms: 50)
+      {:error, :timeout}
+
+      # Inspect why the top hits scored the way they did
+      iex> {:ok, [hit | _]} = TantivyEx.Searcher.search(searcher, query, 3, true, explain: true)
+      iex> %{"value" => _score, "description" => _} = hit["explanation"]
   """
-  @spec search(t(), String.t() | Query.t(), pos_integer(), boolean()) ::
-          {:ok, [search_result()]} | {:error, String.t()}
-  def search(searcher, query, limit \\ 10, include_docs \\ true)
+  @spec search(t(), String.t() | Query.t(), pos_integer(), boolean(), keyword()) ::
+          {:ok, [search_result()]} | {:error, String.t() | :timeout}
+  def search(searcher, query, limit \\ 10, include_docs \\ true, opts \\ [])
 
-  def search(searcher, query, limit, include_docs) when is_binary(query) do
+  def search(searcher, query, limit, include_docs, opts) when is_binary(query) do
     # Legacy string-based search - uses the old implementation for now
     # In the future, this could parse the string with a default parser
-    case Native.searcher_search(searcher, query, limit, include_docs) do
-      {:error, reason} ->
-        {:error, reason}
+    with {:ok, bytes_as} <- bytes_as_option(opts) do
+      case Native.searcher_search(searcher, query, limit, include_docs) do
+        {:error, reason} ->
+          {:error, reason}
 
-      results_json when is_binary(results_json) ->
-        case Jason.decode(results_json) do
-          {:ok, results} -> {:ok, results}
-          {:error, _} -> {:error, "Failed to parse search results"}
-        end
+        results_json when is_binary(results_json) ->
+          case Jason.decode(results_json) do
+            {:ok, results} -> {:ok, represent_bytes(searcher, results, bytes_as)}
+            {:error, _} -> {:error, "Failed to parse search results"}
+          end
 
-      results ->
-        {:ok, results}
+        results ->
+          {:ok, results}
+      end
     end
   rescue
     e -> {:error, "Failed to search: #{inspect(e)}"}
   end
 
-  def search(searcher, query, limit, include_docs) when is_reference(query) do
+  def search(searcher, query, limit, include_docs, opts) when is_reference(query) do
     # Query object-based search - uses the new enhanced search
-    case Native.searcher_search_with_query(searcher, query, limit, include_docs) do
+    with {:ok, bytes_as} <- bytes_as_option(opts),
+         {:ok, timeout_ms} <- timeout_option(opts),
+         {:ok, explain} <- explain_option(opts) do
+      case Native.searcher_search_with_query(
+             searcher,
+             query,
+             limit,
+             include_docs,
+             timeout_ms,
+             explain
+           ) do
+        {:error, reason} ->
+          {:error, reason}
+
+        results_json when is_binary(results_json) ->
+          case Jason.decode(results_json) do
+            {:ok, results} -> {:ok, represent_bytes(searcher, results, bytes_as)}
+            {:error, _} -> {:error, "Failed to parse search results"}
+          end
+
+        results ->
+          {:ok, results}
+      end
+    end
+  rescue
+    e -> {:error, "Failed to search with query: #{inspect(e)}"}
+  end
+
+  defp bytes_as_option(opts) do
+    case Keyword.get(opts, :bytes_as, :base64) do
+      bytes_as when bytes_as in [:base64, :binary] -> {:ok, bytes_as}
+      other -> {:error, "Invalid :bytes_as option: #{inspect(other)}"}
+    end
+  end
+
+  defp timeout_option(opts) do
+    case Keyword.get(opts, :timeout_ms) do
+      nil -> {:ok, nil}
+      timeout_ms when is_integer(timeout_ms) and timeout_ms >= 0 -> {:ok, timeout_ms}
+      other -> {:error, "Invalid :timeout_ms option: #{inspect(other)}"}
+    end
+  end
+
+  defp explain_option(opts) do
+    case Keyword.get(opts, :explain, false) do
+      explain when is_boolean(explain) -> {:ok, explain}
+      other -> {:error, "Invalid :explain option: #{inspect(other)}"}
+    end
+  end
+
+  # Results travel as JSON, so bytes arrive base64-encoded and are decoded here
+  defp represent_bytes(_searcher, results, :base64), do: results
+
+  defp represent_bytes(searcher, results, :binary) do
+    case Native.searcher_bytes_fields(searcher) do
+      [] ->
+        results
+
+      fields ->
+        Enum.map(results, fn result ->
+          Enum.reduce(fields, result, fn field, acc ->
+            case acc do
+              %{^field => value} when is_binary(value) ->
+                Map.put(acc, field, Base.decode64!(value))
+
+              _ ->
+                acc
+            end
+          end)
+        end)
+    end
+  end
+
+  @doc """
+  Searches like `search/4` and also reports live and deleted document totals.
+
+  Deleted documents keep occupying the index until their segments are merged.
+  Comparing `"max_doc"` (which includes them) with `"num_docs"` (live only)
+  tells whether the index is bloated enough to be worth a force-merge.
+
+  ## Parameters
+
+  - `searcher`: The Searcher
+  - `query`: The search query
+  - `limit`: Maximum number of hits to return (default: 10)
+  - `include_docs`: Whether to include full document content (default: true)
+
+  ## Examples
+
+      iex> {:ok, result} = TantivyEx.Searcher.search_with_totals(searcher, query, 10)
+      iex> Map.take(result, ["total_hits", "num_docs", "max_doc", "deleted_docs"])
+      %{"total_hits" => 3, "num_docs" => 40, "max_doc" => 42, "deleted_docs" => 2}
+  """
+  @spec search_with_totals(t(), Query.t(), pos_integer(), boolean()) ::
+          {:ok, map()} | {:error, String.t()}
+  def search_with_totals(searcher, query, limit \\ 10, include_docs \\ true)
+      when is_reference(query) do
+    case Native.searcher_search_with_totals(searcher, query, limit, include_docs) do
       {:error, reason} ->
         {:error, reason}
 
-      results_json when is_binary(results_json) ->
-        case Jason.decode(results_json) do
-          {:ok, results} -> {:ok, results}
+      json when is_binary(json) ->
+        case Jason.decode(json) do
+          {:ok, result} -> {:ok, result}
           {:error, _} -> {:error, "Failed to parse search results"}
         end
+    end
+  rescue
+    e -> {:error, "Failed to search with totals: #{inspect(e)}"}
+  end
+
+  @doc """
+  Runs several independent queries against the searcher in a single call.
+
+  Useful for dashboards that show a handful of result lists at once: the
+  queries share the open reader and cross into native code only once. Each
+  result is tagged with the position of its query, and a query that fails
+  does not fail the others.
+
+  ## Parameters
+
+  - `searcher`: The Searcher
+  - `queries`: List of Query.t()
+  - `limit`: Maximum number of hits per query (default: 10)
+  - `include_docs`: Whether to include full document content (default: true)
+
+  ## Examples
+
+      iex> {:ok, [{0, {:ok, recent}}, {1, {:ok, popular}}]} =
+      ...>   TantivyEx.Searcher.multi_search(searcher, [recent_query, popular_query], 5)
+  """
+  @spec multi_search(t(), [Query.t()], pos_integer(), boolean()) ::
+          {:ok, [{non_neg_integer(), {:ok, [search_result()]} | {:error, String.t()}}]}
+          | {:error, String.t()}
+  def multi_search(searcher, queries, limit \\ 10, include_docs \\ true)
+      when is_list(queries) do
+    case Native.searcher_multi_search(searcher, queries, limit, include_docs) do
+      {:error, reason} ->
+        {:error, reason}
 
-      results ->
-        {:ok, results}
+      json when is_binary(json) ->
+        case Jason.decode(json) do
+          {:ok, results} -> {:ok, Enum.map(results, &multi_search_entry/1)}
+          {:error, _} -> {:error, "Failed to parse search results"}
+        end
     end
   rescue
-    e -> {:error, "Failed to search with query: #{inspect(e)}"}
+    e -> {:error, "Failed to run multi search: #{inspect(e)}"}
   end
 
+  defp multi_search_entry(%{"index" => index, "hits" => hits}), do: {index, {:ok, hits}}
+  defp multi_search_entry(%{"index" => index, "error" => reason}), do: {index, {:error, reason}}
+
   @doc """
   Searches the index and returns only document IDs.
 
@@ -146,6 +427,674 @@ defmodule TantivyEx.Searcher do
     end
   end
 
+  @doc """
+  Counts the documents matching a query without loading or scoring them.
+
+  Cheaper than `search/4` when only the total number of hits is needed.
+
+  ## Parameters
+
+  - `searcher`: The Searcher
+  - `query`: The search query (Query.t())
+
+  ## Examples
+
+      iex> {:ok, query} = TantivyEx.Query.term(schema, "title", "hello")
+      iex> {:ok, count} = TantivyEx.Searcher.count(searcher, query)
+      iex> is_integer(count)
+      true
+  """
+  @spec count(t(), Query.t()) :: {:ok, non_neg_integer()} | {:error, String.t()}
+  def count(searcher, query) when is_reference(query) do
+    case Native.searcher_count(searcher, query) do
+      {:error, reason} -> {:error, reason}
+      count when is_integer(count) -> {:ok, count}
+    end
+  rescue
+    e -> {:error, "Failed to count documents: #{inspect(e)}"}
+  end
+
+  @doc """
+  Fetches the document whose unique key field has the given value.
+
+  The value is coerced to the field type like in `TantivyEx.Query.term/3`.
+  Returns `{:error, :not_found}` when no live document matches, and an error
+  when several documents share the value.
+
+  ## Parameters
+
+  - `searcher`: The Searcher
+  - `field_name`: The unique key field, e.g. `"id"`
+  - `value`: The key to look up
+  - `include_fields`: Stored fields to return; all of them when empty (default: `[]`)
+
+  ## Examples
+
+      iex> {:ok, doc} = TantivyEx.Searcher.get_by_term(searcher, "id", 42)
+      iex> doc["title"]
+      "Hello World"
+
+      iex> TantivyEx.Searcher.get_by_term(searcher, "id", 404)
+      {:error, :not_found}
+  """
+  @spec get_by_term(t(), String.t(), term(), [String.t()]) ::
+          {:ok, map()} | {:error, :not_found | String.t()}
+  def get_by_term(searcher, field_name, value, include_fields \\ [])
+      when is_binary(field_name) and is_list(include_fields) do
+    value =
+      case value do
+        value when is_binary(value) -> value
+        %DateTime{} = value -> DateTime.to_iso8601(value)
+        value -> to_string(value)
+      end
+
+    case Native.searcher_get_by_term(searcher, field_name, value, include_fields) do
+      {:error, reason} ->
+        {:error, reason}
+
+      json when is_binary(json) ->
+        case Jason.decode(json) do
+          {:ok, doc} -> {:ok, doc}
+          {:error, _} -> {:error, "Failed to parse document"}
+        end
+    end
+  rescue
+    e -> {:error, "Failed to get document by term: #{inspect(e)}"}
+  end
+
+  @doc """
+  Returns matching documents without computing relevance scores.
+
+  Intended for queries used purely as filters (e.g. a timestamp range), where
+  scoring would be wasted work. Results carry `"segment_ord"` and `"doc_id"`
+  but no `"score"`, and come back in index order rather than by relevance.
+
+  ## Parameters
+
+  - `searcher`: The Searcher
+  - `query`: The filter query (Query.t())
+  - `limit`: Maximum number of documents to return
+  - `include_docs`: Whether to include stored fields (default: true)
+
+  ## Examples
+
+      iex> {:ok, query} = TantivyEx.Query.range_u64(schema, "timestamp", 1_700_000_000, nil)
+      iex> {:ok, results} = TantivyEx.Searcher.search_unscored(searcher, query, 100)
+  """
+  @spec search_unscored(t(), Query.t(), pos_integer(), boolean()) ::
+          {:ok, [map()]} | {:error, String.t()}
+  def search_unscored(searcher, query, limit, include_docs \\ true)
+      when is_reference(query) and is_integer(limit) and limit > 0 do
+    case Native.searcher_search_unscored(searcher, query, limit, include_docs) do
+      {:error, reason} ->
+        {:error, reason}
+
+      json when is_binary(json) ->
+        case Jason.decode(json) do
+          {:ok, results} -> {:ok, results}
+          {:error, _} -> {:error, "Failed to parse search results"}
+        end
+    end
+  rescue
+    e -> {:error, "Failed to run unscored search: #{inspect(e)}"}
+  end
+
+  @doc """
+  Returns the next batch of matching documents for a full export.
+
+  Documents are returned in index order (segment, then doc id) without scoring,
+  so walking a large result set stays cheap. Pass the returned cursor back to
+  get the following batch; a `nil` cursor means there are no more documents.
+
+  ## Parameters
+
+  - `searcher`: The Searcher
+  - `query`: The search query (Query.t())
+  - `batch_size`: Maximum number of documents per batch
+  - `cursor`: Cursor returned by the previous call, or `nil` to start (default: nil)
+
+  ## Examples
+
+      iex> {:ok, query} = TantivyEx.Query.all()
+      iex> {:ok, docs, cursor} = TantivyEx.Searcher.scroll(searcher, query, 1000)
+      iex> {:ok, more, next_cursor} = TantivyEx.Searcher.scroll(searcher, query, 1000, cursor)
+  """
+  @spec scroll(t(), Query.t(), pos_integer(), {non_neg_integer(), non_neg_integer()} | nil) ::
+          {:ok, [map()], {non_neg_integer(), non_neg_integer()} | nil} | {:error, String.t()}
+  def scroll(searcher, query, batch_size, cursor \\ nil) when is_reference(query) do
+    case Native.searcher_scroll(searcher, query, batch_size, cursor) do
+      {:error, reason} ->
+        {:error, reason}
+
+      json when is_binary(json) ->
+        case Jason.decode(json) do
+          {:ok, %{"documents" => documents, "next_cursor" => [segment_ord, doc_id]}} ->
+            {:ok, documents, {segment_ord, doc_id}}
+
+          {:ok, %{"documents" => documents}} ->
+            {:ok, documents, nil}
+
+          {:error, _} ->
+            {:error, "Failed to parse scroll results"}
+        end
+    end
+  rescue
+    e -> {:error, "Failed to scroll: #{inspect(e)}"}
+  end
+
+  @doc """
+  Returns the page of hits that follows `after_value` when sorting on a fast field.
+
+  This is the `search_after` style of deep pagination: the sort value of the last hit
+  of a page is the cursor for the next one. Since the cursor acts as a range filter,
+  every page costs the same, unlike a growing offset.
+
+  Hits whose sort value equals the cursor are skipped, so sort on a field with unique
+  values such as an id or a timestamp. Date fields are sorted, returned and passed as
+  microseconds since the epoch. Documents without a sort value are never returned.
+
+  ## Parameters
+
+  - `searcher`: The Searcher
+  - `query`: The search query (Query.t())
+  - `limit`: Maximum number of hits per page
+  - `sort_field`: Fast numeric or date field to sort on
+  - `order`: `:asc` or `:desc` (default: :asc)
+  - `after_value`: Cursor returned by the previous call, or `nil` to start (default: nil)
+
+  ## Examples
+
+      iex> {:ok, query} = TantivyEx.Query.all()
+      iex> {:ok, page, cursor} =
+      ...>   TantivyEx.Searcher.search_after(searcher, query, 50, "created_at", :desc)
+      iex> {:ok, next_page, _cursor} =
+      ...>   TantivyEx.Searcher.search_after(searcher, query, 50, "created_at", :desc, cursor)
+  """
+  @spec search_after(t(), Query.t(), pos_integer(), String.t(), :asc | :desc, number() | nil) ::
+          {:ok, [map()], number() | nil} | {:error, String.t()}
+  def search_after(searcher, query, limit, sort_field, order \\ :asc, after_value \\ nil)
+      when is_reference(query) and order in [:asc, :desc] do
+    case Native.searcher_search_after(
+           searcher,
+           query,
+           limit,
+           sort_field,
+           Atom.to_string(order),
+           after_value
+         ) do
+      {:error, reason} ->
+        {:error, reason}
+
+      json when is_binary(json) ->
+        case Jason.decode(json) do
+          {:ok, %{"documents" => documents, "next_cursor" => cursor}} ->
+            {:ok, documents, cursor}
+
+          {:error, _} ->
+            {:error, "Failed to parse search_after results"}
+        end
+    end
+  rescue
+    e -> {:error, "Failed to search after cursor: #{inspect(e)}"}
+  end
+
+  @doc """
+  Performs a search returning both the top documents and facet counts.
+
+  Hits and facet counts are gathered in a single pass over the index, which is
+  what result pages with category navigation usually need. Facet counts are
+  keyed by requested path, each mapping its direct children to document counts.
+
+  ## Parameters
+
+  - `searcher`: The Searcher
+  - `query`: The search query (Query.t())
+  - `limit`: Maximum number of hits to return
+  - `facet_field`: Name of the facet field to count
+  - `facet_paths`: Facet paths whose children are counted (default: ["/"])
+
+  ## Examples
+
+      iex> {:ok, query} = TantivyEx.Query.term(schema, "title", "shoes")
+      iex> {:ok, %{"hits" => hits, "facets" => facets}} =
+      ...>   TantivyEx.Searcher.search_with_facets(searcher, query, 10, "category", ["/"])
+      iex> facets["/"]
+      %{"/men" => 12, "/women" => 8}
+  """
+  @spec search_with_facets(t(), Query.t(), pos_integer(), String.t(), [String.t()]) ::
+          {:ok, %{String.t() => term()}} | {:error, String.t()}
+  def search_with_facets(searcher, query, limit, facet_field, facet_paths \\ ["/"])
+      when is_reference(query) do
+    case Native.searcher_search_with_facets(searcher, query, limit, facet_field, facet_paths) do
+      {:error, reason} ->
+        {:error, reason}
+
+      json when is_binary(json) ->
+        case Jason.decode(json) do
+          {:ok, result} -> {:ok, result}
+          {:error, _} -> {:error, "Failed to parse search results"}
+        end
+    end
+  rescue
+    e -> {:error, "Failed to search with facets: #{inspect(e)}"}
+  end
+
+  @doc """
+  Performs a search keeping only the best-scoring document per field value.
+
+  Results are collapsed on a fast field, e.g. to show one result per domain.
+  Each result carries the `"collapse_key"` it was grouped under; documents
+  without a value for the field are grouped together under `nil`.
+
+  ## Parameters
+
+  - `searcher`: The Searcher
+  - `query`: The search query (Query.t())
+  - `limit`: Maximum number of collapsed results to return
+  - `collapse_field`: Name of the fast field to collapse on
+
+  ## Examples
+
+      iex> {:ok, query} = TantivyEx.Query.term(schema, "title", "elixir")
+      iex> {:ok, results} = TantivyEx.Searcher.search_collapsed(searcher, query, 10, "domain")
+      iex> Enum.map(results, & &1["collapse_key"])
+      ["elixir-lang.org", "hexdocs.pm"]
+  """
+  @spec search_collapsed(t(), Query.t(), pos_integer(), String.t()) ::
+          {:ok, [search_result()]} | {:error, String.t()}
+  def search_collapsed(searcher, query, limit, collapse_field) when is_reference(query) do
+    case Native.searcher_search_collapsed(searcher, query, limit, collapse_field) do
+      {:error, reason} ->
+        {:error, reason}
+
+      json when is_binary(json) ->
+        case Jason.decode(json) do
+          {:ok, results} -> {:ok, results}
+          {:error, _} -> {:error, "Failed to parse search results"}
+        end
+    end
+  rescue
+    e -> {:error, "Failed to search collapsed: #{inspect(e)}"}
+  end
+
+  @doc """
+  Returns document and segment statistics for the searcher.
+
+  `"num_docs"` counts live documents; each entry of `"segments"` reports the
+  live `"doc_count"` and the number of `"deleted"` documents of one segment.
+
+  ## Parameters
+
+  - `searcher`: The Searcher
+
+  ## Examples
+
+      iex> {:ok, stats} = TantivyEx.Searcher.stats(searcher)
+      iex> stats["num_docs"]
+      42
+      iex> [%{"segment_id" => _, "doc_count" => 42, "deleted" => 0}] = stats["segments"]
+  """
+  @spec stats(t()) :: {:ok, map()} | {:error, String.t()}
+  def stats(searcher) do
+    case Native.searcher_stats(searcher) do
+      {:error, reason} ->
+        {:error, reason}
+
+      json when is_binary(json) ->
+        case Jason.decode(json) do
+          {:ok, stats} -> {:ok, stats}
+          {:error, _} -> {:error, "Failed to parse searcher stats"}
+        end
+    end
+  rescue
+    e -> {:error, "Failed to get searcher stats: #{inspect(e)}"}
+  end
+
+  @doc """
+  Reads one fast field value for each document address.
+
+  Addresses are `{segment_ord, doc_id}` tuples, as returned by `scroll/4`.
+  Only the fast field column is read, which is much cheaper than loading
+  stored documents when a single value is needed, e.g. for re-ranking.
+  Documents without a value yield `nil`.
+
+  ## Parameters
+
+  - `searcher`: The Searcher
+  - `field_name`: Name of a fast text, numeric, boolean or date field
+  - `doc_addresses`: List of `{segment_ord, doc_id}` tuples
+
+  ## Examples
+
+      iex> {:ok, docs, _cursor} = TantivyEx.Searcher.scroll(searcher, query, 100)
+      iex> addresses = Enum.map(docs, &{&1["segment_ord"], &1["doc_id"]})
+      iex> {:ok, ranks} = TantivyEx.Searcher.fast_field_values(searcher, "rank", addresses)
+  """
+  @spec fast_field_values(t(), String.t(), [{non_neg_integer(), non_neg_integer()}]) ::
+          {:ok, [term()]} | {:error, String.t()}
+  def fast_field_values(searcher, field_name, doc_addresses)
+      when is_binary(field_name) and is_list(doc_addresses) do
+    case Native.searcher_fast_field_values(searcher, field_name, doc_addresses) do
+      {:error, reason} ->
+        {:error, reason}
+
+      json when is_binary(json) ->
+        case Jason.decode(json) do
+          {:ok, values} -> {:ok, values}
+          {:error, _} -> {:error, "Failed to parse fast field values"}
+        end
+    end
+  rescue
+    e -> {:error, "Failed to read fast field values: #{inspect(e)}"}
+  end
+
+  @doc """
+  Returns the smallest and largest value of a numeric or date fast field.
+
+  Read from the column statistics of each segment without visiting documents,
+  which makes it cheap enough to auto-range a slider or pick a histogram
+  interval before running the aggregation. Values of deleted documents still
+  count until their segment is merged. Dates are returned as timestamps in
+  microseconds.
+
+  ## Parameters
+
+  - `searcher`: The Searcher
+  - `field_name`: Name of a fast u64, i64, f64 or date field
+
+  ## Examples
+
+      iex> {:ok, {min_price, max_price}} = TantivyEx.Searcher.field_range(searcher, "price")
+      iex> interval = (max_price - min_price) / 20
+  """
+  @spec field_range(t(), String.t()) :: {:ok, {number(), number()} | nil} | {:error, String.t()}
+  def field_range(searcher, field_name) when is_binary(field_name) do
+    case Native.searcher_field_range(searcher, field_name) do
+      {:error, reason} ->
+        {:error, reason}
+
+      json when is_binary(json) ->
+        case Jason.decode(json) do
+          {:ok, %{"min" => min, "max" => max}} -> {:ok, {min, max}}
+          {:ok, nil} -> {:ok, nil}
+          _ -> {:error, "Failed to parse field range"}
+        end
+    end
+  rescue
+    e -> {:error, "Failed to read field range: #{inspect(e)}"}
+  end
+
+  @doc """
+  Highlights several fields of one document in a single call.
+
+  Returns a map of field name to an HTML snippet with matched terms wrapped in
+  `<b>` tags. Fields that are not stored text, or that produced no matching
+  fragment, are left out of the map.
+
+  ## Parameters
+
+  - `searcher`: The Searcher
+  - `query`: The query whose terms should be highlighted
+  - `field_names`: Fields to build snippets for
+  - `doc_address`: A `{segment_ord, doc_id}` tuple, as returned by `scroll/4`
+  - `max_chars`: Maximum length of each snippet (default: 150)
+
+  ## Examples
+
+      iex> {:ok, highlights} =
+      ...>   TantivyEx.Searcher.highlight_multi(searcher, query, ["title", "body"], {0, 3})
+      iex> highlights["title"]
+      "Learning <b>Elixir</b>"
+  """
+  @spec highlight_multi(
+          t(),
+          TantivyEx.Query.t(),
+          [String.t()],
+          {non_neg_integer(), non_neg_integer()},
+          pos_integer()
+        ) :: {:ok, %{String.t() => String.t()}} | {:error, String.t()}
+  def highlight_multi(searcher, query, field_names, doc_address, max_chars \\ 150)
+      when is_list(field_names) and is_tuple(doc_address) and is_integer(max_chars) and
+             max_chars > 0 do
+    case Native.searcher_highlight_multi(searcher, query, field_names, doc_address, max_chars) do
+      {:error, reason} ->
+        {:error, reason}
+
+      json when is_binary(json) ->
+        case Jason.decode(json) do
+          {:ok, highlights} -> {:ok, highlights}
+          {:error, _} -> {:error, "Failed to parse highlights"}
+        end
+    end
+  rescue
+    e -> {:error, "Failed to highlight document: #{inspect(e)}"}
+  end
+
+  @doc """
+  Returns the byte offsets of the matched terms in a field of one document.
+
+  For frontends that render their own highlighting instead of using the HTML
+  from `highlight_multi/5`. The stored field is tokenized like it was indexed and
+  every token matching a term of `query` on that field is returned as a
+  `{start, end}` pair, usable with `binary_part/3`. Several values of the field
+  are joined with a single space.
+
+  ## Parameters
+
+  - `searcher`: The Searcher
+  - `query`: The query whose terms should be located
+  - `field_name`: Stored text field to scan
+  - `doc_address`: A `{segment_ord, doc_id}` tuple, as returned by `scroll/4`
+
+  ## Examples
+
+      iex> {:ok, offsets} = TantivyEx.Searcher.match_offsets(searcher, query, "title", {0, 3})
+      iex> offsets
+      [{9, 15}]
+  """
+  @spec match_offsets(
+          t(),
+          TantivyEx.Query.t(),
+          String.t(),
+          {non_neg_integer(), non_neg_integer()}
+        ) :: {:ok, [{non_neg_integer(), non_neg_integer()}]} | {:error, String.t()}
+  def match_offsets(searcher, query, field_name, doc_address)
+      when is_binary(field_name) and is_tuple(doc_address) do
+    case Native.searcher_match_offsets(searcher, query, field_name, doc_address) do
+      {:error, reason} -> {:error, reason}
+      offsets when is_list(offsets) -> {:ok, offsets}
+    end
+  rescue
+    e -> {:error, "Failed to find match offsets: #{inspect(e)}"}
+  end
+
+  @doc """
+  Lists the distinct terms indexed for a text field.
+
+  Terms are returned in lexicographic order together with the number of
+  documents containing them. Useful to build autocomplete dictionaries or to
+  check how a field was tokenized. Document frequencies still include deleted
+  documents until their segments are merged.
+
+  ## Parameters
+
+  - `searcher`: The Searcher
+  - `field_name`: An indexed text field
+  - `prefix`: Only list terms starting with this prefix (default: `""`)
+  - `limit`: Maximum number of terms to return (default: 100)
+
+  ## Examples
+
+      iex> {:ok, terms} = TantivyEx.Searcher.field_terms(searcher, "title", "elix", 10)
+      iex> terms
+      [%{term: "elixir", doc_freq: 2}]
+  """
+  @spec field_terms(t(), String.t(), String.t(), non_neg_integer()) ::
+          {:ok, [%{term: String.t(), doc_freq: non_neg_integer()}]} | {:error, String.t()}
+  def field_terms(searcher, field_name, prefix \\ "", limit \\ 100)
+      when is_binary(field_name) and is_binary(prefix) and is_integer(limit) and limit >= 0 do
+    case Native.searcher_field_terms(searcher, field_name, prefix, limit) do
+      {:error, reason} ->
+        {:error, reason}
+
+      json when is_binary(json) ->
+        case Jason.decode(json) do
+          {:ok, terms} ->
+            {:ok, Enum.map(terms, &%{term: &1["term"], doc_freq: &1["doc_freq"]})}
+
+          {:error, _} ->
+            {:error, "Failed to parse field terms"}
+        end
+    end
+  rescue
+    e -> {:error, "Failed to list field terms: #{inspect(e)}"}
+  end
+
+  @doc """
+  Suggests completions for a prefix, most frequent terms first.
+
+  Walks the term dictionary directly, which is far cheaper than a wildcard
+  query and well suited to type-ahead. Terms with the same document frequency
+  are returned in lexicographic order.
+
+  ## Parameters
+
+  - `searcher`: The Searcher
+  - `field_name`: An indexed text field
+  - `prefix`: The text typed so far, already normalized like the field
+    (e.g. lowercased for the default tokenizer)
+  - `limit`: Maximum number of completions (default: 10)
+
+  ## Examples
+
+      iex> {:ok, completions} = TantivyEx.Searcher.autocomplete(searcher, "title", "el", 5)
+      iex> Enum.map(completions, & &1.term)
+      ["elixir", "elm"]
+  """
+  @spec autocomplete(t(), String.t(), String.t(), non_neg_integer()) ::
+          {:ok, [%{term: String.t(), doc_freq: non_neg_integer()}]} | {:error, String.t()}
+  def autocomplete(searcher, field_name, prefix, limit \\ 10)
+      when is_binary(field_name) and is_binary(prefix) and is_integer(limit) and limit >= 0 do
+    case Native.searcher_autocomplete(searcher, field_name, prefix, limit) do
+      {:error, reason} ->
+        {:error, reason}
+
+      json when is_binary(json) ->
+        case Jason.decode(json) do
+          {:ok, terms} ->
+            {:ok, Enum.map(terms, &%{term: &1["term"], doc_freq: &1["doc_freq"]})}
+
+          {:error, _} ->
+            {:error, "Failed to parse completions"}
+        end
+    end
+  rescue
+    e -> {:error, "Failed to autocomplete: #{inspect(e)}"}
+  end
+
+  @doc """
+  Suggests spelling corrections for a term ("did you mean").
+
+  Finds the terms of a field within `max_distance` edits of `term` by walking
+  the term dictionary with a Levenshtein automaton, and ranks them by document
+  frequency, then by edit distance. `term` is normalized like `TantivyEx.Query.term/3`,
+  so it must analyze to a single term. The term itself is included when it
+  occurs in the index; compare `:distance` to 0 to tell.
+
+  ## Parameters
+
+  - `searcher`: The Searcher
+  - `field_name`: An indexed text field
+  - `term`: The possibly misspelled term
+  - `max_distance`: Maximum edit distance, from 0 to 2 (default: 2). Swapping
+    two adjacent characters counts as one edit
+  - `limit`: Maximum number of suggestions (default: 5)
+
+  ## Examples
+
+      iex> {:ok, [best | _]} = TantivyEx.Searcher.suggest(searcher, "title", "elixer")
+      iex> best.term
+      "elixir"
+  """
+  @spec suggest(t(), String.t(), String.t(), 0..2, non_neg_integer()) ::
+          {:ok, [%{term: String.t(), doc_freq: non_neg_integer(), distance: 0..2}]}
+          | {:error, String.t()}
+  def suggest(searcher, field_name, term, max_distance \\ 2, limit \\ 5)
+      when is_binary(field_name) and is_binary(term) and is_integer(max_distance) and
+             is_integer(limit) and limit >= 0 do
+    case Native.searcher_suggest(searcher, field_name, term, max_distance, limit) do
+      {:error, reason} ->
+        {:error, reason}
+
+      json when is_binary(json) ->
+        case Jason.decode(json) do
+          {:ok, suggestions} ->
+            {:ok,
+             Enum.map(
+               suggestions,
+               &%{term: &1["term"], doc_freq: &1["doc_freq"], distance: &1["distance"]}
+             )}
+
+          {:error, _} ->
+            {:error, "Failed to parse suggestions"}
+        end
+    end
+  rescue
+    e -> {:error, "Failed to suggest: #{inspect(e)}"}
+  end
+
+  @doc """
+  Returns corpus statistics for a single term.
+
+  `doc_freq` is the number of documents containing the term and
+  `total_term_freq` the number of times it occurs across them, the raw inputs
+  to IDF and BM25 for custom ranking. The value is coerced and normalized like
+  in `TantivyEx.Query.term/3`, and must resolve to a single term. Deleted
+  documents are counted until their segment is merged.
+
+  Occurrences are only recorded for fields indexed with term frequencies
+  (e.g. `:fast_stored` text fields). On other fields, such as `:text`,
+  `total_term_freq` is `nil`.
+
+  ## Parameters
+
+  - `searcher`: The Searcher
+  - `field_name`: An indexed field
+  - `value`: The term to look up
+
+  ## Examples
+
+      iex> TantivyEx.Searcher.term_stats(searcher, "body", "elixir")
+      {:ok, %{doc_freq: 2, total_term_freq: 5}}
+  """
+  @spec term_stats(t(), String.t(), term()) ::
+          {:ok, %{doc_freq: non_neg_integer(), total_term_freq: non_neg_integer() | nil}}
+          | {:error, String.t()}
+  def term_stats(searcher, field_name, value) when is_binary(field_name) do
+    value =
+      case value do
+        value when is_binary(value) -> value
+        %DateTime{} = value -> DateTime.to_iso8601(value)
+        value -> to_string(value)
+      end
+
+    case Native.searcher_term_stats(searcher, field_name, value) do
+      {:error, reason} ->
+        {:error, reason}
+
+      json when is_binary(json) ->
+        case Jason.decode(json) do
+          {:ok, stats} ->
+            {:ok, %{doc_freq: stats["doc_freq"], total_term_freq: stats["total_term_freq"]}}
+
+          {:error, _} ->
+            {:error, "Failed to parse term statistics"}
+        end
+    end
+  rescue
+    e -> {:error, "Failed to get term statistics: #{inspect(e)}"}
+  end
+
   @doc """
   Performs a search and returns full documents with metadata.
 
@@ -66,7 +66,10 @@ defmodule TantivyEx.Tokenizer do
   @type tokenizer_name :: String.t()
   @type tokenizer_result :: {:ok, String.t()} | {:error, String.t()}
   @type tokens :: [String.t()]
-  @type detailed_tokens :: [{String.t(), non_neg_integer(), non_neg_integer()}]
+  @type detailed_tokens :: [
+          {String.t(), non_neg_integer(), non_neg_integer(), non_neg_integer(),
+           pos_integer()}
+        ]
 
   @doc """
   Register default tokenizers with sensible configurations.
@@ -165,6 +168,41 @@ defmodule TantivyEx.Tokenizer do
     end
   end
 
+  @doc """
+  Register a tokenizer that splits on delimiter characters and lowercases tokens.
+
+  Useful for tag lists stored in a single field, such as `"Red, Green;blue"`.
+  Whitespace around each token is trimmed, while whitespace inside a token is
+  kept, so `"dark red"` stays a single token.
+
+  ## Parameters
+
+  - `name`: Name to register the tokenizer under
+  - `delimiters`: Delimiter characters, as a string (`",;"`) or a list of
+    single-character strings (`[",", ";"]`)
+
+  ## Examples
+
+      iex> TantivyEx.Tokenizer.register_delimiter_tokenizer("tags", ",;")
+      {:ok, "Delimiter tokenizer 'tags' registered successfully"}
+      iex> TantivyEx.Tokenizer.tokenize_text("tags", "Red, Dark Green;blue")
+      ["red", "dark green", "blue"]
+  """
+  @spec register_delimiter_tokenizer(tokenizer_name(), String.t() | [String.t()]) ::
+          tokenizer_result()
+  def register_delimiter_tokenizer(name, delimiters) when is_list(delimiters) do
+    register_delimiter_tokenizer(name, Enum.join(delimiters))
+  end
+
+  def register_delimiter_tokenizer(name, delimiters)
+      when is_binary(name) and is_binary(delimiters) do
+    case Native.register_delimiter_tokenizer(name, delimiters) do
+      {:ok, result} -> {:ok, result}
+      result when is_binary(result) -> {:ok, result}
+      {:error, reason} -> {:error, reason}
+    end
+  end
+
   @doc """
   Register an N-gram tokenizer.
 
@@ -291,6 +329,77 @@ defmodule TantivyEx.Tokenizer do
     end
   end
 
+  @doc """
+  Register a text analyzer from an ordered list of token filters.
+
+  Unlike `register_text_analyzer/6`, every filter is optional and filters run
+  in exactly the order given, so e.g. ASCII folding can be applied before or
+  after stemming.
+
+  ## Parameters
+
+  - `name`: Name to register the analyzer under
+  - `base_tokenizer`: Base tokenizer ("simple", "whitespace" or "raw")
+  - `filters`: Ordered list of filter specs. Supported specs:
+    - `"lowercase"`
+    - `"ascii_fold"`
+    - `"alpha_num_only"`
+    - `"remove_long:<limit>"` - drop tokens of `limit` bytes or longer
+    - `"stop:<lang>"` - stop word removal for a language code such as `"en"`
+    - `"stem:<lang>"` - stemming for a language code such as `"en"`
+
+  ## Examples
+
+      iex> TantivyEx.Tokenizer.register_custom_analyzer(
+      ...>   "folded_en",
+      ...>   "simple",
+      ...>   ["lowercase", "remove_long:40", "stem:en", "ascii_fold"]
+      ...> )
+      {:ok, "Custom analyzer 'folded_en' registered successfully"}
+  """
+  @spec register_custom_analyzer(tokenizer_name(), String.t(), [String.t()]) ::
+          tokenizer_result()
+  def register_custom_analyzer(name, base_tokenizer, filters)
+      when is_binary(name) and is_binary(base_tokenizer) and is_list(filters) do
+    case Native.register_custom_analyzer(name, base_tokenizer, filters) do
+      {:ok, result} -> {:ok, result}
+      result when is_binary(result) -> {:ok, result}
+      {:error, reason} -> {:error, reason}
+    end
+  end
+
+  @doc """
+  Register a tokenizer that strips HTML before tokenizing.
+
+  Tags, comments and `<script>`/`<style>` contents are removed and common
+  entities (`&amp;`, `&lt;`, `&nbsp;`, numeric references, ...) are decoded
+  before the base tokenizer runs. Each tag acts as a word separator. Token
+  offsets refer to the original HTML, so highlights line up with the stored
+  markup.
+
+  ## Parameters
+
+  - `name`: Name to register the tokenizer under
+  - `base_tokenizer`: Base tokenizer ("simple", "whitespace" or "raw")
+  - `lowercase`: Whether to lowercase tokens (default: true)
+
+  ## Examples
+
+      iex> TantivyEx.Tokenizer.register_html_tokenizer("html", "simple")
+      {:ok, "HTML tokenizer 'html' registered successfully"}
+      iex> TantivyEx.Tokenizer.tokenize_text("html", "<p>Fish &amp; <b>Chips</b></p>")
+      ["fish", "chips"]
+  """
+  @spec register_html_tokenizer(tokenizer_name(), String.t(), boolean()) :: tokenizer_result()
+  def register_html_tokenizer(name, base_tokenizer, lowercase \\ true)
+      when is_binary(name) and is_binary(base_tokenizer) and is_boolean(lowercase) do
+    case Native.register_html_tokenizer(name, base_tokenizer, lowercase) do
+      {:ok, result} -> {:ok, result}
+      result when is_binary(result) -> {:ok, result}
+      {:error, reason} -> {:error, reason}
+    end
+  end
+
   @doc """
   Get a list of all registered tokenizers.
 
@@ -337,10 +446,38 @@ defmodule TantivyEx.Tokenizer do
     end
   end
 
+  @doc """
+  Tokenize many texts with the same registered tokenizer in one call.
+
+  Much cheaper than calling `tokenize_text/2` per string when preprocessing a
+  large corpus, since the tokenizer is looked up once for the whole batch.
+  Returns one token list per input text, in order.
+
+  ## Parameters
+
+  - `tokenizer_name`: Name of the registered tokenizer
+  - `texts`: List of texts to tokenize
+
+  ## Examples
+
+      iex> TantivyEx.Tokenizer.register_default_tokenizers()
+      iex> TantivyEx.Tokenizer.tokenize_texts("en_stem", ["running fast", "Jumped"])
+      [["run", "fast"], ["jump"]]
+  """
+  @spec tokenize_texts(tokenizer_name(), [String.t()]) :: [tokens()]
+  def tokenize_texts(tokenizer_name, texts) when is_binary(tokenizer_name) and is_list(texts) do
+    case Native.tokenize_texts(tokenizer_name, texts) do
+      token_lists when is_list(token_lists) -> token_lists
+      {:error, reason} -> raise "Tokenization failed: #{reason}"
+    end
+  end
+
   @doc """
   Tokenize text and return detailed token information including positions.
 
-  Returns tuples of {token, start_offset, end_offset}.
+  Returns tuples of `{token, start_offset, end_offset, position, position_length}`.
+  `position` is the token position the analyzer assigned (the one phrase queries
+  match against) and `position_length` is the number of positions the token spans.
 
   ## Parameters
 
@@ -351,7 +488,7 @@ defmodule TantivyEx.Tokenizer do
 
       iex> TantivyEx.Tokenizer.register_default_tokenizers()
       iex> TantivyEx.Tokenizer.tokenize_text_detailed("simple", "Hello World")
-      [{"hello", 0, 5}, {"world", 6, 11}]
+      [{"hello", 0, 5, 0, 1}, {"world", 6, 11, 1, 1}]
   """
   @spec tokenize_text_detailed(tokenizer_name(), String.t()) :: detailed_tokens()
   def tokenize_text_detailed(tokenizer_name, text)
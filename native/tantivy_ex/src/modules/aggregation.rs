@@ -5,17 +5,23 @@ use tantivy::aggregation::agg_req::{Aggregation, AggregationVariants, Aggregatio
 use tantivy::aggregation::agg_result::AggregationResults;
 use tantivy::aggregation::bucket::RangeAggregationRange;
 use tantivy::aggregation::bucket::{
-    DateHistogramAggregationReq, HistogramAggregation, RangeAggregation, TermsAggregation,
+    DateHistogramAggregationReq, HistogramAggregation, HistogramBounds, RangeAggregation,
+    TermsAggregation,
 };
 use tantivy::aggregation::metric::{
     AverageAggregation, CountAggregation, MaxAggregation, MinAggregation, PercentileValues,
     PercentilesAggregationReq, StatsAggregation, SumAggregation,
 };
 use tantivy::aggregation::{AggregationCollector, AggregationLimitsGuard, Key};
+use tantivy::collector::{Collector, MultiCollector, TopDocs};
+use tantivy::query::Query;
 use tantivy::schema::OwnedValue;
-use tantivy::schema::Schema;
+use tantivy::schema::{FieldType, Schema};
+use tantivy::{DateTime, DocAddress, Order, Searcher};
 
-use crate::modules::resources::{QueryResource, SearcherResource};
+use crate::modules::resources::{QueryResource, SchemaResource, SearcherResource};
+use crate::modules::schema::field_type_name;
+use crate::modules::search::parse_sort_order;
 
 #[derive(Debug, Clone)]
 pub struct AggregationRequest {
@@ -29,10 +35,22 @@ pub struct AggregationRequest {
 #[derive(Debug, Clone)]
 pub enum AggregationType {
     // Bucket aggregations
-    Terms { size: Option<usize> },
-    Histogram { interval: f64 },
-    DateHistogram { interval: String },
-    Range { ranges: Vec<RangeSpec> },
+    Terms {
+        size: Option<usize>,
+        shard_size: Option<usize>,
+    },
+    Histogram {
+        interval: f64,
+        offset: Option<f64>,
+    },
+    DateHistogram {
+        interval: DateInterval,
+        /// Shift of the bucket grid, in milliseconds
+        offset_ms: Option<i64>,
+    },
+    Range {
+        ranges: Vec<RangeSpec>,
+    },
 
     // Metric aggregations
     Avg,
@@ -41,7 +59,18 @@ pub enum AggregationType {
     Sum,
     Count,
     Stats,
-    Percentiles { percents: Vec<f64> },
+    Percentiles {
+        percents: Vec<f64>,
+    },
+    PercentileRanks {
+        values: Vec<f64>,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub enum DateInterval {
+    Fixed(String),
+    Calendar(String),
 }
 
 #[derive(Debug, Clone)]
@@ -56,6 +85,8 @@ pub struct AggregationOptions {
     pub min_doc_count: Option<u64>,
     pub missing: Option<String>,
     pub keyed: Option<bool>,
+    pub extended_bounds: Option<HistogramBounds>,
+    pub hard_bounds: Option<HistogramBounds>,
 }
 
 #[rustler::nif(schedule = "DirtyCpu")]
@@ -65,16 +96,52 @@ pub fn run_aggregations<'a>(
     query_res: ResourceArc<QueryResource>,
     aggregations_json: String,
 ) -> NifResult<Term<'a>> {
-    let aggregation_requests = match parse_aggregation_requests(&aggregations_json) {
-        Ok(requests) => requests,
-        Err(e) => return Ok(format!("Error parsing aggregations: {}", e).encode(env)),
-    };
+    match collect_aggregations(
+        &searcher_res.searcher,
+        query_res.query.as_ref(),
+        &aggregations_json,
+    ) {
+        Ok(json_result) => match serde_json::to_string(&json_result) {
+            Ok(json_str) => Ok(json_str.encode(env)),
+            Err(e) => Ok(format!("Error serializing result: {}", e).encode(env)),
+        },
+        Err(e) => Ok(e.encode(env)),
+    }
+}
+
+/// Like `run_aggregations`, but returns the result as Elixir terms.
+///
+/// Objects become maps with string keys, integers stay integers and floats
+/// stay floats, so the result is identical to the decoded JSON without paying
+/// for serialization on one side and decoding on the other.
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn run_aggregations_terms<'a>(
+    env: Env<'a>,
+    searcher_res: ResourceArc<SearcherResource>,
+    query_res: ResourceArc<QueryResource>,
+    aggregations_json: String,
+) -> NifResult<Term<'a>> {
+    let result = collect_aggregations(
+        &searcher_res.searcher,
+        query_res.query.as_ref(),
+        &aggregations_json,
+    )
+    .map_err(|e| rustler::Error::Term(Box::new(e)))?;
+
+    json_value_to_term(env, &result)
+}
+
+fn collect_aggregations(
+    searcher: &Searcher,
+    query: &dyn Query,
+    aggregations_json: &str,
+) -> Result<JsonValue, String> {
+    let aggregation_requests = parse_aggregation_requests(aggregations_json)
+        .map_err(|e| format!("Error parsing aggregations: {}", e))?;
 
     let tantivy_aggregations =
-        match build_tantivy_aggregations(&aggregation_requests, &searcher_res.searcher.schema()) {
-            Ok(aggs) => aggs,
-            Err(e) => return Ok(format!("Error building aggregations: {}", e).encode(env)),
-        };
+        build_tantivy_aggregations(&aggregation_requests, &searcher.schema())
+            .map_err(|e| format!("Error building aggregations: {}", e))?;
 
     let limits = AggregationLimitsGuard::new(
         Some(500_000_000), // 500MB default memory limit
@@ -82,19 +149,53 @@ pub fn run_aggregations<'a>(
     );
     let collector = AggregationCollector::from_aggs(tantivy_aggregations, limits);
 
-    match searcher_res.searcher.search(&query_res.query, &collector) {
-        Ok(agg_result) => {
-            let json_result =
-                convert_aggregation_result_to_json(&agg_result, &aggregation_requests);
-            match serde_json::to_string(&json_result) {
-                Ok(json_str) => Ok(json_str.encode(env)),
-                Err(e) => Ok(format!("Error serializing result: {}", e).encode(env)),
+    // AggregationCollector does not require scoring, so the query runs without BM25
+    let agg_result = searcher
+        .search(query, &collector)
+        .map_err(|e| format!("Error executing aggregations: {}", e))?;
+
+    Ok(convert_aggregation_result_to_json(
+        &agg_result,
+        &aggregation_requests,
+    ))
+}
+
+/// Encode a JSON value as the term `Jason.decode/1` would produce for it
+fn json_value_to_term<'a>(env: Env<'a>, value: &JsonValue) -> NifResult<Term<'a>> {
+    Ok(match value {
+        JsonValue::Null => rustler::types::atom::nil().encode(env),
+        JsonValue::Bool(b) => b.encode(env),
+        JsonValue::Number(n) => {
+            if let Some(u) = n.as_u64() {
+                u.encode(env)
+            } else if let Some(i) = n.as_i64() {
+                i.encode(env)
+            } else if let Some(f) = n.as_f64() {
+                f.encode(env)
+            } else {
+                rustler::types::atom::nil().encode(env)
             }
         }
-        Err(e) => Ok(format!("Error executing aggregations: {}", e).encode(env)),
-    }
+        JsonValue::String(s) => s.encode(env),
+        JsonValue::Array(items) => items
+            .iter()
+            .map(|item| json_value_to_term(env, item))
+            .collect::<NifResult<Vec<Term<'a>>>>()?
+            .encode(env),
+        JsonValue::Object(object) => {
+            let mut map = rustler::types::map::map_new(env);
+            for (key, item) in object {
+                map = map.map_put(key.as_str(), json_value_to_term(env, item)?)?;
+            }
+            map
+        }
+    })
 }
 
+/// Search returning hits and aggregations from a single pass.
+///
+/// Hits are ordered by relevance, or by the fast field of `sort` given as
+/// `{field, "asc" | "desc"}`.
 #[rustler::nif(schedule = "DirtyCpu")]
 pub fn run_search_with_aggregations<'a>(
     env: Env<'a>,
@@ -102,16 +203,60 @@ pub fn run_search_with_aggregations<'a>(
     query_res: ResourceArc<QueryResource>,
     aggregations_json: String,
     search_limit: usize,
+    sort: Option<(String, String)>,
+) -> NifResult<Term<'a>> {
+    search_with_aggregations(
+        env,
+        &searcher_res.searcher,
+        query_res.query.as_ref(),
+        None,
+        &aggregations_json,
+        search_limit,
+        sort,
+    )
+}
+
+/// Search where hits honor `query` but aggregations are computed over `agg_query`.
+///
+/// Passing `AllQuery` as `agg_query` gives global aggregations: facet counts that
+/// don't shrink as filters are applied to the hits.
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn run_search_with_separate_aggregations<'a>(
+    env: Env<'a>,
+    searcher_res: ResourceArc<SearcherResource>,
+    query_res: ResourceArc<QueryResource>,
+    agg_query_res: ResourceArc<QueryResource>,
+    aggregations_json: String,
+    search_limit: usize,
+    sort: Option<(String, String)>,
 ) -> NifResult<Term<'a>> {
-    use tantivy::collector::{MultiCollector, TopDocs};
+    search_with_aggregations(
+        env,
+        &searcher_res.searcher,
+        query_res.query.as_ref(),
+        Some(agg_query_res.query.as_ref()),
+        &aggregations_json,
+        search_limit,
+        sort,
+    )
+}
 
-    let aggregation_requests = match parse_aggregation_requests(&aggregations_json) {
+fn search_with_aggregations<'a>(
+    env: Env<'a>,
+    searcher: &Searcher,
+    query: &dyn Query,
+    agg_query: Option<&dyn Query>,
+    aggregations_json: &str,
+    search_limit: usize,
+    sort: Option<(String, String)>,
+) -> NifResult<Term<'a>> {
+    let aggregation_requests = match parse_aggregation_requests(aggregations_json) {
         Ok(requests) => requests,
         Err(e) => return Ok(format!("Error parsing aggregations: {}", e).encode(env)),
     };
 
     let tantivy_aggregations =
-        match build_tantivy_aggregations(&aggregation_requests, &searcher_res.searcher.schema()) {
+        match build_tantivy_aggregations(&aggregation_requests, &searcher.schema()) {
             Ok(aggs) => aggs,
             Err(e) => return Ok(format!("Error building aggregations: {}", e).encode(env)),
         };
@@ -121,30 +266,59 @@ pub fn run_search_with_aggregations<'a>(
         Some(65535),       // Default bucket limit
     );
     let agg_collector = AggregationCollector::from_aggs(tantivy_aggregations, limits);
-    let top_docs_collector = TopDocs::with_limit(search_limit);
-
-    let mut multi_collector = MultiCollector::new();
-    let agg_handle = multi_collector.add_collector(agg_collector);
-    let top_docs_handle = multi_collector.add_collector(top_docs_collector);
+    let top_docs = TopDocs::with_limit(search_limit);
+
+    let search_result = match sort {
+        None => collect_hits_and_aggregations(searcher, query, agg_query, agg_collector, top_docs),
+        Some((sort_field, order)) => {
+            let (field_type, order) =
+                match sorted_hits_field_type(searcher.schema(), &sort_field, &order) {
+                    Ok(sort_type) => sort_type,
+                    Err(e) => return Ok(format!("Error sorting hits: {}", e).encode(env)),
+                };
+            match field_type {
+                FieldType::U64(_) => collect_hits_and_aggregations(
+                    searcher,
+                    query,
+                    agg_query,
+                    agg_collector,
+                    top_docs.order_by_fast_field::<u64>(&sort_field, order),
+                ),
+                FieldType::I64(_) => collect_hits_and_aggregations(
+                    searcher,
+                    query,
+                    agg_query,
+                    agg_collector,
+                    top_docs.order_by_fast_field::<i64>(&sort_field, order),
+                ),
+                FieldType::F64(_) => collect_hits_and_aggregations(
+                    searcher,
+                    query,
+                    agg_query,
+                    agg_collector,
+                    top_docs.order_by_fast_field::<f64>(&sort_field, order),
+                ),
+                _ => collect_hits_and_aggregations(
+                    searcher,
+                    query,
+                    agg_query,
+                    agg_collector,
+                    top_docs.order_by_fast_field::<DateTime>(&sort_field, order),
+                ),
+            }
+        }
+    };
 
-    match searcher_res
-        .searcher
-        .search(&query_res.query, &multi_collector)
-    {
-        Ok(mut multi_fruit) => {
-            let agg_result = agg_handle.extract(&mut multi_fruit);
-            let top_docs = top_docs_handle.extract(&mut multi_fruit);
+    match search_result {
+        Ok((agg_result, top_docs)) => {
             // Convert search results to JSON
             let mut hits = Vec::new();
-            for (_score, doc_address) in top_docs {
-                match searcher_res
-                    .searcher
-                    .doc::<tantivy::TantivyDocument>(doc_address)
-                {
+            for doc_address in top_docs {
+                match searcher.doc::<tantivy::TantivyDocument>(doc_address) {
                     Ok(doc) => {
                         let mut doc_map = serde_json::Map::new();
                         for (field, field_value) in doc.field_values() {
-                            let field_name = searcher_res.searcher.schema().get_field_name(field);
+                            let field_name = searcher.schema().get_field_name(field);
                             let owned_value: OwnedValue = field_value.into();
                             let value = convert_owned_value_to_json(&owned_value);
                             doc_map.insert(field_name.to_string(), value);
@@ -177,6 +351,196 @@ pub fn run_search_with_aggregations<'a>(
     }
 }
 
+/// Type of a fast numeric or date field hits can be sorted on, with the parsed order
+fn sorted_hits_field_type(
+    schema: &Schema,
+    sort_field: &str,
+    order: &str,
+) -> Result<(FieldType, Order), String> {
+    let field = schema
+        .get_field(sort_field)
+        .map_err(|_| format!("Field '{}' not found in schema", sort_field))?;
+    let field_entry = schema.get_field_entry(field);
+    let numeric_or_date = matches!(
+        field_entry.field_type(),
+        FieldType::U64(_) | FieldType::I64(_) | FieldType::F64(_) | FieldType::Date(_)
+    );
+    if !field_entry.is_fast() || !numeric_or_date {
+        return Err(format!(
+            "Field '{}' must be a fast numeric or date field to sort on",
+            sort_field
+        ));
+    }
+    Ok((field_entry.field_type().clone(), parse_sort_order(order)?))
+}
+
+/// Collect the hits of `hits_collector` along with the aggregations.
+///
+/// Without a separate `agg_query`, hits and aggregations share the query and
+/// are collected in one pass.
+fn collect_hits_and_aggregations<S, C>(
+    searcher: &Searcher,
+    query: &dyn Query,
+    agg_query: Option<&dyn Query>,
+    agg_collector: AggregationCollector,
+    hits_collector: C,
+) -> tantivy::Result<(AggregationResults, Vec<DocAddress>)>
+where
+    C: Collector<Fruit = Vec<(S, DocAddress)>>,
+{
+    let (agg_result, hits) = match agg_query {
+        None => {
+            let mut multi_collector = MultiCollector::new();
+            let agg_handle = multi_collector.add_collector(agg_collector);
+            let hits_handle = multi_collector.add_collector(hits_collector);
+
+            let mut multi_fruit = searcher.search(query, &multi_collector)?;
+            let agg_result = agg_handle.extract(&mut multi_fruit);
+            let hits = hits_handle.extract(&mut multi_fruit);
+            (agg_result, hits)
+        }
+        Some(agg_query) => {
+            let hits = searcher.search(query, &hits_collector)?;
+            let agg_result = searcher.search(agg_query, &agg_collector)?;
+            (agg_result, hits)
+        }
+    };
+
+    Ok((
+        agg_result,
+        hits.into_iter()
+            .map(|(_, doc_address)| doc_address)
+            .collect(),
+    ))
+}
+
+/// Validate an aggregation request against a schema without running it.
+///
+/// Returns `{"valid": bool, "errors": [...]}`. Each error names the dotted
+/// aggregation path, the field and one of `invalid_request`, `unknown_field`,
+/// `not_fast` or `unsupported_field_type`.
+#[rustler::nif]
+pub fn validate_aggregations(
+    schema_res: ResourceArc<SchemaResource>,
+    aggregations_json: String,
+) -> NifResult<String> {
+    let schema = &schema_res.schema;
+    let mut errors = Vec::new();
+
+    match parse_aggregation_requests(&aggregations_json) {
+        Ok(requests) => {
+            for (name, request) in &requests {
+                collect_aggregation_errors(name, request, schema, &mut errors);
+            }
+            // Field checks passed, so anything left is an invalid option combination
+            if errors.is_empty() {
+                if let Err(e) = build_tantivy_aggregations(&requests, schema) {
+                    errors.push(validation_error(None, None, "invalid_request", e));
+                }
+            }
+        }
+        Err(e) => errors.push(validation_error(None, None, "invalid_request", e)),
+    }
+
+    let response = json!({
+        "valid": errors.is_empty(),
+        "errors": errors,
+    });
+    Ok(response.to_string())
+}
+
+fn validation_error(
+    aggregation: Option<&str>,
+    field: Option<&str>,
+    error: &str,
+    message: String,
+) -> JsonValue {
+    json!({
+        "aggregation": aggregation,
+        "field": field,
+        "error": error,
+        "message": message,
+    })
+}
+
+fn collect_aggregation_errors(
+    path: &str,
+    request: &AggregationRequest,
+    schema: &Schema,
+    errors: &mut Vec<JsonValue>,
+) {
+    if let Err((error, message)) = check_aggregation_field(request, schema) {
+        errors.push(validation_error(
+            Some(path),
+            Some(&request.field),
+            error,
+            message,
+        ));
+    }
+
+    for (sub_name, sub_request) in &request.sub_aggregations {
+        collect_aggregation_errors(
+            &format!("{}.{}", path, sub_name),
+            sub_request,
+            schema,
+            errors,
+        );
+    }
+}
+
+/// Check that the field of a single aggregation exists, has a suitable type and is fast.
+///
+/// Errors carry the validation error kind and a message naming the field and its type.
+fn check_aggregation_field(
+    request: &AggregationRequest,
+    schema: &Schema,
+) -> Result<(), (&'static str, String)> {
+    let field = schema.get_field(&request.field).map_err(|_| {
+        (
+            "unknown_field",
+            format!("Field '{}' not found in schema", request.field),
+        )
+    })?;
+    let field_entry = schema.get_field_entry(field);
+    let field_type = field_entry.field_type();
+    let numeric = matches!(
+        field_type,
+        FieldType::U64(_) | FieldType::I64(_) | FieldType::F64(_)
+    );
+    let (supported, expected) = match request.aggregation_type {
+        AggregationType::Terms { .. } | AggregationType::Count => (true, ""),
+        AggregationType::DateHistogram { .. } => (matches!(field_type, FieldType::Date(_)), "date"),
+        AggregationType::PercentileRanks { .. } => (numeric, "numeric"),
+        _ => (
+            numeric || matches!(field_type, FieldType::Date(_)),
+            "numeric or date",
+        ),
+    };
+
+    if !supported {
+        return Err((
+            "unsupported_field_type",
+            format!(
+                "Field '{}' is {}, not {}",
+                request.field,
+                field_type_name(field_type),
+                expected
+            ),
+        ));
+    }
+    if !field_entry.is_fast() {
+        return Err((
+            "not_fast",
+            format!(
+                "Field '{}' must be a fast field to aggregate",
+                request.field
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
 fn parse_aggregation_requests(
     json_str: &str,
 ) -> Result<HashMap<String, AggregationRequest>, String> {
@@ -248,23 +612,55 @@ fn parse_aggregation_type(type_name: &str, config: &JsonValue) -> Result<Aggrega
                 .get("size")
                 .and_then(|v| v.as_u64())
                 .map(|v| v as usize);
-            Ok(AggregationType::Terms { size })
+            // `shard_size` follows Elasticsearch naming; tantivy calls it `segment_size`
+            let shard_size = match config
+                .get("shard_size")
+                .or_else(|| config.get("segment_size"))
+            {
+                Some(value) => match value.as_u64() {
+                    Some(shard_size) if shard_size > 0 => Some(shard_size as usize),
+                    _ => return Err("shard_size must be a positive integer".to_string()),
+                },
+                None => None,
+            };
+            Ok(AggregationType::Terms { size, shard_size })
         }
         "histogram" => {
             let interval = config
                 .get("interval")
                 .and_then(|v| v.as_f64())
                 .ok_or("Histogram requires interval")?;
-            Ok(AggregationType::Histogram { interval })
+            let offset = match config.get("offset") {
+                Some(value) => Some(value.as_f64().ok_or("Histogram offset must be a number")?),
+                None => None,
+            };
+            Ok(AggregationType::Histogram { interval, offset })
         }
         "date_histogram" => {
-            let interval = config
-                .get("calendar_interval")
-                .or_else(|| config.get("fixed_interval"))
-                .and_then(|v| v.as_str())
-                .ok_or("Date histogram requires interval")?
-                .to_string();
-            Ok(AggregationType::DateHistogram { interval })
+            let calendar_interval = config.get("calendar_interval").and_then(|v| v.as_str());
+            let fixed_interval = config.get("fixed_interval").and_then(|v| v.as_str());
+            let interval = match (calendar_interval, fixed_interval) {
+                (Some(_), Some(_)) => return Err(
+                    "Date histogram accepts either calendar_interval or fixed_interval, not both"
+                        .to_string(),
+                ),
+                (Some(interval), None) => DateInterval::Calendar(interval.to_string()),
+                (None, Some(interval)) => DateInterval::Fixed(interval.to_string()),
+                (None, None) => return Err("Date histogram requires interval".to_string()),
+            };
+            let offset_ms = match config.get("offset") {
+                Some(value) => {
+                    let offset = value
+                        .as_str()
+                        .ok_or("Date histogram offset must be a string such as \"-4h\"")?;
+                    Some(parse_offset_millis(offset)?)
+                }
+                None => None,
+            };
+            Ok(AggregationType::DateHistogram {
+                interval,
+                offset_ms,
+            })
         }
         "range" => {
             let ranges_json = config
@@ -304,6 +700,20 @@ fn parse_aggregation_type(type_name: &str, config: &JsonValue) -> Result<Aggrega
                 .unwrap_or_else(|| vec![1.0, 5.0, 25.0, 50.0, 75.0, 95.0, 99.0]);
             Ok(AggregationType::Percentiles { percents })
         }
+        "percentile_ranks" => {
+            let values_json = config
+                .get("values")
+                .and_then(|v| v.as_array())
+                .ok_or("Percentile ranks requires a values array")?;
+            let values = values_json
+                .iter()
+                .map(|v| v.as_f64().ok_or("Percentile ranks values must be numbers"))
+                .collect::<Result<Vec<f64>, _>>()?;
+            if values.is_empty() {
+                return Err("Percentile ranks requires at least one value".to_string());
+            }
+            Ok(AggregationType::PercentileRanks { values })
+        }
         _ => Err(format!("Unknown aggregation type: {}", type_name)),
     }
 }
@@ -311,7 +721,11 @@ fn parse_aggregation_type(type_name: &str, config: &JsonValue) -> Result<Aggrega
 fn parse_aggregation_options(config: &JsonValue) -> Result<AggregationOptions, String> {
     let mut options = AggregationOptions::default();
 
-    if let Some(min_doc_count) = config.get("min_doc_count").and_then(|v| v.as_u64()) {
+    // An explicit 0 must survive parsing, since it is what requests empty buckets
+    if let Some(min_doc_count) = config.get("min_doc_count") {
+        let min_doc_count = min_doc_count
+            .as_u64()
+            .ok_or("min_doc_count must be a non-negative integer")?;
         options.min_doc_count = Some(min_doc_count);
     }
 
@@ -323,9 +737,87 @@ fn parse_aggregation_options(config: &JsonValue) -> Result<AggregationOptions, S
         options.keyed = Some(keyed);
     }
 
+    if let Some(bounds) = config.get("extended_bounds") {
+        options.extended_bounds = Some(parse_histogram_bounds("extended_bounds", bounds)?);
+    }
+
+    if let Some(bounds) = config.get("hard_bounds") {
+        options.hard_bounds = Some(parse_histogram_bounds("hard_bounds", bounds)?);
+    }
+
     Ok(options)
 }
 
+/// Parse a `{"min": ..., "max": ...}` bounds object; dates may be given as RFC3339 strings
+fn parse_histogram_bounds(name: &str, bounds: &JsonValue) -> Result<HistogramBounds, String> {
+    let bounds: HistogramBounds =
+        serde_json::from_value(bounds.clone()).map_err(|e| format!("Invalid {}: {}", name, e))?;
+
+    if bounds.min > bounds.max {
+        return Err(format!(
+            "Invalid {}: min ({}) must not be greater than max ({})",
+            name, bounds.min, bounds.max
+        ));
+    }
+
+    Ok(bounds)
+}
+
+/// Map a calendar unit of constant length in UTC to its fixed interval and bucket offset
+fn calendar_unit_as_fixed_interval(unit: &str) -> Option<(&'static str, i64)> {
+    match unit {
+        "1m" | "minute" => Some(("1m", 0)),
+        "1h" | "hour" => Some(("1h", 0)),
+        "1d" | "day" => Some(("1d", 0)),
+        // Fixed buckets are aligned on the epoch (a Thursday), calendar weeks start on Monday
+        "1w" | "week" => Some(("7d", -3 * 86_400_000)),
+        _ => None,
+    }
+}
+
+/// Parses a signed duration such as `"-4h"`, `"+30m"` or `"90s"` into milliseconds
+fn parse_offset_millis(offset: &str) -> Result<i64, String> {
+    let invalid = || format!("Invalid date histogram offset '{}'", offset);
+    let (sign, rest) = match offset.strip_prefix('-') {
+        Some(rest) => (-1, rest),
+        None => (1, offset.strip_prefix('+').unwrap_or(offset)),
+    };
+    let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+    let (number, unit) = rest.split_at(digits);
+    let number: i64 = number.parse().map_err(|_| invalid())?;
+    let unit_ms = match unit {
+        "ms" => 1,
+        "s" => 1_000,
+        "m" => 60_000,
+        "h" => 3_600_000,
+        "d" => 86_400_000,
+        _ => return Err(invalid()),
+    };
+    number
+        .checked_mul(unit_ms)
+        .map(|millis| sign * millis)
+        .ok_or_else(invalid)
+}
+
+/// Smallest range bound above `value`, so that a bucket ending there counts values <= `value`
+fn percentile_rank_boundary(value: f64, integer_field: bool) -> f64 {
+    if integer_field {
+        return value.floor() + 1.0;
+    }
+    if value.is_nan() || value == f64::INFINITY {
+        return value;
+    }
+    if value == 0.0 {
+        return f64::from_bits(1);
+    }
+    let bits = value.to_bits();
+    if value > 0.0 {
+        f64::from_bits(bits + 1)
+    } else {
+        f64::from_bits(bits - 1)
+    }
+}
+
 fn build_tantivy_aggregations(
     requests: &HashMap<String, AggregationRequest>,
     schema: &Schema,
@@ -344,7 +836,9 @@ fn build_single_tantivy_aggregation(
     request: &AggregationRequest,
     schema: &Schema,
 ) -> Result<Aggregation, String> {
-    let _field = schema
+    // Catch type mismatches here, tantivy's own errors don't say which field is wrong
+    check_aggregation_field(request, schema).map_err(|(_, message)| message)?;
+    let field = schema
         .get_field(&request.field)
         .map_err(|_| format!("Field '{}' not found in schema", request.field))?;
 
@@ -352,41 +846,61 @@ fn build_single_tantivy_aggregation(
     let sub_aggregations = build_sub_aggregations(&request.sub_aggregations, schema)?;
 
     let aggregation_variant = match &request.aggregation_type {
-        AggregationType::Terms { size } => {
+        AggregationType::Terms { size, shard_size } => {
             let terms_agg = TermsAggregation {
                 field: field_name,
                 size: Some(size.unwrap_or(10) as u32),
-                segment_size: None,
+                segment_size: shard_size.map(|shard_size| shard_size as u32),
                 min_doc_count: Some(request.options.min_doc_count.unwrap_or(1)),
                 order: None,
                 missing: None, // Convert to Key if needed
-                show_term_doc_count_error: Some(false),
+                // Left to tantivy, which reports the error bound when ordering by count
+                show_term_doc_count_error: None,
             };
             AggregationVariants::Terms(terms_agg)
         }
-        AggregationType::Histogram { interval } => {
+        AggregationType::Histogram { interval, offset } => {
             let histogram_agg = HistogramAggregation {
                 field: field_name,
                 interval: *interval,
-                offset: None,
+                offset: *offset,
                 min_doc_count: Some(request.options.min_doc_count.unwrap_or(1)),
-                extended_bounds: None,
-                hard_bounds: None,
+                extended_bounds: request.options.extended_bounds,
+                hard_bounds: request.options.hard_bounds,
                 keyed: request.options.keyed.unwrap_or(false),
                 is_normalized_to_ns: false,
             };
             AggregationVariants::Histogram(histogram_agg)
         }
-        AggregationType::DateHistogram { interval } => {
+        AggregationType::DateHistogram {
+            interval,
+            offset_ms,
+        } => {
+            let (fixed_interval, calendar_interval, alignment_ms) = match interval {
+                DateInterval::Fixed(interval) => (Some(interval.clone()), None, 0),
+                DateInterval::Calendar(unit) => match calendar_unit_as_fixed_interval(unit) {
+                    Some((fixed, alignment_ms)) => (Some(fixed.to_string()), None, alignment_ms),
+                    // Variable-length units (months, quarters, years) stay calendar intervals so
+                    // they are reported as unsupported instead of bucketed by a fixed duration
+                    None => (None, Some(unit.clone()), 0),
+                },
+            };
+            // The user's offset (e.g. a timezone shift) applies on top of the week alignment
+            let offset = match (alignment_ms, offset_ms) {
+                (0, None) => None,
+                (alignment_ms, offset_ms) => {
+                    Some(format!("{}ms", alignment_ms + offset_ms.unwrap_or(0)))
+                }
+            };
             let date_histogram_agg = DateHistogramAggregationReq {
                 field: field_name,
-                fixed_interval: Some(interval.clone()),
+                fixed_interval,
                 interval: None,
-                calendar_interval: None,
-                offset: None,
+                calendar_interval,
+                offset,
                 min_doc_count: Some(request.options.min_doc_count.unwrap_or(1)),
-                extended_bounds: None,
-                hard_bounds: None,
+                extended_bounds: request.options.extended_bounds,
+                hard_bounds: request.options.hard_bounds,
                 keyed: request.options.keyed.unwrap_or(false),
                 format: None,
             };
@@ -460,6 +974,47 @@ fn build_single_tantivy_aggregation(
             };
             AggregationVariants::Percentiles(percentiles_agg)
         }
+        AggregationType::PercentileRanks { values } => {
+            // Ranks are derived from contiguous range buckets split right above each value
+            let integer_field = match schema.get_field_entry(field).field_type() {
+                FieldType::U64(_) | FieldType::I64(_) => true,
+                FieldType::F64(_) => false,
+                _ => {
+                    return Err(format!(
+                        "Percentile ranks require a numeric field, '{}' is not numeric",
+                        request.field
+                    ))
+                }
+            };
+            let mut boundaries: Vec<f64> = values
+                .iter()
+                .map(|value| percentile_rank_boundary(*value, integer_field))
+                .collect();
+            boundaries.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+            boundaries.dedup();
+
+            let mut ranges = Vec::with_capacity(boundaries.len() + 1);
+            let mut from = None;
+            for boundary in boundaries {
+                ranges.push(RangeAggregationRange {
+                    from,
+                    to: Some(boundary),
+                    key: None,
+                });
+                from = Some(boundary);
+            }
+            ranges.push(RangeAggregationRange {
+                from,
+                to: None,
+                key: None,
+            });
+
+            AggregationVariants::Range(RangeAggregation {
+                field: field_name,
+                ranges,
+                keyed: false,
+            })
+        }
     };
 
     Ok(Aggregation {
@@ -502,7 +1057,15 @@ fn convert_agg_result_to_json(
     result: &tantivy::aggregation::agg_result::AggregationResult,
     request: &AggregationRequest,
 ) -> JsonValue {
-    use tantivy::aggregation::agg_result::AggregationResult;
+    use tantivy::aggregation::agg_result::{AggregationResult, BucketResult};
+
+    if let (
+        AggregationType::PercentileRanks { values },
+        AggregationResult::BucketResult(BucketResult::Range { buckets }),
+    ) = (&request.aggregation_type, result)
+    {
+        return convert_percentile_ranks_to_json(values, buckets);
+    }
 
     match result {
         AggregationResult::BucketResult(bucket_result) => {
@@ -518,7 +1081,7 @@ fn convert_bucket_result_to_json(
     result: &tantivy::aggregation::agg_result::BucketResult,
     request: &AggregationRequest,
 ) -> JsonValue {
-    use tantivy::aggregation::agg_result::{BucketEntries, BucketResult};
+    use tantivy::aggregation::agg_result::BucketResult;
 
     match result {
         BucketResult::Terms {
@@ -554,12 +1117,11 @@ fn convert_bucket_result_to_json(
             })
         }
         BucketResult::Histogram { buckets } => {
-            let bucket_iter: Box<dyn Iterator<Item = _>> = match buckets {
-                BucketEntries::Vec(vec) => Box::new(vec.iter()),
-                BucketEntries::HashMap(map) => Box::new(map.values()),
-            };
-            let buckets_json: Vec<JsonValue> = bucket_iter
-                .map(|bucket| {
+            let buckets_json = bucket_entries_to_json(
+                buckets,
+                request.options.keyed.unwrap_or(false),
+                |bucket| bucket.key.to_string(),
+                |bucket| {
                     let mut bucket_obj = Map::new();
                     bucket_obj.insert("key".to_string(), convert_key_to_json(&bucket.key));
                     bucket_obj.insert("doc_count".to_string(), json!(bucket.doc_count));
@@ -576,18 +1138,17 @@ fn convert_bucket_result_to_json(
                     }
 
                     JsonValue::Object(bucket_obj)
-                })
-                .collect();
+                },
+            );
 
             json!({ "buckets": buckets_json })
         }
         BucketResult::Range { buckets } => {
-            let bucket_iter: Box<dyn Iterator<Item = _>> = match buckets {
-                BucketEntries::Vec(vec) => Box::new(vec.iter()),
-                BucketEntries::HashMap(map) => Box::new(map.values()),
-            };
-            let buckets_json: Vec<JsonValue> = bucket_iter
-                .map(|bucket| {
+            let buckets_json = bucket_entries_to_json(
+                buckets,
+                request.options.keyed.unwrap_or(false),
+                |bucket| bucket.key.to_string(),
+                |bucket| {
                     let mut bucket_obj = Map::new();
 
                     if let Some(from) = bucket.from {
@@ -611,14 +1172,73 @@ fn convert_bucket_result_to_json(
                     }
 
                     JsonValue::Object(bucket_obj)
-                })
-                .collect();
+                },
+            );
 
             json!({ "buckets": buckets_json })
         }
     }
 }
 
+/// Emit bucket entries as a list, or as an object keyed by bucket key for keyed requests
+fn bucket_entries_to_json<T>(
+    buckets: &tantivy::aggregation::agg_result::BucketEntries<T>,
+    keyed: bool,
+    key_of: impl Fn(&T) -> String,
+    to_json: impl Fn(&T) -> JsonValue,
+) -> JsonValue {
+    use tantivy::aggregation::agg_result::BucketEntries;
+
+    match buckets {
+        BucketEntries::HashMap(map) => JsonValue::Object(
+            map.iter()
+                .map(|(key, bucket)| (key.clone(), to_json(bucket)))
+                .collect(),
+        ),
+        BucketEntries::Vec(vec) if keyed => JsonValue::Object(
+            vec.iter()
+                .map(|bucket| (key_of(bucket), to_json(bucket)))
+                .collect(),
+        ),
+        BucketEntries::Vec(vec) => JsonValue::Array(vec.iter().map(to_json).collect()),
+    }
+}
+
+fn convert_percentile_ranks_to_json(
+    values: &[f64],
+    buckets: &tantivy::aggregation::agg_result::BucketEntries<
+        tantivy::aggregation::agg_result::RangeBucketEntry,
+    >,
+) -> JsonValue {
+    use tantivy::aggregation::agg_result::BucketEntries;
+
+    let bucket_counts: Vec<(Option<f64>, u64)> = match buckets {
+        BucketEntries::Vec(vec) => vec.iter().map(|b| (b.from, b.doc_count)).collect(),
+        BucketEntries::HashMap(map) => map.values().map(|b| (b.from, b.doc_count)).collect(),
+    };
+    let total: u64 = bucket_counts.iter().map(|(_, count)| count).sum();
+
+    let mut ranks = Map::new();
+    for value in values {
+        // Buckets are split right above each value, so a bucket starting at or below
+        // the value only holds values <= value
+        let at_or_below: u64 = bucket_counts
+            .iter()
+            .filter(|(from, _)| from.map_or(true, |from| from <= *value))
+            .map(|(_, count)| count)
+            .sum();
+        let rank = if total == 0 {
+            JsonValue::Null
+        } else {
+            json!(at_or_below as f64 / total as f64 * 100.0)
+        };
+        // Keys are formatted like the keyed percentiles output ("50.0")
+        ranks.insert(format!("{:?}", value), rank);
+    }
+
+    json!({ "values": ranks })
+}
+
 fn convert_metric_result_to_json(
     result: &tantivy::aggregation::agg_result::MetricResult,
     _request: &AggregationRequest,
@@ -699,7 +1319,8 @@ fn convert_key_to_json(key: &Key) -> JsonValue {
     }
 }
 
-fn convert_owned_value_to_json(value: &tantivy::schema::OwnedValue) -> JsonValue {
+/// Convert a stored value to JSON, recursing into arrays and objects
+pub fn convert_owned_value_to_json(value: &tantivy::schema::OwnedValue) -> JsonValue {
     match value {
         tantivy::schema::OwnedValue::Str(s) => json!(s),
         tantivy::schema::OwnedValue::U64(u) => json!(u),
@@ -1,8 +1,13 @@
+use regex::Regex;
 use rustler::{Error, NifResult, ResourceArc};
 use serde_json;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
-use tantivy::{DocId, Score, SegmentOrdinal, SegmentReader};
+use tantivy::columnar::{Column, StrColumn};
+use tantivy::query::{EnableScoring, QueryParser};
+use tantivy::schema::{Field, FieldType, Value};
+use tantivy::store::StoreReader;
+use tantivy::{DateTime, DocAddress, DocId, Score, SegmentOrdinal, SegmentReader, TantivyDocument};
 
 use crate::modules::resources::IndexResource;
 
@@ -16,14 +21,19 @@ pub struct CustomCollectorResource {
 
 /// Custom collector trait for user-defined collection logic
 pub trait CustomCollector: Send + Sync {
+    /// Collect the candidate documents `doc_ids` of one segment
     fn collect_segment(
         &mut self,
         segment_reader: &SegmentReader,
         segment_ord: SegmentOrdinal,
+        doc_ids: &[DocId],
     ) -> NifResult<()>;
     fn merge_results(&mut self, other: Box<dyn CustomCollector>) -> NifResult<()>;
     fn get_results(&self) -> NifResult<CollectionResult>;
     fn name(&self) -> &str;
+
+    /// Clear any state accumulated by a previous collection run
+    fn reset(&mut self) {}
 }
 
 /// Scoring function configuration
@@ -63,7 +73,7 @@ pub enum ScoringType {
 #[derive(Debug, Clone)]
 pub struct CollectionResult {
     pub result_type: String,
-    pub document_scores: Vec<(DocId, Score)>,
+    pub document_scores: Vec<(DocAddress, Score)>,
     pub aggregations: HashMap<String, f64>,
     pub metadata: HashMap<String, String>,
     pub total_hits: u64,
@@ -75,8 +85,8 @@ pub struct TopKCollector {
     pub name: String,
     pub k: usize,
     pub scoring_function: ScoringFunction,
-    pub results: Vec<(DocId, Score)>,
-    pub segment_results: Vec<Vec<(DocId, Score)>>,
+    pub results: Vec<(DocAddress, Score)>,
+    pub segment_results: Vec<Vec<(DocAddress, Score)>>,
 }
 
 /// Aggregation collector for computing statistics
@@ -102,7 +112,7 @@ pub enum AggregationType {
 pub struct FilteringCollector {
     pub name: String,
     pub filter_criteria: Vec<FilterCriterion>,
-    pub collected_docs: Vec<DocId>,
+    pub collected_docs: Vec<DocAddress>,
     pub metadata: HashMap<String, String>,
 }
 
@@ -130,6 +140,163 @@ pub enum FilterValue {
     Boolean(bool),
 }
 
+/// A single document value extracted for filter evaluation
+#[derive(Debug, Clone)]
+enum DocValue {
+    Text(String),
+    Number(f64),
+    Boolean(bool),
+}
+
+/// Fast field column used when a filtered field is not stored
+enum FastColumn {
+    U64(Column<u64>),
+    I64(Column<i64>),
+    F64(Column<f64>),
+    Bool(Column<bool>),
+    Date(Column<DateTime>),
+    Str(StrColumn),
+}
+
+/// Where the values of a filtered field are read from in a segment
+enum ValueSource {
+    Stored(Field),
+    Fast(FastColumn),
+}
+
+/// Filter criterion resolved against a segment's schema and columns
+struct SegmentFilter<'a> {
+    criterion: &'a FilterCriterion,
+    source: ValueSource,
+    regex: Option<Regex>,
+}
+
+impl FilterValue {
+    fn as_text(&self) -> String {
+        match self {
+            FilterValue::String(s) => s.clone(),
+            FilterValue::Number(n) => n.to_string(),
+            FilterValue::Boolean(b) => b.to_string(),
+        }
+    }
+}
+
+impl FilterCriterion {
+    fn matches_value(&self, doc_value: &DocValue, regex: Option<&Regex>) -> bool {
+        match (&self.operator, doc_value, &self.value) {
+            (FilterOperator::Equals, DocValue::Number(n), FilterValue::Number(v)) => n == v,
+            (FilterOperator::Equals, DocValue::Boolean(b), FilterValue::Boolean(v)) => b == v,
+            (FilterOperator::Equals, DocValue::Text(t), value) => *t == value.as_text(),
+            (FilterOperator::GreaterThan, DocValue::Number(n), FilterValue::Number(v)) => n > v,
+            (FilterOperator::GreaterThan, DocValue::Text(t), FilterValue::String(v)) => t > v,
+            (FilterOperator::LessThan, DocValue::Number(n), FilterValue::Number(v)) => n < v,
+            (FilterOperator::LessThan, DocValue::Text(t), FilterValue::String(v)) => t < v,
+            (FilterOperator::Contains, DocValue::Text(t), value) => t.contains(&value.as_text()),
+            (FilterOperator::Regex, DocValue::Text(t), _) => {
+                regex.map(|re| re.is_match(t)).unwrap_or(false)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl FastColumn {
+    fn open(
+        segment_reader: &SegmentReader,
+        field_name: &str,
+        field_type: &FieldType,
+    ) -> NifResult<Self> {
+        let fast_fields = segment_reader.fast_fields();
+        let column = match field_type {
+            FieldType::U64(_) => fast_fields.u64(field_name).map(FastColumn::U64),
+            FieldType::I64(_) => fast_fields.i64(field_name).map(FastColumn::I64),
+            FieldType::F64(_) => fast_fields.f64(field_name).map(FastColumn::F64),
+            FieldType::Bool(_) => fast_fields.bool(field_name).map(FastColumn::Bool),
+            FieldType::Date(_) => fast_fields.date(field_name).map(FastColumn::Date),
+            FieldType::Str(_) => match fast_fields.str(field_name) {
+                Ok(Some(column)) => Ok(FastColumn::Str(column)),
+                Ok(None) => {
+                    return Err(Error::Term(Box::new(format!(
+                        "Fast field '{}' has no values in segment",
+                        field_name
+                    ))))
+                }
+                Err(e) => Err(e),
+            },
+            _ => {
+                return Err(Error::Term(Box::new(format!(
+                    "Field '{}' has a type that cannot be filtered",
+                    field_name
+                ))))
+            }
+        };
+
+        column.map_err(|e| {
+            Error::Term(Box::new(format!(
+                "Failed to open fast field '{}': {}",
+                field_name, e
+            )))
+        })
+    }
+
+    fn values(&self, doc_id: DocId) -> Vec<DocValue> {
+        match self {
+            FastColumn::U64(column) => column
+                .values_for_doc(doc_id)
+                .map(|v| DocValue::Number(v as f64))
+                .collect(),
+            FastColumn::I64(column) => column
+                .values_for_doc(doc_id)
+                .map(|v| DocValue::Number(v as f64))
+                .collect(),
+            FastColumn::F64(column) => column
+                .values_for_doc(doc_id)
+                .map(DocValue::Number)
+                .collect(),
+            FastColumn::Bool(column) => column
+                .values_for_doc(doc_id)
+                .map(DocValue::Boolean)
+                .collect(),
+            FastColumn::Date(column) => column
+                .values_for_doc(doc_id)
+                .map(|d| DocValue::Number(d.into_timestamp_secs() as f64))
+                .collect(),
+            FastColumn::Str(column) => {
+                let mut values = Vec::new();
+                for ord in column.term_ords(doc_id) {
+                    let mut text = String::new();
+                    if let Ok(true) = column.ord_to_str(ord, &mut text) {
+                        values.push(DocValue::Text(text));
+                    }
+                }
+                values
+            }
+        }
+    }
+}
+
+fn stored_values(doc: &TantivyDocument, field: Field) -> Vec<DocValue> {
+    doc.get_all(field)
+        .filter_map(|value| {
+            if let Some(s) = value.as_str() {
+                Some(DocValue::Text(s.to_string()))
+            } else if let Some(n) = value.as_u64() {
+                Some(DocValue::Number(n as f64))
+            } else if let Some(n) = value.as_i64() {
+                Some(DocValue::Number(n as f64))
+            } else if let Some(n) = value.as_f64() {
+                Some(DocValue::Number(n))
+            } else if let Some(b) = value.as_bool() {
+                Some(DocValue::Boolean(b))
+            } else if let Some(d) = value.as_datetime() {
+                Some(DocValue::Number(d.into_timestamp_secs() as f64))
+            } else {
+                value.as_facet().map(|f| DocValue::Text(f.to_string()))
+            }
+        })
+        .collect()
+}
+
 // Safety traits for cross-thread usage
 unsafe impl Send for CustomCollectorResource {}
 unsafe impl Sync for CustomCollectorResource {}
@@ -151,6 +318,7 @@ impl CustomCollector for TopKCollector {
         &mut self,
         _segment_reader: &SegmentReader,
         _segment_ord: SegmentOrdinal,
+        _doc_ids: &[DocId],
     ) -> NifResult<()> {
         // Simplified implementation - in reality would collect docs and score them
         Ok(())
@@ -182,6 +350,7 @@ impl CustomCollector for AggregationCollector {
         &mut self,
         _segment_reader: &SegmentReader,
         _segment_ord: SegmentOrdinal,
+        _doc_ids: &[DocId],
     ) -> NifResult<()> {
         // Simplified implementation - would aggregate values from documents
         self.doc_count += 100; // Placeholder
@@ -209,13 +378,108 @@ impl CustomCollector for AggregationCollector {
     }
 }
 
+impl FilteringCollector {
+    fn resolve_filters(&self, segment_reader: &SegmentReader) -> NifResult<Vec<SegmentFilter<'_>>> {
+        let schema = segment_reader.schema();
+        let mut filters = Vec::with_capacity(self.filter_criteria.len());
+
+        for criterion in &self.filter_criteria {
+            let field = schema.get_field(&criterion.field).map_err(|_| {
+                Error::Term(Box::new(format!(
+                    "Filter field '{}' not found in schema",
+                    criterion.field
+                )))
+            })?;
+            let field_entry = schema.get_field_entry(field);
+
+            // Stored values carry every type, fast fields cover non-stored columns
+            let source = if field_entry.is_stored() {
+                ValueSource::Stored(field)
+            } else if field_entry.is_fast() {
+                ValueSource::Fast(FastColumn::open(
+                    segment_reader,
+                    &criterion.field,
+                    field_entry.field_type(),
+                )?)
+            } else {
+                return Err(Error::Term(Box::new(format!(
+                    "Filter field '{}' must be stored or fast",
+                    criterion.field
+                ))));
+            };
+
+            let regex = match criterion.operator {
+                FilterOperator::Regex => {
+                    Some(Regex::new(&criterion.value.as_text()).map_err(|e| {
+                        Error::Term(Box::new(format!(
+                            "Invalid regex for field '{}': {}",
+                            criterion.field, e
+                        )))
+                    })?)
+                }
+                _ => None,
+            };
+
+            filters.push(SegmentFilter {
+                criterion,
+                source,
+                regex,
+            });
+        }
+
+        Ok(filters)
+    }
+}
+
 impl CustomCollector for FilteringCollector {
     fn collect_segment(
         &mut self,
-        _segment_reader: &SegmentReader,
-        _segment_ord: SegmentOrdinal,
+        segment_reader: &SegmentReader,
+        segment_ord: SegmentOrdinal,
+        doc_ids: &[DocId],
     ) -> NifResult<()> {
-        // Simplified implementation - would filter documents based on criteria
+        let mut matched_docs = Vec::new();
+
+        {
+            let filters = self.resolve_filters(segment_reader)?;
+            let store_reader: Option<StoreReader> = if filters
+                .iter()
+                .any(|f| matches!(f.source, ValueSource::Stored(_)))
+            {
+                Some(segment_reader.get_store_reader(1).map_err(|e| {
+                    Error::Term(Box::new(format!("Failed to open document store: {}", e)))
+                })?)
+            } else {
+                None
+            };
+
+            for &doc_id in doc_ids {
+                let stored_doc = match &store_reader {
+                    Some(reader) => Some(reader.get::<TantivyDocument>(doc_id).map_err(|e| {
+                        Error::Term(Box::new(format!("Failed to load document: {}", e)))
+                    })?),
+                    None => None,
+                };
+
+                // All criteria must match; a criterion matches if any value of the field does
+                let is_match = filters.iter().all(|filter| {
+                    let values = match (&filter.source, &stored_doc) {
+                        (ValueSource::Stored(field), Some(doc)) => stored_values(doc, *field),
+                        (ValueSource::Fast(column), _) => column.values(doc_id),
+                        _ => Vec::new(),
+                    };
+                    values
+                        .iter()
+                        .any(|value| filter.criterion.matches_value(value, filter.regex.as_ref()))
+                });
+
+                if is_match {
+                    matched_docs.push(DocAddress::new(segment_ord, doc_id));
+                }
+            }
+        }
+
+        self.collected_docs.extend(matched_docs);
         Ok(())
     }
 
@@ -227,7 +491,11 @@ impl CustomCollector for FilteringCollector {
     fn get_results(&self) -> NifResult<CollectionResult> {
         Ok(CollectionResult {
             result_type: "filtering".to_string(),
-            document_scores: Vec::new(),
+            document_scores: self
+                .collected_docs
+                .iter()
+                .map(|doc_address| (*doc_address, 1.0))
+                .collect(),
             aggregations: HashMap::new(),
             metadata: self.metadata.clone(),
             total_hits: self.collected_docs.len() as u64,
@@ -238,6 +506,10 @@ impl CustomCollector for FilteringCollector {
     fn name(&self) -> &str {
         &self.name
     }
+
+    fn reset(&mut self) {
+        self.collected_docs.clear();
+    }
 }
 
 /// Create a new custom collector resource
@@ -388,8 +660,11 @@ pub fn custom_collector_create_filtering(
             _ => return Err(Error::BadArg),
         };
 
-        // Simplified value parsing - would need proper type detection
-        let filter_value = if let Ok(num) = value.parse::<f64>() {
+        // Simplified value parsing - would need proper type detection.
+        // Text operators always compare against the raw string.
+        let filter_value = if matches!(op, FilterOperator::Contains | FilterOperator::Regex) {
+            FilterValue::String(value)
+        } else if let Ok(num) = value.parse::<f64>() {
             FilterValue::Number(num)
         } else if let Ok(bool_val) = value.parse::<bool>() {
             FilterValue::Boolean(bool_val)
@@ -418,6 +693,9 @@ pub fn custom_collector_create_filtering(
 }
 
 /// Execute collection with a custom collector
+///
+/// `query_str` is parsed over the schema's indexed text fields and only its
+/// matches are collected; an empty string collects every live document.
 #[rustler::nif(schedule = "DirtyCpu")]
 pub fn custom_collector_execute(
     collector_resource: ResourceArc<CustomCollectorResource>,
@@ -425,31 +703,58 @@ pub fn custom_collector_execute(
     collector_name: String,
     query_str: String,
 ) -> NifResult<String> {
-    // Simplified execution - in reality would parse query and run collection
     let reader = index_resource.index.reader().map_err(|_| Error::BadArg)?;
-    let _searcher = reader.searcher();
-
-    // Simulate collection results
-    let result = CollectionResult {
-        result_type: "execution".to_string(),
-        document_scores: vec![(0, 1.5), (1, 1.2), (2, 1.0)],
-        aggregations: HashMap::new(),
-        metadata: [("query".to_string(), query_str)].iter().cloned().collect(),
-        total_hits: 3,
-        collection_time_ms: 15,
+    let searcher = reader.searcher();
+    let segment_doc_ids = candidate_doc_ids(&index_resource.index, &searcher, &query_str)?;
+
+    let result = {
+        let mut collectors = collector_resource.collectors.lock().unwrap();
+        let collector = collectors.get_mut(&collector_name).ok_or_else(|| {
+            Error::Term(Box::new(format!("Unknown collector: {}", collector_name)))
+        })?;
+
+        let start = std::time::Instant::now();
+        collector.reset();
+        for (segment_ord, (segment_reader, doc_ids)) in searcher
+            .segment_readers()
+            .iter()
+            .zip(&segment_doc_ids)
+            .enumerate()
+        {
+            collector.collect_segment(segment_reader, segment_ord as SegmentOrdinal, doc_ids)?;
+        }
+        let mut result = collector.get_results()?;
+        result.collection_time_ms = start.elapsed().as_millis() as u64;
+        result
+            .metadata
+            .insert("query".to_string(), query_str.clone());
+        result
     };
 
     // Store results
     let mut results = collector_resource.collection_results.lock().unwrap();
     results.insert(collector_name.clone(), result.clone());
 
+    let top_documents: Vec<serde_json::Value> = result
+        .document_scores
+        .iter()
+        .take(10)
+        .map(|(doc_address, score)| {
+            serde_json::json!({
+                "segment_ord": doc_address.segment_ord,
+                "doc_id": doc_address.doc_id,
+                "score": score,
+            })
+        })
+        .collect();
+
     // Return JSON response
     let response = serde_json::json!({
         "collector_name": collector_name,
         "result_type": result.result_type,
         "total_hits": result.total_hits,
         "collection_time_ms": result.collection_time_ms,
-        "top_documents": result.document_scores.iter().take(10).collect::<Vec<_>>(),
+        "top_documents": top_documents,
         "aggregations": result.aggregations,
         "metadata": result.metadata
     });
@@ -457,6 +762,52 @@ pub fn custom_collector_execute(
     Ok(response.to_string())
 }
 
+/// Live documents matching `query_str`, per segment, or all live documents for an empty query
+fn candidate_doc_ids(
+    index: &tantivy::Index,
+    searcher: &tantivy::Searcher,
+    query_str: &str,
+) -> NifResult<Vec<Vec<DocId>>> {
+    if query_str.trim().is_empty() {
+        return Ok(searcher
+            .segment_readers()
+            .iter()
+            .map(|segment_reader| segment_reader.doc_ids_alive().collect())
+            .collect());
+    }
+
+    let schema = searcher.schema();
+    let default_fields: Vec<Field> = schema
+        .fields()
+        .filter(|(_, entry)| entry.is_indexed() && matches!(entry.field_type(), FieldType::Str(_)))
+        .map(|(field, _)| field)
+        .collect();
+    let query = QueryParser::for_index(index, default_fields)
+        .parse_query(query_str)
+        .map_err(|e| Error::Term(Box::new(format!("Failed to parse query: {}", e))))?;
+    let weight = query
+        .weight(EnableScoring::disabled_from_searcher(searcher))
+        .map_err(|e| Error::Term(Box::new(format!("Failed to build query: {}", e))))?;
+
+    let mut segment_doc_ids = Vec::with_capacity(searcher.segment_readers().len());
+    for segment_reader in searcher.segment_readers() {
+        let alive_bitset = segment_reader.alive_bitset();
+        let mut doc_ids = Vec::new();
+        weight
+            .for_each_no_score(segment_reader, &mut |docs| {
+                doc_ids.extend(
+                    docs.iter()
+                        .copied()
+                        .filter(|doc| alive_bitset.map_or(true, |alive| alive.is_alive(*doc))),
+                );
+            })
+            .map_err(|e| Error::Term(Box::new(format!("Failed to run query: {}", e))))?;
+        segment_doc_ids.push(doc_ids);
+    }
+
+    Ok(segment_doc_ids)
+}
+
 /// Get collection results
 #[rustler::nif]
 pub fn custom_collector_get_results(
@@ -1,15 +1,22 @@
 use base64::{engine::general_purpose, Engine as _};
 use chrono;
-use rustler::{Encoder, Env, NifResult, ResourceArc, Term};
+use rustler::{Encoder, Env, LocalPid, NifResult, OwnedEnv, ResourceArc, Term};
 use serde_json;
 use std::collections::HashMap;
-use tantivy::query::{BooleanQuery, Occur, PhraseQuery, TermQuery};
+use std::ops::Bound;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use tantivy::collector::Count;
+use tantivy::query::{BooleanQuery, Occur, PhraseQuery, RangeQuery, RegexQuery, TermQuery};
 use tantivy::schema::{Field, FieldType, IndexRecordOption};
-use tantivy::{TantivyDocument, Term as TantivyTerm};
+use tantivy::tokenizer::{PreTokenizedString, Token};
+use tantivy::{IndexReader, IndexWriter, ReloadPolicy, TantivyDocument, Term as TantivyTerm};
 
+use crate::modules::index::cached_reader_searcher;
+use crate::modules::query::validate_regex_pattern;
 use crate::modules::resources::{
-    atoms, convert_ip_to_ipv6, convert_json_value_to_btreemap, IndexWriterResource, QueryResource,
-    SchemaResource,
+    atoms, convert_ip_to_ipv6, convert_json_value_to_btreemap, IndexResource, IndexWriterResource,
+    QueryResource, SchemaResource, SearcherResource,
 };
 
 /// Document operations and validation functions
@@ -44,7 +51,12 @@ pub fn writer_add_document<'a>(
 
             match field_entry.field_type() {
                 FieldType::Str(_) => {
-                    if let Ok(string_val) = value.decode::<String>() {
+                    if let Some(pre_tokenized) = decode_pre_tokenized(value) {
+                        let pre_tokenized = pre_tokenized.map_err(|e| {
+                            rustler::Error::Term(Box::new(format!("Field '{}': {}", field_name, e)))
+                        })?;
+                        tantivy_doc.add_pre_tokenized_text(field, pre_tokenized);
+                    } else if let Ok(string_val) = value.decode::<String>() {
                         tantivy_doc.add_text(field, &string_val);
                     }
                 }
@@ -131,7 +143,10 @@ pub fn writer_add_document<'a>(
     }
 
     match writer.add_document(tantivy_doc) {
-        Ok(_) => Ok(atoms::ok().encode(env)),
+        Ok(_) => {
+            writer_res.pending_documents.fetch_add(1, Ordering::Relaxed);
+            Ok(atoms::ok().encode(env))
+        }
         Err(e) => Err(rustler::Error::Term(Box::new(format!(
             "Failed to add document: {}",
             e
@@ -146,7 +161,10 @@ pub fn writer_commit<'a>(
 ) -> NifResult<Term<'a>> {
     let mut writer = writer_res.writer.lock().unwrap();
     match writer.commit() {
-        Ok(_) => Ok(atoms::ok().encode(env)),
+        Ok(_) => {
+            writer_res.pending_documents.store(0, Ordering::Relaxed);
+            Ok(atoms::ok().encode(env))
+        }
         Err(e) => Err(rustler::Error::Term(Box::new(format!(
             "Failed to commit: {}",
             e
@@ -154,19 +172,178 @@ pub fn writer_commit<'a>(
     }
 }
 
+/// Commit pending changes and return the opstamp of the commit
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn writer_commit_with_opstamp(writer_res: ResourceArc<IndexWriterResource>) -> NifResult<u64> {
+    let mut writer = writer_res.writer.lock().unwrap();
+    let opstamp = writer
+        .commit()
+        .map_err(|e| rustler::Error::Term(Box::new(format!("Failed to commit: {}", e))))?;
+    writer_res.pending_documents.store(0, Ordering::Relaxed);
+    Ok(opstamp)
+}
+
+/// Prepare a commit and publish it in the background
+///
+/// Pending segments are flushed before returning, but publishing the new metas
+/// (and the fsync that goes with it) happens on tantivy's segment updater
+/// thread. Once it finishes, `pid` receives `{:tantivy_commit, opstamp, :ok}`
+/// or `{:tantivy_commit, opstamp, {:error, reason}}`.
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn writer_commit_soft(
+    writer_res: ResourceArc<IndexWriterResource>,
+    pid: LocalPid,
+) -> NifResult<u64> {
+    let mut writer = writer_res.writer.lock().unwrap();
+    let prepared = writer
+        .prepare_commit()
+        .map_err(|e| rustler::Error::Term(Box::new(format!("Failed to commit: {}", e))))?;
+    let opstamp = prepared.opstamp();
+    let commit_future = prepared.commit_future();
+    writer_res.pending_documents.store(0, Ordering::Relaxed);
+
+    std::thread::spawn(move || {
+        let result = commit_future.wait();
+        let mut owned_env = OwnedEnv::new();
+        // Delivery is best effort: the caller may have exited in the meantime
+        let _ = owned_env.send_and_clear(&pid, |env| match result {
+            Ok(_) => (atoms::tantivy_commit(), opstamp, atoms::ok()).encode(env),
+            Err(e) => (
+                atoms::tantivy_commit(),
+                opstamp,
+                (atoms::error(), format!("Failed to commit: {}", e)),
+            )
+                .encode(env),
+        });
+    });
+
+    Ok(opstamp)
+}
+
+/// Commit pending changes and return a searcher that already sees them
+///
+/// The index's cached reader is reloaded while the writer lock is still held,
+/// so no other commit can slip in between and the searcher is never stale.
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn writer_commit_and_searcher(
+    writer_res: ResourceArc<IndexWriterResource>,
+    index_res: ResourceArc<IndexResource>,
+) -> NifResult<ResourceArc<SearcherResource>> {
+    let mut writer = writer_res.writer.lock().unwrap();
+    writer
+        .commit()
+        .map_err(|e| rustler::Error::Term(Box::new(format!("Failed to commit: {}", e))))?;
+    writer_res.pending_documents.store(0, Ordering::Relaxed);
+
+    let searcher = cached_reader_searcher(&index_res)?;
+
+    Ok(ResourceArc::new(SearcherResource {
+        searcher: Arc::new(searcher),
+    }))
+}
+
+/// Delete documents matching a query and return how many committed documents matched
 #[rustler::nif(schedule = "DirtyCpu")]
-pub fn writer_delete_documents<'a>(
-    env: Env<'a>,
+pub fn writer_delete_documents(
     writer_res: ResourceArc<IndexWriterResource>,
     query_res: ResourceArc<QueryResource>,
-) -> NifResult<Term<'a>> {
+) -> NifResult<u64> {
     // Get a reference to the writer
     let writer = writer_res.writer.lock().unwrap();
-    let query = query_res.query.box_clone();
+
+    delete_query_counted(&writer, query_res.query.box_clone())
+}
+
+/// Delete documents whose date field is strictly before `timestamp` (unix seconds)
+/// and return how many committed documents matched
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn writer_delete_before(
+    writer_res: ResourceArc<IndexWriterResource>,
+    date_field: String,
+    timestamp: i64,
+) -> NifResult<u64> {
+    let writer = writer_res.writer.lock().unwrap();
+    let schema = writer.index().schema();
+
+    let field = schema.get_field(&date_field).map_err(|_| {
+        rustler::Error::Term(Box::new(format!(
+            "Field '{}' not found in schema",
+            date_field
+        )))
+    })?;
+    let field_entry = schema.get_field_entry(field);
+    if !matches!(field_entry.field_type(), FieldType::Date(_)) {
+        return Err(rustler::Error::Term(Box::new(format!(
+            "Field '{}' is not a date field",
+            date_field
+        ))));
+    }
+    if !field_entry.is_indexed() && !field_entry.is_fast() {
+        return Err(rustler::Error::Term(Box::new(format!(
+            "Date field '{}' must be indexed or fast to delete by range",
+            date_field
+        ))));
+    }
+
+    let upper =
+        TantivyTerm::from_field_date(field, tantivy::DateTime::from_timestamp_secs(timestamp));
+    let query = RangeQuery::new(Bound::Unbounded, Bound::Excluded(upper));
+
+    delete_query_counted(&writer, Box::new(query))
+}
+
+/// Delete documents with a term in a text field matching `pattern` and return how
+/// many committed documents matched
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn writer_delete_regex(
+    writer_res: ResourceArc<IndexWriterResource>,
+    field_name: String,
+    pattern: String,
+) -> NifResult<u64> {
+    let writer = writer_res.writer.lock().unwrap();
+    let (field, field_type) = delete_term_field(&writer, &field_name)?;
+
+    let indexed_text = match &field_type {
+        FieldType::Str(text_options) => text_options.get_indexing_options().is_some(),
+        _ => false,
+    };
+    if !indexed_text {
+        return Err(rustler::Error::Term(Box::new(format!(
+            "Field '{}' must be an indexed text field for regex deletes",
+            field_name
+        ))));
+    }
+
+    validate_regex_pattern(&pattern).map_err(|e| rustler::Error::Term(Box::new(e)))?;
+    let query = RegexQuery::from_pattern(&pattern, field).map_err(|e| {
+        rustler::Error::Term(Box::new(format!("Failed to create regex query: {}", e)))
+    })?;
+
+    delete_query_counted(&writer, Box::new(query))
+}
+
+/// Queue the deletion of `query` and return how many committed documents it matched
+fn delete_query_counted(
+    writer: &IndexWriter,
+    query: Box<dyn tantivy::query::Query>,
+) -> NifResult<u64> {
+    // Count matches against the last commit before queueing the deletion
+    let reader: IndexReader = writer
+        .index()
+        .reader_builder()
+        .reload_policy(ReloadPolicy::Manual)
+        .try_into()
+        .map_err(|e| rustler::Error::Term(Box::new(format!("Failed to create reader: {}", e))))?;
+    let deleted_count = reader.searcher().search(&*query, &Count).map_err(|e| {
+        rustler::Error::Term(Box::new(format!(
+            "Failed to count documents to delete: {}",
+            e
+        )))
+    })?;
 
     // Delete documents matching the query
     match writer.delete_query(query) {
-        Ok(_) => Ok(atoms::ok().encode(env)),
+        Ok(_) => Ok(deleted_count as u64),
         Err(e) => Err(rustler::Error::Term(Box::new(format!(
             "Failed to delete documents by query: {}",
             e
@@ -198,7 +375,10 @@ pub fn writer_rollback<'a>(
     let mut writer = writer_res.writer.lock().unwrap();
 
     match writer.rollback() {
-        Ok(_) => Ok(atoms::ok().encode(env)),
+        Ok(_) => {
+            writer_res.pending_documents.store(0, Ordering::Relaxed);
+            Ok(atoms::ok().encode(env))
+        }
         Err(e) => Err(rustler::Error::Term(Box::new(format!(
             "Failed to rollback: {}",
             e
@@ -214,10 +394,37 @@ pub fn writer_delete_term<'a>(
     term_value: rustler::Term<'a>,
 ) -> NifResult<Term<'a>> {
     let writer = writer_res.writer.lock().unwrap();
+    let (field, field_type) = delete_term_field(&writer, &term_field)?;
+
+    delete_term_value(&writer, field, &field_type, &term_field, term_value)?;
+
+    Ok(atoms::ok().encode(env))
+}
+
+/// Delete all documents matching one field value each, under a single writer lock
+#[rustler::nif]
+pub fn writer_delete_terms<'a>(
+    env: Env<'a>,
+    writer_res: ResourceArc<IndexWriterResource>,
+    term_field: String,
+    term_values: Vec<rustler::Term<'a>>,
+) -> NifResult<Term<'a>> {
+    let writer = writer_res.writer.lock().unwrap();
+    let (field, field_type) = delete_term_field(&writer, &term_field)?;
+
+    for term_value in term_values {
+        delete_term_value(&writer, field, &field_type, &term_field, term_value)?;
+    }
+
+    Ok(atoms::ok().encode(env))
+}
+
+/// Resolve the field used for term deletion from the writer's schema
+fn delete_term_field(writer: &IndexWriter, term_field: &str) -> NifResult<(Field, FieldType)> {
     let schema = writer.index().schema();
 
     // Get the field from the schema
-    let field = match schema.get_field(&term_field) {
+    let field = match schema.get_field(term_field) {
         Ok(field) => field,
         Err(_) => {
             return Err(rustler::Error::Term(Box::new(format!(
@@ -227,9 +434,17 @@ pub fn writer_delete_term<'a>(
         }
     };
 
-    let field_entry = schema.get_field_entry(field);
-    let field_type = field_entry.field_type();
+    Ok((field, schema.get_field_entry(field).field_type().clone()))
+}
 
+/// Queue the deletion of documents matching a single value, coerced to the field type
+fn delete_term_value(
+    writer: &IndexWriter,
+    field: Field,
+    field_type: &FieldType,
+    term_field: &str,
+    term_value: rustler::Term,
+) -> NifResult<()> {
     // Create a Tantivy Term or Query based on the field type and value
     match field_type {
         FieldType::Str(text_options) => {
@@ -275,9 +490,7 @@ pub fn writer_delete_term<'a>(
 
                     // Perform the deletion using query
                     match writer.delete_query(query) {
-                        Ok(_opstamp) => {
-                            return Ok(atoms::ok().encode(env));
-                        }
+                        Ok(_opstamp) => return Ok(()),
                         Err(e) => {
                             return Err(rustler::Error::Term(Box::new(format!(
                                 "Failed to delete by query: {}",
@@ -292,7 +505,7 @@ pub fn writer_delete_term<'a>(
                     // Perform the deletion
                     let _opstamp = writer.delete_term(tantivy_term.clone());
 
-                    return Ok(atoms::ok().encode(env));
+                    return Ok(());
                 }
             } else {
                 return Err(rustler::Error::Term(Box::new(
@@ -331,7 +544,7 @@ pub fn writer_delete_term<'a>(
             // Perform the deletion
             let _opstamp = writer.delete_term(tantivy_term.clone());
 
-            return Ok(atoms::ok().encode(env));
+            return Ok(());
         }
         FieldType::I64(_) => {
             let tantivy_term = if let Ok(int_val) = term_value.decode::<i64>() {
@@ -358,7 +571,7 @@ pub fn writer_delete_term<'a>(
             // Perform the deletion
             let _opstamp = writer.delete_term(tantivy_term.clone());
 
-            return Ok(atoms::ok().encode(env));
+            return Ok(());
         }
         FieldType::F64(_) => {
             let tantivy_term = if let Ok(float_val) = term_value.decode::<f64>() {
@@ -385,7 +598,7 @@ pub fn writer_delete_term<'a>(
             // Perform the deletion
             let _opstamp = writer.delete_term(tantivy_term.clone());
 
-            return Ok(atoms::ok().encode(env));
+            return Ok(());
         }
         FieldType::Bool(_) => {
             let tantivy_term = if let Ok(bool_val) = term_value.decode::<bool>() {
@@ -412,7 +625,7 @@ pub fn writer_delete_term<'a>(
             // Perform the deletion
             let _opstamp = writer.delete_term(tantivy_term.clone());
 
-            return Ok(atoms::ok().encode(env));
+            return Ok(());
         }
         FieldType::Date(_) => {
             let tantivy_term = if let Ok(timestamp) = term_value.decode::<i64>() {
@@ -442,7 +655,7 @@ pub fn writer_delete_term<'a>(
             // Perform the deletion
             let _opstamp = writer.delete_term(tantivy_term.clone());
 
-            return Ok(atoms::ok().encode(env));
+            return Ok(());
         }
         _ => {
             return Err(rustler::Error::Term(Box::new(format!(
@@ -484,7 +697,12 @@ pub fn writer_add_document_with_schema<'a>(
 
             match field_entry.field_type() {
                 FieldType::Str(_) => {
-                    if let Ok(string_val) = value.decode::<String>() {
+                    if let Some(pre_tokenized) = decode_pre_tokenized(value) {
+                        let pre_tokenized = pre_tokenized.map_err(|e| {
+                            rustler::Error::Term(Box::new(format!("Field '{}': {}", field_name, e)))
+                        })?;
+                        tantivy_doc.add_pre_tokenized_text(field, pre_tokenized);
+                    } else if let Ok(string_val) = value.decode::<String>() {
                         tantivy_doc.add_text(field, &string_val);
                     }
                 }
@@ -573,7 +791,62 @@ pub fn writer_add_document_with_schema<'a>(
     }
 
     match writer.add_document(tantivy_doc) {
-        Ok(_) => Ok(atoms::ok().encode(env)),
+        Ok(_) => {
+            writer_res.pending_documents.fetch_add(1, Ordering::Relaxed);
+            Ok(atoms::ok().encode(env))
+        }
+        Err(e) => Err(rustler::Error::Term(Box::new(format!(
+            "Failed to add document: {}",
+            e
+        )))),
+    }
+}
+
+/// Add a document, failing on any value that does not fit its field type.
+///
+/// With `coerce`, string values are parsed for numeric fields and `"true"` /
+/// `"false"` are accepted for boolean fields before giving up, which suits
+/// loosely typed sources such as CSV exports. Fields missing from the schema
+/// are ignored, as in `writer_add_document`.
+#[rustler::nif]
+pub fn writer_add_document_checked<'a>(
+    env: Env<'a>,
+    writer_res: ResourceArc<IndexWriterResource>,
+    document: rustler::Term<'a>,
+    coerce: bool,
+) -> NifResult<Term<'a>> {
+    let doc_map: HashMap<String, rustler::Term> = match document.decode() {
+        Ok(map) => map,
+        Err(_) => {
+            return Err(rustler::Error::Term(Box::new(
+                "Failed to decode document map: Expected a map".to_string(),
+            )))
+        }
+    };
+
+    let writer = writer_res.writer.lock().unwrap();
+    let schema = writer.index().schema();
+
+    let mut tantivy_doc = TantivyDocument::default();
+    for (field_name, value) in doc_map {
+        if let Ok(field) = schema.get_field(&field_name) {
+            let field_type = schema.get_field_entry(field).field_type();
+            let value = if coerce {
+                coerce_string_value(env, value, field_type).unwrap_or(value)
+            } else {
+                value
+            };
+            add_field_to_document(&mut tantivy_doc, field, field_type, value).map_err(|e| {
+                rustler::Error::Term(Box::new(format!("Field '{}': {}", field_name, e)))
+            })?;
+        }
+    }
+
+    match writer.add_document(tantivy_doc) {
+        Ok(_) => {
+            writer_res.pending_documents.fetch_add(1, Ordering::Relaxed);
+            Ok(atoms::ok().encode(env))
+        }
         Err(e) => Err(rustler::Error::Term(Box::new(format!(
             "Failed to add document: {}",
             e
@@ -630,7 +903,10 @@ pub fn writer_add_document_batch<'a>(
 
         if doc_valid {
             match writer.add_document(tantivy_doc) {
-                Ok(_) => successful_count += 1,
+                Ok(_) => {
+                    successful_count += 1;
+                    writer_res.pending_documents.fetch_add(1, Ordering::Relaxed);
+                }
                 Err(e) => {
                     errors.push((index, format!("Failed to add document: {}", e)));
                 }
@@ -648,11 +924,17 @@ pub fn writer_add_document_batch<'a>(
     Ok(result.encode(env))
 }
 
+/// Check a document against the schema without adding it.
+///
+/// Every field present in the document must exist and hold a value of the
+/// right type. Fields in `required_fields` must also be present and not `nil`.
+/// All problems are reported together, joined with `"; "`.
 #[rustler::nif]
 pub fn validate_document_against_schema<'a>(
     env: Env<'a>,
     document: rustler::Term<'a>,
     schema_res: ResourceArc<SchemaResource>,
+    required_fields: Vec<String>,
 ) -> NifResult<Term<'a>> {
     let doc_map: HashMap<String, rustler::Term> = match document.decode() {
         Ok(map) => map,
@@ -664,9 +946,22 @@ pub fn validate_document_against_schema<'a>(
     };
 
     let schema = &schema_res.schema;
+    let nil = atoms::nil().to_term(env);
     let mut validation_errors = Vec::new();
 
+    for field_name in &required_fields {
+        if schema.get_field(field_name).is_err() {
+            validation_errors.push(format!("Unknown required field: '{}'", field_name));
+        } else if doc_map.get(field_name).map_or(true, |value| *value == nil) {
+            validation_errors.push(format!("Missing required field: '{}'", field_name));
+        }
+    }
+
     for (field_name, value) in doc_map {
+        if value == nil && required_fields.contains(&field_name) {
+            // Already reported as missing
+            continue;
+        }
         if let Ok(field) = schema.get_field(&field_name) {
             let field_entry = schema.get_field_entry(field);
 
@@ -688,6 +983,88 @@ pub fn validate_document_against_schema<'a>(
 
 /// Helper functions for document operations
 
+/// Parse a string value into the number or boolean its field expects.
+///
+/// `None` when the value is not a string, does not parse, or the field type
+/// has no coercion, so the strict handling and its error apply unchanged.
+fn coerce_string_value<'a>(
+    env: Env<'a>,
+    value: rustler::Term<'a>,
+    field_type: &FieldType,
+) -> Option<rustler::Term<'a>> {
+    let string_val: String = value.decode().ok()?;
+    let string_val = string_val.trim();
+    match field_type {
+        FieldType::U64(_) => string_val.parse::<u64>().ok().map(|val| val.encode(env)),
+        FieldType::I64(_) => string_val.parse::<i64>().ok().map(|val| val.encode(env)),
+        // NaN and infinities have no Erlang float representation
+        FieldType::F64(_) => string_val
+            .parse::<f64>()
+            .ok()
+            .filter(|val| val.is_finite())
+            .map(|val| val.encode(env)),
+        FieldType::Bool(_) => match string_val.to_ascii_lowercase().as_str() {
+            "true" => Some(true.encode(env)),
+            "false" => Some(false.encode(env)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Decode a `{:pre_tokenized, text, tokens}` text value, `None` for any other term.
+///
+/// Each token is `{token_text, start, end, position}` with byte offsets into
+/// `text`. The tokens are indexed as given, bypassing the field's tokenizer.
+fn decode_pre_tokenized(value: rustler::Term) -> Option<Result<PreTokenizedString, String>> {
+    let (tag, text, tokens): (rustler::Atom, String, rustler::Term) = value.decode().ok()?;
+    if tag != atoms::pre_tokenized() {
+        return None;
+    }
+
+    let tokens: Vec<(String, usize, usize, usize)> = match tokens.decode() {
+        Ok(tokens) => tokens,
+        Err(_) => {
+            return Some(Err(
+                "Pre-tokenized tokens must be {text, start, end, position} tuples".to_string(),
+            ))
+        }
+    };
+
+    let mut last_position = 0;
+    let mut token_structs = Vec::with_capacity(tokens.len());
+    for (token_text, offset_from, offset_to, position) in tokens {
+        if offset_from > offset_to || offset_to > text.len() {
+            return Some(Err(format!(
+                "Token {:?} has offsets {}..{} outside of the {}-byte text",
+                token_text,
+                offset_from,
+                offset_to,
+                text.len()
+            )));
+        }
+        if position < last_position {
+            return Some(Err(format!(
+                "Token {:?} has position {} after position {}",
+                token_text, position, last_position
+            )));
+        }
+        last_position = position;
+        token_structs.push(Token {
+            offset_from,
+            offset_to,
+            position,
+            text: token_text,
+            position_length: 1,
+        });
+    }
+
+    Some(Ok(PreTokenizedString {
+        text,
+        tokens: token_structs,
+    }))
+}
+
 pub fn add_field_to_document(
     doc: &mut TantivyDocument,
     field: Field,
@@ -696,6 +1073,10 @@ pub fn add_field_to_document(
 ) -> Result<(), String> {
     match field_type {
         FieldType::Str(_) => {
+            if let Some(pre_tokenized) = decode_pre_tokenized(value) {
+                doc.add_pre_tokenized_text(field, pre_tokenized?);
+                return Ok(());
+            }
             let string_val: String = value.decode().map_err(|_| "Expected string value")?;
             doc.add_text(field, &string_val);
             Ok(())
@@ -819,6 +1200,9 @@ pub fn add_field_to_document(
 pub fn validate_field_value(value: rustler::Term, field_type: &FieldType) -> Result<(), String> {
     match field_type {
         FieldType::Str(_) => {
+            if let Some(pre_tokenized) = decode_pre_tokenized(value) {
+                return pre_tokenized.map(|_| ());
+            }
             value
                 .decode::<String>()
                 .map_err(|_| "Expected string value")?;
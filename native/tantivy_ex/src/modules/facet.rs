@@ -1,5 +1,7 @@
 use rustler::{NifResult, ResourceArc};
 use serde_json;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
 use tantivy::collector::FacetCollector;
 use tantivy::query::{BooleanQuery, Occur};
 use tantivy::schema::Facet;
@@ -9,7 +11,7 @@ use crate::modules::resources::{QueryResource, SearcherResource};
 
 /// Resource for managing FacetCollector state
 pub struct FacetCollectorResource {
-    pub collector: FacetCollector,
+    pub collector: Mutex<FacetCollector>,
 }
 
 // Make FacetCollectorResource safe for concurrent access
@@ -34,7 +36,9 @@ pub fn facet_collector_for_field(
     field_name: String,
 ) -> NifResult<ResourceArc<FacetCollectorResource>> {
     let collector = FacetCollector::for_field(&field_name);
-    Ok(ResourceArc::new(FacetCollectorResource { collector }))
+    Ok(ResourceArc::new(FacetCollectorResource {
+        collector: Mutex::new(collector),
+    }))
 }
 
 /// Adds a facet path to the collector for counting
@@ -43,26 +47,44 @@ pub fn facet_collector_add_facet(
     collector_res: ResourceArc<FacetCollectorResource>,
     facet_path: String,
 ) -> NifResult<rustler::Atom> {
-    let facet = match Facet::from_text(&facet_path) {
-        Ok(f) => f,
-        Err(e) => {
-            return Err(rustler::Error::Term(Box::new(format!(
-                "Invalid facet path '{}': {}",
-                facet_path, e
-            ))))
-        }
-    };
+    let facet = parse_facet_path(&facet_path)?;
+
+    collector_res.collector.lock().unwrap().add_facet(facet);
+
+    Ok(rustler::types::atom::ok())
+}
+
+/// Adds several facet paths to the collector under a single lock
+///
+/// Every path is parsed before the collector is touched, so an invalid path
+/// leaves the collector unchanged.
+#[rustler::nif]
+pub fn facet_collector_add_facets(
+    collector_res: ResourceArc<FacetCollectorResource>,
+    facet_paths: Vec<String>,
+) -> NifResult<rustler::Atom> {
+    let facets = facet_paths
+        .iter()
+        .map(|facet_path| parse_facet_path(facet_path))
+        .collect::<NifResult<Vec<Facet>>>()?;
 
-    // Need to access the collector mutably, but ResourceArc doesn't allow mutation
-    // For now, we'll use an unsafe approach - in production this would need better design
-    let collector_ptr = &collector_res.collector as *const FacetCollector as *mut FacetCollector;
-    unsafe {
-        (*collector_ptr).add_facet(facet);
+    let mut collector = collector_res.collector.lock().unwrap();
+    for facet in facets {
+        collector.add_facet(facet);
     }
 
     Ok(rustler::types::atom::ok())
 }
 
+fn parse_facet_path(facet_path: &str) -> NifResult<Facet> {
+    Facet::from_text(facet_path).map_err(|e| {
+        rustler::Error::Term(Box::new(format!(
+            "Invalid facet path '{}': {}",
+            facet_path, e
+        )))
+    })
+}
+
 /// Performs a search with facet collection
 #[rustler::nif(schedule = "DirtyCpu")]
 pub fn facet_search(
@@ -72,7 +94,7 @@ pub fn facet_search(
 ) -> NifResult<String> {
     match searcher_res
         .searcher
-        .search(&*query_res.query, &collector_res.collector)
+        .search(&*query_res.query, &*collector_res.collector.lock().unwrap())
     {
         Ok(facet_counts) => {
             // Convert FacetCounts to a nested JSON structure
@@ -102,6 +124,66 @@ pub fn facet_search(
     }
 }
 
+/// Performs a search with facet collection, keeping facets up to `max_depth` levels
+///
+/// Deeper facets are rolled up into their ancestor at `max_depth` when the
+/// collector did not count that ancestor itself.
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn facet_search_depth(
+    searcher_res: ResourceArc<SearcherResource>,
+    query_res: ResourceArc<QueryResource>,
+    collector_res: ResourceArc<FacetCollectorResource>,
+    max_depth: usize,
+) -> NifResult<String> {
+    if max_depth < 1 {
+        return Err(rustler::Error::Term(Box::new(
+            "max_depth must be at least 1".to_string(),
+        )));
+    }
+
+    let facet_counts = searcher_res
+        .searcher
+        .search(&*query_res.query, &*collector_res.collector.lock().unwrap())
+        .map_err(|e| rustler::Error::Term(Box::new(format!("Faceted search failed: {}", e))))?;
+
+    // Facets counted directly by the collector are exact
+    let mut exact: BTreeMap<String, u64> = BTreeMap::new();
+    // Roll-ups keep only the shallowest level found below the ancestor, because
+    // each level already includes the documents of the levels under it
+    let mut rolled_up: BTreeMap<String, (usize, u64)> = BTreeMap::new();
+
+    for (facet, count) in facet_counts.get("/") {
+        let segments = facet.to_path();
+        if segments.len() <= max_depth {
+            exact.insert(facet.to_string(), count);
+            continue;
+        }
+
+        let ancestor = Facet::from_path(&segments[..max_depth]).to_string();
+        let entry = rolled_up.entry(ancestor).or_insert((segments.len(), 0));
+        if segments.len() < entry.0 {
+            *entry = (segments.len(), count);
+        } else if segments.len() == entry.0 {
+            entry.1 += count;
+        }
+    }
+
+    let mut result = serde_json::Map::new();
+    for (path, (_, count)) in rolled_up {
+        insert_facet_hierarchically(&mut result, &path, count);
+    }
+    for (path, count) in exact {
+        insert_facet_hierarchically(&mut result, &path, count);
+    }
+
+    serde_json::to_string(&result).map_err(|e| {
+        rustler::Error::Term(Box::new(format!(
+            "Failed to serialize facet results: {}",
+            e
+        )))
+    })
+}
+
 /// Helper function to insert facet counts hierarchically
 fn insert_facet_hierarchically(
     result: &mut serde_json::Map<String, serde_json::Value>,
@@ -128,6 +210,59 @@ fn insert_facet_hierarchically(
     );
 }
 
+/// Counts the direct children of `base_path` for documents matching a query
+///
+/// Builds the facet collector internally, so the common "counts under
+/// /category" case is a single call. Returns a JSON object of facet path to count.
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn facet_counts(
+    searcher_res: ResourceArc<SearcherResource>,
+    query_res: ResourceArc<QueryResource>,
+    field_name: String,
+    base_path: String,
+) -> NifResult<String> {
+    let schema = searcher_res.searcher.schema();
+    let field = schema
+        .get_field(&field_name)
+        .map_err(|_| rustler::Error::Term(Box::new(format!("Field '{}' not found", field_name))))?;
+    if !matches!(
+        schema.get_field_entry(field).field_type(),
+        tantivy::schema::FieldType::Facet(_)
+    ) {
+        return Err(rustler::Error::Term(Box::new(format!(
+            "Field '{}' is not a facet field",
+            field_name
+        ))));
+    }
+
+    let base_facet = Facet::from_text(&base_path).map_err(|e| {
+        rustler::Error::Term(Box::new(format!(
+            "Invalid facet path '{}': {}",
+            base_path, e
+        )))
+    })?;
+
+    let mut collector = FacetCollector::for_field(&field_name);
+    collector.add_facet(base_facet.clone());
+
+    let facet_counts = searcher_res
+        .searcher
+        .search(&*query_res.query, &collector)
+        .map_err(|e| rustler::Error::Term(Box::new(format!("Faceted search failed: {}", e))))?;
+
+    let counts: serde_json::Map<String, serde_json::Value> = facet_counts
+        .get(base_facet)
+        .map(|(facet, count)| (facet.to_string(), serde_json::Value::from(count)))
+        .collect();
+
+    serde_json::to_string(&counts).map_err(|e| {
+        rustler::Error::Term(Box::new(format!(
+            "Failed to serialize facet results: {}",
+            e
+        )))
+    })
+}
+
 /// Creates a multi-facet boolean query
 #[rustler::nif]
 pub fn facet_multi_query(
@@ -186,3 +321,50 @@ pub fn facet_from_text(facet_path: String) -> NifResult<ResourceArc<FacetResourc
 pub fn facet_to_string(facet_res: ResourceArc<FacetResource>) -> NifResult<String> {
     Ok(facet_res.facet.to_string())
 }
+
+/// Builds an escaped facet path from unescaped segments, so segments may contain `/`
+#[rustler::nif]
+pub fn facet_encode(segments: Vec<String>) -> NifResult<String> {
+    // An empty segment would be indistinguishable from the root facet, and
+    // tantivy uses NUL internally as the segment separator
+    if segments
+        .iter()
+        .any(|segment| segment.is_empty() || segment.contains('\0'))
+    {
+        return Err(rustler::Error::Term(Box::new(
+            "Facet segments must be non-empty and must not contain NUL characters".to_string(),
+        )));
+    }
+
+    if segments.is_empty() {
+        return Ok("/".to_string());
+    }
+
+    let mut facet_path = String::new();
+    for segment in &segments {
+        facet_path.push('/');
+        for c in segment.chars() {
+            if c == '/' || c == '\\' {
+                facet_path.push('\\');
+            }
+            facet_path.push(c);
+        }
+    }
+    Ok(facet_path)
+}
+
+/// Splits a facet path into its unescaped segments, the inverse of `facet_encode`
+#[rustler::nif]
+pub fn facet_decode(facet_path: String) -> NifResult<Vec<String>> {
+    let facet = Facet::from_text(&facet_path).map_err(|e| {
+        rustler::Error::Term(Box::new(format!(
+            "Invalid facet path '{}': {}",
+            facet_path, e
+        )))
+    })?;
+
+    if facet.is_root() {
+        return Ok(Vec::new());
+    }
+    Ok(facet.to_path().into_iter().map(String::from).collect())
+}
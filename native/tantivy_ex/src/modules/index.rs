@@ -1,9 +1,21 @@
-use rustler::{Encoder, Env, NifResult, ResourceArc, Term};
-use std::path::Path;
+use rustler::{Binary, Encoder, Env, NifResult, OwnedBinary, ResourceArc, Term};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
-use tantivy::{directory::MmapDirectory, Index};
+use std::time::{Duration, Instant};
+use tantivy::directory::error::{DeleteError, LockError, OpenReadError, OpenWriteError};
+use tantivy::directory::{
+    Directory, DirectoryLock, FileHandle, Lock, MmapDirectory, RamDirectory, WatchCallback,
+    WatchHandle, WritePtr, INDEX_WRITER_LOCK,
+};
+use tantivy::store::Compressor;
+use tantivy::{Index, IndexReader, IndexSettings, ReloadPolicy};
 
-use crate::modules::resources::{IndexResource, IndexWriterResource, SchemaResource};
+use crate::modules::resources::{
+    atoms, IndexResource, IndexWriterResource, SchemaResource, SearcherResource,
+};
+use crate::modules::schema::field_type_name;
 
 /// Index creation and management functions
 #[rustler::nif(schedule = "DirtyIo")]
@@ -26,6 +38,55 @@ pub fn index_create_in_dir(
     match Index::create_in_dir(index_path, schema_res.schema.clone()) {
         Ok(index) => Ok(ResourceArc::new(IndexResource {
             index: Arc::new(index),
+            cached_reader: Mutex::new(None),
+        })),
+        Err(e) => Err(rustler::Error::Term(Box::new(format!(
+            "Failed to create index: {}",
+            e
+        )))),
+    }
+}
+
+/// Creates an index in a directory with custom index settings.
+///
+/// Settings are passed as a JSON object, e.g.
+/// `{"docstore_compression": "zstd(compression_level=9)", "docstore_blocksize": 65536}`.
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn index_create_in_dir_with_settings(
+    path: String,
+    schema_res: ResourceArc<SchemaResource>,
+    settings_json: String,
+) -> NifResult<ResourceArc<IndexResource>> {
+    let settings = match parse_index_settings(&settings_json) {
+        Ok(settings) => settings,
+        Err(e) => {
+            return Err(rustler::Error::Term(Box::new(format!(
+                "Invalid index settings: {}",
+                e
+            ))))
+        }
+    };
+
+    let index_path = Path::new(&path);
+
+    // Create the directory if it doesn't exist
+    if !index_path.exists() {
+        if let Err(e) = std::fs::create_dir_all(index_path) {
+            return Err(rustler::Error::Term(Box::new(format!(
+                "Failed to create directory: {}",
+                e
+            ))));
+        }
+    }
+
+    match Index::builder()
+        .schema(schema_res.schema.clone())
+        .settings(settings)
+        .create_in_dir(index_path)
+    {
+        Ok(index) => Ok(ResourceArc::new(IndexResource {
+            index: Arc::new(index),
+            cached_reader: Mutex::new(None),
         })),
         Err(e) => Err(rustler::Error::Term(Box::new(format!(
             "Failed to create index: {}",
@@ -41,17 +102,80 @@ pub fn index_create_in_ram(
     let index = Index::create_in_ram(schema_res.schema.clone());
     Ok(ResourceArc::new(IndexResource {
         index: Arc::new(index),
+        cached_reader: Mutex::new(None),
     }))
 }
 
-#[rustler::nif]
+/// Creates a writer, optionally retrying while the writer lock is held elsewhere.
+///
+/// Without `lock_timeout_ms` a busy lock fails immediately, as in tantivy. With
+/// it, acquiring the lock is retried until the timeout elapses, which covers a
+/// previous writer that is still shutting down.
+#[rustler::nif(schedule = "DirtyIo")]
 pub fn index_writer(
     index_res: ResourceArc<IndexResource>,
     memory_budget: u64,
+    lock_timeout_ms: Option<u64>,
+) -> NifResult<ResourceArc<IndexWriterResource>> {
+    // `None` means no deadline: a timeout too large to represent waits indefinitely
+    let deadline = Instant::now().checked_add(Duration::from_millis(lock_timeout_ms.unwrap_or(0)));
+
+    let writer = loop {
+        match index_res.index.writer(memory_budget as usize) {
+            Ok(writer) => break writer,
+            Err(tantivy::TantivyError::LockFailure(LockError::LockBusy, _))
+                if !deadline.is_some_and(|deadline| Instant::now() >= deadline) =>
+            {
+                let remaining = deadline.map_or(WRITER_LOCK_RETRY_INTERVAL, |deadline| {
+                    deadline.saturating_duration_since(Instant::now())
+                });
+                std::thread::sleep(WRITER_LOCK_RETRY_INTERVAL.min(remaining));
+            }
+            Err(e) => {
+                return Err(rustler::Error::Term(Box::new(format!(
+                    "Failed to create writer: {}",
+                    e
+                ))))
+            }
+        }
+    };
+
+    Ok(ResourceArc::new(IndexWriterResource {
+        writer: Arc::new(Mutex::new(writer)),
+        memory_budget: memory_budget as usize,
+        num_threads: None,
+        pending_documents: AtomicU64::new(0),
+    }))
+}
+
+/// How often `index_writer` retries a busy writer lock
+const WRITER_LOCK_RETRY_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Creates a writer with an explicit number of indexing threads.
+///
+/// The memory budget is shared: each thread gets `memory_budget / num_threads` bytes,
+/// and tantivy requires at least 15MB per thread.
+#[rustler::nif]
+pub fn index_writer_with_threads(
+    index_res: ResourceArc<IndexResource>,
+    memory_budget: u64,
+    num_threads: u64,
 ) -> NifResult<ResourceArc<IndexWriterResource>> {
-    match index_res.index.writer(memory_budget as usize) {
+    if num_threads < 1 {
+        return Err(rustler::Error::Term(Box::new(
+            "Number of indexing threads must be at least 1".to_string(),
+        )));
+    }
+
+    match index_res
+        .index
+        .writer_with_num_threads(num_threads as usize, memory_budget as usize)
+    {
         Ok(writer) => Ok(ResourceArc::new(IndexWriterResource {
             writer: Arc::new(Mutex::new(writer)),
+            memory_budget: memory_budget as usize,
+            num_threads: Some(num_threads as usize),
+            pending_documents: AtomicU64::new(0),
         })),
         Err(e) => Err(rustler::Error::Term(Box::new(format!(
             "Failed to create writer: {}",
@@ -80,6 +204,153 @@ pub fn index_reader<'a>(
     }
 }
 
+/// Create a searcher through a reader with an explicit reload policy
+///
+/// `policy` is `"manual"` (no background reload thread) or `"on_commit"`
+/// (tantivy's `OnCommitWithDelay`, the default used by `index_reader`).
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn index_reader_with_policy(
+    index_res: ResourceArc<IndexResource>,
+    policy: String,
+) -> NifResult<ResourceArc<SearcherResource>> {
+    let reload_policy = match policy.as_str() {
+        "manual" => ReloadPolicy::Manual,
+        "on_commit" => ReloadPolicy::OnCommitWithDelay,
+        _ => {
+            return Err(rustler::Error::Term(Box::new(format!(
+                "Unknown reload policy '{}'. Use 'manual' or 'on_commit'",
+                policy
+            ))))
+        }
+    };
+
+    let reader: IndexReader = index_res
+        .index
+        .reader_builder()
+        .reload_policy(reload_policy)
+        .try_into()
+        .map_err(|e| {
+            rustler::Error::Term(Box::new(format!("Failed to create index reader: {}", e)))
+        })?;
+
+    Ok(ResourceArc::new(SearcherResource {
+        searcher: Arc::new(reader.searcher()),
+    }))
+}
+
+/// Reload the index and return a searcher over its latest commit
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn reader_reload_and_searcher(
+    index_res: ResourceArc<IndexResource>,
+) -> NifResult<ResourceArc<SearcherResource>> {
+    let reader: IndexReader = index_res
+        .index
+        .reader_builder()
+        .reload_policy(ReloadPolicy::Manual)
+        .try_into()
+        .map_err(|e| {
+            rustler::Error::Term(Box::new(format!("Failed to create index reader: {}", e)))
+        })?;
+
+    reader
+        .reload()
+        .map_err(|e| rustler::Error::Term(Box::new(format!("Failed to reload reader: {}", e))))?;
+
+    Ok(ResourceArc::new(SearcherResource {
+        searcher: Arc::new(reader.searcher()),
+    }))
+}
+
+/// Return a searcher from the index's long-lived cached reader
+///
+/// The reader is created on first use and only reloaded when the committed
+/// opstamp has moved since the last call, so repeated calls don't rebuild it.
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn index_acquire_searcher(
+    index_res: ResourceArc<IndexResource>,
+) -> NifResult<ResourceArc<SearcherResource>> {
+    let searcher = cached_reader_searcher(&index_res)?;
+
+    Ok(ResourceArc::new(SearcherResource {
+        searcher: Arc::new(searcher),
+    }))
+}
+
+/// Get a searcher from the index's cached reader, reloading it if the committed opstamp moved
+pub(crate) fn cached_reader_searcher(index_res: &IndexResource) -> NifResult<tantivy::Searcher> {
+    let opstamp = index_res
+        .index
+        .load_metas()
+        .map_err(|e| rustler::Error::Term(Box::new(format!("Failed to load metas: {}", e))))?
+        .opstamp;
+
+    let mut cached = index_res
+        .cached_reader
+        .lock()
+        .map_err(|_| rustler::Error::Term(Box::new("Failed to acquire reader lock".to_string())))?;
+
+    if cached.is_none() {
+        let reader: IndexReader = index_res
+            .index
+            .reader_builder()
+            .reload_policy(ReloadPolicy::Manual)
+            .try_into()
+            .map_err(|e| {
+                rustler::Error::Term(Box::new(format!("Failed to create index reader: {}", e)))
+            })?;
+        *cached = Some((reader, opstamp));
+    }
+
+    let (reader, reader_opstamp) = cached.as_mut().expect("reader cache was just filled");
+    if *reader_opstamp != opstamp {
+        reader.reload().map_err(|e| {
+            rustler::Error::Term(Box::new(format!("Failed to reload reader: {}", e)))
+        })?;
+        *reader_opstamp = opstamp;
+    }
+
+    Ok(reader.searcher())
+}
+
+/// Block until the index's last commit has reached `opstamp`
+///
+/// Polls the committed index metas, so a reader reloaded after this returns
+/// sees every operation up to `opstamp`. Fails with the `timeout` atom when
+/// the opstamp is not reached within `timeout_ms`.
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn reader_wait_for_opstamp<'a>(
+    env: Env<'a>,
+    index_res: ResourceArc<IndexResource>,
+    opstamp: u64,
+    timeout_ms: u64,
+) -> NifResult<Term<'a>> {
+    // `None` means no deadline: a timeout too large to represent waits indefinitely
+    let deadline = Instant::now().checked_add(Duration::from_millis(timeout_ms));
+
+    loop {
+        let metas = index_res.index.load_metas().map_err(|e| {
+            rustler::Error::Term(Box::new(format!("Failed to load index metas: {}", e)))
+        })?;
+
+        if metas.opstamp >= opstamp {
+            return Ok(atoms::ok().encode(env));
+        }
+
+        let remaining = match deadline {
+            Some(deadline) => deadline.saturating_duration_since(Instant::now()),
+            None => OPSTAMP_POLL_INTERVAL,
+        };
+        if remaining.is_zero() {
+            return Err(rustler::Error::Term(Box::new(atoms::timeout())));
+        }
+
+        std::thread::sleep(OPSTAMP_POLL_INTERVAL.min(remaining));
+    }
+}
+
+/// How often `reader_wait_for_opstamp` re-reads the index metas
+const OPSTAMP_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
 #[rustler::nif(schedule = "DirtyIo")]
 pub fn index_open_in_dir(path: String) -> NifResult<ResourceArc<IndexResource>> {
     let index_path = Path::new(&path);
@@ -87,6 +358,7 @@ pub fn index_open_in_dir(path: String) -> NifResult<ResourceArc<IndexResource>>
     match Index::open_in_dir(index_path) {
         Ok(index) => Ok(ResourceArc::new(IndexResource {
             index: Arc::new(index),
+            cached_reader: Mutex::new(None),
         })),
         Err(e) => Err(rustler::Error::Term(Box::new(format!(
             "Failed to open index: {}",
@@ -95,6 +367,114 @@ pub fn index_open_in_dir(path: String) -> NifResult<ResourceArc<IndexResource>>
     }
 }
 
+/// Opens an index for searching only.
+///
+/// The directory never creates lock files, so replicas on a shared read-only
+/// volume can open an index even while an indexer holds the writer lock.
+/// Creating a writer on the returned index fails.
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn index_open_read_only(path: String) -> NifResult<ResourceArc<IndexResource>> {
+    let directory = match MmapDirectory::open(Path::new(&path)) {
+        Ok(directory) => directory,
+        Err(e) => {
+            return Err(rustler::Error::Term(Box::new(format!(
+                "Failed to open directory: {}",
+                e
+            ))))
+        }
+    };
+
+    match Index::open(ReadOnlyDirectory { inner: directory }) {
+        Ok(index) => Ok(ResourceArc::new(IndexResource {
+            index: Arc::new(index),
+            cached_reader: Mutex::new(None),
+        })),
+        Err(e) => Err(rustler::Error::Term(Box::new(format!(
+            "Failed to open index: {}",
+            e
+        )))),
+    }
+}
+
+/// Directory wrapper that serves reads from an `MmapDirectory` and refuses every write.
+///
+/// Reader locks are granted without touching the disk; the writer lock is always refused.
+#[derive(Clone, Debug)]
+struct ReadOnlyDirectory {
+    inner: MmapDirectory,
+}
+
+fn read_only_error() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::PermissionDenied,
+        "index was opened read-only",
+    )
+}
+
+impl Directory for ReadOnlyDirectory {
+    fn get_file_handle(&self, path: &Path) -> Result<Arc<dyn FileHandle>, OpenReadError> {
+        self.inner.get_file_handle(path)
+    }
+
+    fn delete(&self, path: &Path) -> Result<(), DeleteError> {
+        Err(DeleteError::IoError {
+            io_error: Arc::new(read_only_error()),
+            filepath: path.to_path_buf(),
+        })
+    }
+
+    fn exists(&self, path: &Path) -> Result<bool, OpenReadError> {
+        self.inner.exists(path)
+    }
+
+    fn open_write(&self, path: &Path) -> Result<WritePtr, OpenWriteError> {
+        Err(OpenWriteError::IoError {
+            io_error: Arc::new(read_only_error()),
+            filepath: path.to_path_buf(),
+        })
+    }
+
+    fn atomic_read(&self, path: &Path) -> Result<Vec<u8>, OpenReadError> {
+        self.inner.atomic_read(path)
+    }
+
+    fn atomic_write(&self, _path: &Path, _data: &[u8]) -> io::Result<()> {
+        Err(read_only_error())
+    }
+
+    fn sync_directory(&self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn acquire_lock(&self, lock: &Lock) -> Result<DirectoryLock, LockError> {
+        if lock.filepath == INDEX_WRITER_LOCK.filepath {
+            return Err(LockError::IoError(Arc::new(read_only_error())));
+        }
+        Ok(DirectoryLock::from(Box::new(())))
+    }
+
+    fn watch(&self, watch_callback: WatchCallback) -> tantivy::Result<WatchHandle> {
+        self.inner.watch(watch_callback)
+    }
+}
+
+/// Checks whether the directory contains an index (i.e. has a `meta.json`).
+///
+/// Never fails: a missing or unreadable directory is reported as `false`.
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn index_exists_in_dir(path: String) -> bool {
+    let index_path = Path::new(&path);
+
+    if !index_path.is_dir() {
+        return false;
+    }
+
+    match MmapDirectory::open(index_path) {
+        Ok(directory) => Index::exists(&directory).unwrap_or(false),
+        Err(_) => false,
+    }
+}
+
 #[rustler::nif(schedule = "DirtyIo")]
 pub fn index_open_or_create_in_dir(
     path: String,
@@ -117,6 +497,7 @@ pub fn index_open_or_create_in_dir(
         Ok(directory) => match Index::open_or_create(directory, schema_res.schema.clone()) {
             Ok(index) => Ok(ResourceArc::new(IndexResource {
                 index: Arc::new(index),
+                cached_reader: Mutex::new(None),
             })),
             Err(e) => Err(rustler::Error::Term(Box::new(format!(
                 "Failed to open or create index: {}",
@@ -129,3 +510,590 @@ pub fn index_open_or_create_in_dir(
         )))),
     }
 }
+
+/// Reports the writer's configured memory budget and buffered work.
+///
+/// tantivy does not expose how full its indexing arena is, so the buffered
+/// state is reported as the number of documents added since the last commit
+/// or rollback, plus the opstamp of the last commit.
+#[rustler::nif]
+pub fn writer_status(writer_res: ResourceArc<IndexWriterResource>) -> NifResult<String> {
+    let commit_opstamp = writer_res.writer.lock().unwrap().commit_opstamp();
+
+    let result = serde_json::json!({
+        "memory_budget": writer_res.memory_budget,
+        "num_threads": writer_res.num_threads,
+        "pending_documents": writer_res.pending_documents.load(Ordering::Relaxed),
+        "commit_opstamp": commit_opstamp,
+    });
+
+    serde_json::to_string(&result).map_err(|e| {
+        rustler::Error::Term(Box::new(format!(
+            "Failed to serialize writer status: {}",
+            e
+        )))
+    })
+}
+
+/// Describes an opened index: its schema fields, settings, last committed opstamp
+/// and document count.
+///
+/// Returns a JSON object. `directory_type` is `"mmap"`, `"ram"` or `"other"`.
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn index_metadata(index_res: ResourceArc<IndexResource>) -> NifResult<String> {
+    let index = &index_res.index;
+
+    let metas = index.load_metas().map_err(|e| {
+        rustler::Error::Term(Box::new(format!("Failed to load index metas: {}", e)))
+    })?;
+
+    let reader = index.reader().map_err(|e| {
+        rustler::Error::Term(Box::new(format!("Failed to create index reader: {}", e)))
+    })?;
+
+    let schema = index.schema();
+    let fields: Vec<serde_json::Value> = schema
+        .fields()
+        .map(|(_, field_entry)| {
+            serde_json::json!({
+                "name": field_entry.name(),
+                "type": field_type_name(field_entry.field_type()),
+                "indexed": field_entry.is_indexed(),
+                "stored": field_entry.is_stored(),
+                "fast": field_entry.is_fast(),
+            })
+        })
+        .collect();
+
+    // The managed directory wraps the concrete one, which only exposes itself via Debug
+    let directory_debug = format!("{:?}", index.directory());
+    let directory_type = if directory_debug.contains("MmapDirectory") {
+        "mmap"
+    } else if directory_debug.contains("RamDirectory") {
+        "ram"
+    } else {
+        "other"
+    };
+
+    let settings = index.settings();
+    let compression =
+        serde_json::to_value(settings.docstore_compression).unwrap_or(serde_json::Value::Null);
+    let result = serde_json::json!({
+        "fields": fields,
+        "opstamp": metas.opstamp,
+        "num_docs": reader.searcher().num_docs(),
+        "segment_count": metas.segments.len(),
+        "directory_type": directory_type,
+        "settings": {
+            "docstore_compression": compression,
+            "docstore_blocksize": settings.docstore_blocksize,
+        },
+    });
+
+    serde_json::to_string(&result).map_err(|e| {
+        rustler::Error::Term(Box::new(format!(
+            "Failed to serialize index metadata: {}",
+            e
+        )))
+    })
+}
+
+/// Reads the committed index metas: the commit opstamp and the committed segment ids.
+///
+/// Cheaper than `index_metadata` as no reader is opened, so replicas can poll it to
+/// detect a new commit. Returns a JSON object.
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn index_load_metas(index_res: ResourceArc<IndexResource>) -> NifResult<String> {
+    let metas = index_res.index.load_metas().map_err(|e| {
+        rustler::Error::Term(Box::new(format!("Failed to load index metas: {}", e)))
+    })?;
+
+    let segment_ids: Vec<String> = metas
+        .segments
+        .iter()
+        .map(|segment_meta| segment_meta.id().uuid_string())
+        .collect();
+    let result = serde_json::json!({
+        "opstamp": metas.opstamp,
+        "segment_count": segment_ids.len(),
+        "segment_ids": segment_ids,
+    });
+
+    Ok(result.to_string())
+}
+
+/// Removes files that are no longer referenced by the index (e.g. after merges and deletes).
+///
+/// Garbage collection is driven by the index writer, which knows which files are still in use.
+/// Returns a JSON summary of deleted files and files that could not be deleted yet.
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn index_garbage_collect_files(
+    writer_res: ResourceArc<IndexWriterResource>,
+) -> NifResult<String> {
+    let writer = writer_res.writer.lock().unwrap();
+
+    match writer.garbage_collect_files().wait() {
+        Ok(gc_result) => {
+            let to_strings = |paths: &[std::path::PathBuf]| -> Vec<String> {
+                paths
+                    .iter()
+                    .map(|path| path.to_string_lossy().to_string())
+                    .collect()
+            };
+
+            let result = serde_json::json!({
+                "deleted_count": gc_result.deleted_files.len(),
+                "deleted_files": to_strings(&gc_result.deleted_files),
+                "failed_count": gc_result.failed_to_delete_files.len(),
+                "failed_to_delete_files": to_strings(&gc_result.failed_to_delete_files),
+            });
+
+            match serde_json::to_string(&result) {
+                Ok(json) => Ok(json),
+                Err(e) => Err(rustler::Error::Term(Box::new(format!(
+                    "Failed to serialize garbage collection result: {}",
+                    e
+                )))),
+            }
+        }
+        Err(e) => Err(rustler::Error::Term(Box::new(format!(
+            "Failed to garbage collect files: {}",
+            e
+        )))),
+    }
+}
+
+/// Commits, force-merges down to `max_segments`, waits for the merge and garbage-collects files.
+///
+/// The smallest segments are merged together into one so that at most
+/// `max_segments` searchable segments remain. Returns a JSON summary with
+/// `segments_before`, `segments_after` and `files_deleted`.
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn index_optimize(
+    writer_res: ResourceArc<IndexWriterResource>,
+    max_segments: u64,
+) -> NifResult<String> {
+    if max_segments < 1 {
+        return Err(rustler::Error::Term(Box::new(
+            "max_segments must be at least 1".to_string(),
+        )));
+    }
+
+    let mut writer = writer_res.writer.lock().unwrap();
+
+    writer
+        .commit()
+        .map_err(|e| rustler::Error::Term(Box::new(format!("Failed to commit: {}", e))))?;
+    writer_res.pending_documents.store(0, Ordering::Relaxed);
+
+    let load_segment_metas = |index: &Index| {
+        index.searchable_segment_metas().map_err(|e| {
+            rustler::Error::Term(Box::new(format!("Failed to load segment metas: {}", e)))
+        })
+    };
+
+    let mut segment_metas = load_segment_metas(writer.index())?;
+    let segments_before = segment_metas.len();
+
+    if segments_before > max_segments as usize {
+        segment_metas.sort_by_key(|meta| meta.num_docs());
+        let merge_count = segments_before - max_segments as usize + 1;
+        let segment_ids: Vec<_> = segment_metas
+            .iter()
+            .take(merge_count)
+            .map(|meta| meta.id())
+            .collect();
+
+        writer.merge(&segment_ids).wait().map_err(|e| {
+            rustler::Error::Term(Box::new(format!("Failed to merge segments: {}", e)))
+        })?;
+    }
+
+    let gc_result = writer.garbage_collect_files().wait().map_err(|e| {
+        rustler::Error::Term(Box::new(format!("Failed to garbage collect files: {}", e)))
+    })?;
+
+    let segments_after = load_segment_metas(writer.index())?.len();
+
+    let result = serde_json::json!({
+        "segments_before": segments_before,
+        "segments_after": segments_after,
+        "files_deleted": gc_result.deleted_files.len(),
+    });
+
+    serde_json::to_string(&result).map_err(|e| {
+        rustler::Error::Term(Box::new(format!(
+            "Failed to serialize optimize result: {}",
+            e
+        )))
+    })
+}
+
+/// Commits, then merges away the deleted documents of heavily-deleted segments.
+///
+/// Every searchable segment whose deleted/max_doc ratio exceeds
+/// `min_deleted_ratio` is merged (together with the other selected segments)
+/// into a fresh segment holding only its live documents. The merge is waited
+/// for and unreferenced files are garbage-collected, so the space is reclaimed
+/// when the call returns. Returns a JSON summary with `segments_expunged`,
+/// `deleted_docs_reclaimed`, `segments_before`, `segments_after` and
+/// `files_deleted`.
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn writer_expunge_deleted(
+    writer_res: ResourceArc<IndexWriterResource>,
+    min_deleted_ratio: f64,
+) -> NifResult<String> {
+    if !(0.0..=1.0).contains(&min_deleted_ratio) {
+        return Err(rustler::Error::Term(Box::new(
+            "min_deleted_ratio must be between 0.0 and 1.0".to_string(),
+        )));
+    }
+
+    let mut writer = writer_res.writer.lock().unwrap();
+
+    writer
+        .commit()
+        .map_err(|e| rustler::Error::Term(Box::new(format!("Failed to commit: {}", e))))?;
+    writer_res.pending_documents.store(0, Ordering::Relaxed);
+
+    let load_segment_metas = |index: &Index| {
+        index.searchable_segment_metas().map_err(|e| {
+            rustler::Error::Term(Box::new(format!("Failed to load segment metas: {}", e)))
+        })
+    };
+
+    let segment_metas = load_segment_metas(writer.index())?;
+    let segments_before = segment_metas.len();
+
+    let expunged: Vec<_> = segment_metas
+        .iter()
+        .filter(|meta| {
+            meta.num_deleted_docs() > 0
+                && meta.num_deleted_docs() as f64 / meta.max_doc() as f64 > min_deleted_ratio
+        })
+        .collect();
+    let deleted_docs_reclaimed: u64 = expunged
+        .iter()
+        .map(|meta| meta.num_deleted_docs() as u64)
+        .sum();
+
+    if !expunged.is_empty() {
+        let segment_ids: Vec<_> = expunged.iter().map(|meta| meta.id()).collect();
+        writer.merge(&segment_ids).wait().map_err(|e| {
+            rustler::Error::Term(Box::new(format!("Failed to merge segments: {}", e)))
+        })?;
+    }
+
+    let gc_result = writer.garbage_collect_files().wait().map_err(|e| {
+        rustler::Error::Term(Box::new(format!("Failed to garbage collect files: {}", e)))
+    })?;
+
+    let segments_after = load_segment_metas(writer.index())?.len();
+
+    let result = serde_json::json!({
+        "segments_expunged": expunged.len(),
+        "deleted_docs_reclaimed": deleted_docs_reclaimed,
+        "segments_before": segments_before,
+        "segments_after": segments_after,
+        "files_deleted": gc_result.deleted_files.len(),
+    });
+
+    serde_json::to_string(&result).map_err(|e| {
+        rustler::Error::Term(Box::new(format!(
+            "Failed to serialize expunge result: {}",
+            e
+        )))
+    })
+}
+
+/// Magic header identifying a serialized index snapshot
+const INDEX_SNAPSHOT_MAGIC: &[u8; 8] = b"TEXIDX01";
+
+/// Serializes the committed state of an index into a single byte buffer.
+///
+/// The snapshot contains `meta.json`, `.managed.json` and every file of the
+/// searchable segments, so it can be loaded back with `index_open_from_bytes`.
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn index_save_to_bytes<'a>(
+    env: Env<'a>,
+    index_res: ResourceArc<IndexResource>,
+) -> NifResult<Binary<'a>> {
+    let index = &index_res.index;
+    let directory = index.directory();
+
+    let segment_metas = match index.searchable_segment_metas() {
+        Ok(metas) => metas,
+        Err(e) => {
+            return Err(rustler::Error::Term(Box::new(format!(
+                "Failed to load segment metas: {}",
+                e
+            ))))
+        }
+    };
+
+    let mut paths: Vec<PathBuf> = vec![PathBuf::from("meta.json"), PathBuf::from(".managed.json")];
+    for segment_meta in &segment_metas {
+        let mut segment_files: Vec<PathBuf> = segment_meta.list_files().into_iter().collect();
+        segment_files.sort();
+        paths.extend(segment_files);
+    }
+
+    let mut files: Vec<(String, Vec<u8>)> = Vec::new();
+    for path in paths {
+        // Some segment components (e.g. delete bitsets) only exist when used
+        if !directory.exists(&path).unwrap_or(false) {
+            continue;
+        }
+
+        let data = match directory.atomic_read(&path) {
+            Ok(data) => data,
+            Err(e) => {
+                return Err(rustler::Error::Term(Box::new(format!(
+                    "Failed to read index file '{}': {}",
+                    path.display(),
+                    e
+                ))))
+            }
+        };
+        files.push((path.to_string_lossy().to_string(), data));
+    }
+
+    let mut buffer = Vec::new();
+    buffer.extend_from_slice(INDEX_SNAPSHOT_MAGIC);
+    buffer.extend_from_slice(&(files.len() as u32).to_le_bytes());
+    for (path, data) in &files {
+        buffer.extend_from_slice(&(path.len() as u32).to_le_bytes());
+        buffer.extend_from_slice(path.as_bytes());
+        buffer.extend_from_slice(&(data.len() as u64).to_le_bytes());
+        buffer.extend_from_slice(data);
+    }
+
+    let mut binary = match OwnedBinary::new(buffer.len()) {
+        Some(binary) => binary,
+        None => {
+            return Err(rustler::Error::Term(Box::new(
+                "Failed to allocate binary for index snapshot".to_string(),
+            )))
+        }
+    };
+    binary.as_mut_slice().copy_from_slice(&buffer);
+
+    Ok(binary.release(env))
+}
+
+/// Loads an index snapshot produced by `index_save_to_bytes` into a RAM directory.
+///
+/// The schema stored in the snapshot must match the given schema.
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn index_open_from_bytes(
+    bytes: Binary,
+    schema_res: ResourceArc<SchemaResource>,
+) -> NifResult<ResourceArc<IndexResource>> {
+    let files = match parse_index_snapshot(bytes.as_slice()) {
+        Ok(files) => files,
+        Err(e) => {
+            return Err(rustler::Error::Term(Box::new(format!(
+                "Invalid index snapshot: {}",
+                e
+            ))))
+        }
+    };
+
+    let directory = RamDirectory::create();
+    for (path, data) in files {
+        if let Err(e) = directory.atomic_write(Path::new(&path), data) {
+            return Err(rustler::Error::Term(Box::new(format!(
+                "Failed to write index file '{}': {}",
+                path, e
+            ))));
+        }
+    }
+
+    // A snapshot without meta.json is an error rather than a new empty index
+    let index = Index::open(directory).map_err(|e| {
+        rustler::Error::Term(Box::new(format!("Failed to open index from bytes: {}", e)))
+    })?;
+    if index.schema() != schema_res.schema {
+        return Err(rustler::Error::Term(Box::new(
+            "Failed to open index from bytes: the snapshot schema does not match".to_string(),
+        )));
+    }
+
+    Ok(ResourceArc::new(IndexResource {
+        index: Arc::new(index),
+        cached_reader: Mutex::new(None),
+    }))
+}
+
+/// Helper function to split a serialized index snapshot into (path, data) pairs
+fn parse_index_snapshot(bytes: &[u8]) -> Result<Vec<(String, &[u8])>, String> {
+    fn take<'b>(bytes: &'b [u8], cursor: &mut usize, len: usize) -> Result<&'b [u8], String> {
+        let end = cursor
+            .checked_add(len)
+            .filter(|end| *end <= bytes.len())
+            .ok_or("unexpected end of data")?;
+        let slice = &bytes[*cursor..end];
+        *cursor = end;
+        Ok(slice)
+    }
+
+    let mut cursor = 0;
+    if take(bytes, &mut cursor, INDEX_SNAPSHOT_MAGIC.len())? != INDEX_SNAPSHOT_MAGIC {
+        return Err("missing snapshot header".to_string());
+    }
+
+    let file_count = u32::from_le_bytes(take(bytes, &mut cursor, 4)?.try_into().unwrap());
+    // The count comes from the caller, so never reserve more entries than the
+    // remaining bytes could hold (each entry has at least its two length prefixes)
+    let max_entries = (bytes.len() - cursor) / (4 + 8);
+    let mut files = Vec::with_capacity((file_count as usize).min(max_entries));
+    for _ in 0..file_count {
+        let path_len = u32::from_le_bytes(take(bytes, &mut cursor, 4)?.try_into().unwrap());
+        let path = std::str::from_utf8(take(bytes, &mut cursor, path_len as usize)?)
+            .map_err(|_| "file path is not valid UTF-8")?
+            .to_string();
+        let data_len = u64::from_le_bytes(take(bytes, &mut cursor, 8)?.try_into().unwrap());
+        let data = take(bytes, &mut cursor, data_len as usize)?;
+        files.push((path, data));
+    }
+
+    Ok(files)
+}
+
+/// Copies the committed state of an index into `dest_path`.
+///
+/// The segment files are taken from the loaded `meta.json`, whose segment
+/// metas keep those files safe from garbage collection while they are copied.
+/// `meta.json` is written last, so the destination only becomes an openable
+/// index once every file it references is in place.
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn index_snapshot(
+    index_res: ResourceArc<IndexResource>,
+    dest_path: String,
+) -> NifResult<String> {
+    let index = &index_res.index;
+    let directory = index.directory();
+    let dest = Path::new(&dest_path);
+
+    if dest.join("meta.json").exists() {
+        return Err(rustler::Error::Term(Box::new(format!(
+            "Destination '{}' already contains an index",
+            dest_path
+        ))));
+    }
+    std::fs::create_dir_all(dest).map_err(|e| {
+        rustler::Error::Term(Box::new(format!(
+            "Failed to create snapshot directory '{}': {}",
+            dest_path, e
+        )))
+    })?;
+
+    let metas = index.load_metas().map_err(|e| {
+        rustler::Error::Term(Box::new(format!("Failed to load index metas: {}", e)))
+    })?;
+
+    let mut paths: Vec<PathBuf> = Vec::new();
+    for segment_meta in &metas.segments {
+        let mut segment_files: Vec<PathBuf> = segment_meta.list_files().into_iter().collect();
+        segment_files.sort();
+        paths.extend(segment_files);
+    }
+
+    let mut copied: Vec<String> = Vec::new();
+    let mut total_bytes: u64 = 0;
+    for path in paths {
+        // Some segment components (e.g. delete bitsets) only exist when used
+        if !directory.exists(&path).unwrap_or(false) {
+            continue;
+        }
+
+        let data = directory.atomic_read(&path).map_err(|e| {
+            rustler::Error::Term(Box::new(format!(
+                "Failed to read index file '{}': {}",
+                path.display(),
+                e
+            )))
+        })?;
+        std::fs::write(dest.join(&path), &data).map_err(|e| {
+            rustler::Error::Term(Box::new(format!(
+                "Failed to write snapshot file '{}': {}",
+                path.display(),
+                e
+            )))
+        })?;
+
+        total_bytes += data.len() as u64;
+        copied.push(path.to_string_lossy().to_string());
+    }
+
+    // Let the snapshot's own writers garbage-collect the copied files later on
+    let mut managed_files = copied.clone();
+    managed_files.push("meta.json".to_string());
+    let managed_json = serde_json::to_vec(&managed_files).map_err(|e| {
+        rustler::Error::Term(Box::new(format!(
+            "Failed to serialize managed files: {}",
+            e
+        )))
+    })?;
+    std::fs::write(dest.join(".managed.json"), managed_json).map_err(|e| {
+        rustler::Error::Term(Box::new(format!("Failed to write .managed.json: {}", e)))
+    })?;
+
+    let meta_json = serde_json::to_string_pretty(&metas).map_err(|e| {
+        rustler::Error::Term(Box::new(format!("Failed to serialize index metas: {}", e)))
+    })?;
+    let meta_tmp = dest.join("meta.json.tmp");
+    std::fs::write(&meta_tmp, format!("{}\n", meta_json))
+        .and_then(|_| std::fs::rename(&meta_tmp, dest.join("meta.json")))
+        .map_err(|e| rustler::Error::Term(Box::new(format!("Failed to write meta.json: {}", e))))?;
+
+    let result = serde_json::json!({
+        "path": dest_path,
+        "opstamp": metas.opstamp,
+        "segment_count": metas.segments.len(),
+        "files": copied,
+        "total_bytes": total_bytes,
+    });
+
+    serde_json::to_string(&result).map_err(|e| {
+        rustler::Error::Term(Box::new(format!(
+            "Failed to serialize snapshot result: {}",
+            e
+        )))
+    })
+}
+
+/// Helper function to build `IndexSettings` from a JSON object, keeping defaults for missing keys
+fn parse_index_settings(settings_json: &str) -> Result<IndexSettings, String> {
+    let settings_value: serde_json::Value =
+        serde_json::from_str(settings_json).map_err(|e| format!("Invalid JSON: {}", e))?;
+    let settings_obj = settings_value
+        .as_object()
+        .ok_or("Index settings must be a JSON object")?;
+
+    // tantivy 0.24 removed index sorting, so refuse it rather than silently
+    // creating an unsorted index
+    if settings_obj.contains_key("sort_by_field") {
+        return Err("sort_by_field is not supported: tantivy 0.24 removed index sorting".into());
+    }
+
+    let mut settings = IndexSettings::default();
+
+    if let Some(compression) = settings_obj.get("docstore_compression") {
+        // Reuse tantivy's own parser, which accepts "none", "lz4", "zstd" and
+        // "zstd(compression_level=N)"
+        settings.docstore_compression =
+            serde_json::from_value::<Compressor>(compression.clone())
+                .map_err(|e| format!("Invalid docstore_compression: {}", e))?;
+    }
+
+    if let Some(blocksize) = settings_obj.get("docstore_blocksize") {
+        let blocksize = blocksize
+            .as_u64()
+            .filter(|size| *size > 0)
+            .ok_or("docstore_blocksize must be a positive integer")?;
+        settings.docstore_blocksize = blocksize as usize;
+    }
+
+    Ok(settings)
+}
@@ -209,6 +209,91 @@ pub fn index_warming_warm_index(
     Ok(rustler::types::atom::ok())
 }
 
+/// Warm the fast field columns of the given fields and cache the searcher.
+///
+/// Reads every column of each field in every segment, touching each page so the
+/// columnar data is resident before the first sort or aggregation. Returns the
+/// number of bytes warmed.
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn index_warming_warm_fast_fields(
+    warming_resource: ResourceArc<IndexWarmingResource>,
+    index_resource: ResourceArc<IndexResource>,
+    field_names: Vec<String>,
+    cache_key: String,
+) -> NifResult<u64> {
+    let start_time = Instant::now();
+    let schema = index_resource.index.schema();
+
+    for field_name in &field_names {
+        let field = schema.get_field(field_name).map_err(|_| {
+            Error::Term(Box::new(format!(
+                "Field '{}' not found in schema",
+                field_name
+            )))
+        })?;
+        if !schema.get_field_entry(field).is_fast() {
+            return Err(Error::Term(Box::new(format!(
+                "Field '{}' is not a fast field",
+                field_name
+            ))));
+        }
+    }
+
+    let reader = index_resource.index.reader().map_err(|_| Error::BadArg)?;
+    let searcher = reader.searcher();
+
+    let mut bytes_warmed: u64 = 0;
+    for segment_reader in searcher.segment_readers() {
+        let fast_fields = segment_reader.fast_fields();
+        for field_name in &field_names {
+            let handles = fast_fields
+                .dynamic_column_handles(field_name)
+                .map_err(|e| {
+                    Error::Term(Box::new(format!(
+                        "Failed to open fast field '{}': {}",
+                        field_name, e
+                    )))
+                })?;
+            for handle in handles {
+                let bytes = handle.file_slice().read_bytes().map_err(|e| {
+                    Error::Term(Box::new(format!(
+                        "Failed to read fast field '{}': {}",
+                        field_name, e
+                    )))
+                })?;
+                // Touch one byte per page so mmapped columns are paged in
+                let checksum = bytes
+                    .as_slice()
+                    .iter()
+                    .step_by(4096)
+                    .fold(0u8, |acc, byte| acc.wrapping_add(*byte));
+                std::hint::black_box(checksum);
+                bytes_warmed += bytes.len() as u64;
+            }
+        }
+    }
+
+    let cached_searcher = CachedSearcher {
+        searcher: Arc::new(searcher),
+        created_at: Instant::now(),
+        access_count: 0,
+        last_accessed: Instant::now(),
+        size_bytes: bytes_warmed as usize,
+    };
+
+    let mut cache = warming_resource.cache.lock().unwrap();
+    let mut stats = warming_resource.stats.lock().unwrap();
+    if let Some(previous) = cache.insert(cache_key, cached_searcher) {
+        stats.memory_usage_bytes = stats.memory_usage_bytes.saturating_sub(previous.size_bytes);
+    }
+
+    stats.warming_operations += 1;
+    stats.total_warming_time_ms += start_time.elapsed().as_millis() as u64;
+    stats.memory_usage_bytes += bytes_warmed as usize;
+
+    Ok(bytes_warmed)
+}
+
 /// Get a cached searcher
 #[rustler::nif]
 pub fn index_warming_get_searcher(
@@ -2,6 +2,7 @@ use rustler::{Env, Error, NifResult, ResourceArc, Term};
 use std::sync::Arc;
 use tantivy::index::SegmentId;
 use tantivy::indexer::{LogMergePolicy, MergePolicy, NoMergePolicy};
+use tantivy::SegmentReader;
 
 use crate::modules::resources::{IndexResource, IndexWriterResource};
 
@@ -148,3 +149,32 @@ pub fn index_get_num_segments(index_resource: ResourceArc<IndexResource>) -> Nif
         Err(_) => Err(Error::BadArg),
     }
 }
+
+/// List `(segment_id, num_docs, num_deleted)` for every searchable segment
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn index_segment_doc_counts(
+    index_resource: ResourceArc<IndexResource>,
+) -> NifResult<Vec<(String, u32, u32)>> {
+    let segments = index_resource
+        .index
+        .searchable_segments()
+        .map_err(|e| Error::Term(Box::new(format!("Failed to list segments: {}", e))))?;
+
+    segments
+        .iter()
+        .map(|segment| {
+            let reader = SegmentReader::open(segment).map_err(|e| {
+                Error::Term(Box::new(format!(
+                    "Failed to open segment {}: {}",
+                    segment.id().uuid_string(),
+                    e
+                )))
+            })?;
+            Ok((
+                segment.id().uuid_string(),
+                reader.num_docs(),
+                reader.num_deleted_docs(),
+            ))
+        })
+        .collect()
+}
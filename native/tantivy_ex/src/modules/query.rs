@@ -1,25 +1,42 @@
 use rustler::{NifResult, ResourceArc};
 use serde_json;
+use std::collections::HashMap;
+use std::fmt;
 use std::ops::Bound;
 use tantivy::query::Occur;
 use tantivy::query::{
-    AllQuery, BooleanQuery, EmptyQuery, ExistsQuery, FuzzyTermQuery, MoreLikeThisQuery,
-    PhrasePrefixQuery, PhraseQuery, QueryParser, RangeQuery, RegexQuery, TermQuery,
+    AllQuery, BooleanQuery, BoostQuery, DisjunctionMaxQuery, EmptyQuery, EnableScoring,
+    ExistsQuery, FuzzyTermQuery, MoreLikeThisQuery, MoreLikeThisQueryBuilder, PhrasePrefixQuery,
+    PhraseQuery, Query, QueryParser, RangeQuery, RegexQuery, TermQuery, TermSetQuery, Weight,
 };
-use tantivy::schema::{FieldType, OwnedValue};
+use tantivy::schema::{Field, FieldType, OwnedValue, Schema};
+use tantivy::tokenizer::TokenizerManager;
 use tantivy::Term as TantivyTerm;
+use tantivy::{DocAddress, Score};
 
 use crate::modules::resources::{
-    IndexResource, QueryParserResource, QueryResource, SchemaResource,
+    IndexResource, QueryParserResource, QueryResource, SchemaResource, SearcherResource,
 };
+use crate::modules::schema::add_field_entry_to_builder;
+use crate::modules::tokenizer::analyzer_for_tokenizer;
 
 /// Query system functions
 
+/// Create a query parser over the given default fields.
+///
+/// With `strict`, any unknown field is an error. Otherwise unknown fields are
+/// dropped and returned alongside the parser, which is only refused when no
+/// valid field remains.
+///
+/// `field_tokenizers` maps text fields to the tokenizer their query text is
+/// analyzed with instead of the one they were indexed with.
 #[rustler::nif]
 pub fn query_parser_new(
     index_res: ResourceArc<IndexResource>,
     default_fields: Vec<String>,
-) -> NifResult<ResourceArc<QueryParserResource>> {
+    strict: bool,
+    field_tokenizers: HashMap<String, String>,
+) -> NifResult<(ResourceArc<QueryParserResource>, Vec<String>)> {
     // Need at least one default field
     if default_fields.is_empty() {
         return Err(rustler::Error::Term(Box::new(
@@ -28,15 +45,19 @@ pub fn query_parser_new(
     }
 
     // Convert field names to Field objects
+    let schema = index_res.index.schema();
     let mut fields = Vec::new();
+    let mut skipped_fields = Vec::new();
     for field_name in default_fields {
-        if let Ok(field) = index_res.index.schema().get_field(&field_name) {
+        if let Ok(field) = schema.get_field(&field_name) {
             fields.push(field);
-        } else {
+        } else if strict {
             return Err(rustler::Error::Term(Box::new(format!(
                 "Field '{}' not found in schema",
                 field_name
             ))));
+        } else {
+            skipped_fields.push(field_name);
         }
     }
 
@@ -48,8 +69,94 @@ pub fn query_parser_new(
     }
 
     // Create the parser using fields we found
-    let parser = QueryParser::for_index(&*index_res.index, fields);
-    Ok(ResourceArc::new(QueryParserResource { parser }))
+    let parser = if field_tokenizers.is_empty() {
+        QueryParser::for_index(&*index_res.index, fields)
+    } else {
+        parser_with_field_tokenizers(&index_res.index, fields, &field_tokenizers)?
+    };
+    Ok((
+        ResourceArc::new(QueryParserResource { parser, schema }),
+        skipped_fields,
+    ))
+}
+
+/// Build a parser whose view of the schema analyzes some fields with other tokenizers.
+///
+/// The parser gets a copy of the schema where the overridden fields carry the
+/// query-time tokenizer. Fields keep their position, so the terms it produces
+/// address the real index fields.
+fn parser_with_field_tokenizers(
+    index: &tantivy::Index,
+    default_fields: Vec<Field>,
+    field_tokenizers: &HashMap<String, String>,
+) -> NifResult<QueryParser> {
+    let schema = index.schema();
+    let resolve_analyzer = |name: &str| {
+        index
+            .tokenizers()
+            .get(name)
+            .or_else(|| analyzer_for_tokenizer(name))
+    };
+
+    for (field_name, tokenizer) in field_tokenizers {
+        let field = schema.get_field(field_name).map_err(|_| {
+            rustler::Error::Term(Box::new(format!(
+                "Field '{}' not found in schema",
+                field_name
+            )))
+        })?;
+        let indexed_text = match schema.get_field_entry(field).field_type() {
+            FieldType::Str(text_options) => text_options.get_indexing_options().is_some(),
+            _ => false,
+        };
+        if !indexed_text {
+            return Err(rustler::Error::Term(Box::new(format!(
+                "Field '{}' must be an indexed text field to override its tokenizer",
+                field_name
+            ))));
+        }
+        if resolve_analyzer(tokenizer).is_none() {
+            return Err(rustler::Error::Term(Box::new(format!(
+                "Tokenizer '{}' for field '{}' is not registered",
+                tokenizer, field_name
+            ))));
+        }
+    }
+
+    let mut schema_builder = Schema::builder();
+    for (_field, field_entry) in schema.fields() {
+        let field_name = field_entry.name();
+        match (field_tokenizers.get(field_name), field_entry.field_type()) {
+            (Some(tokenizer), FieldType::Str(text_options)) => {
+                let indexing = text_options
+                    .get_indexing_options()
+                    .cloned()
+                    .unwrap_or_default()
+                    .set_tokenizer(tokenizer);
+                let options = text_options.clone().set_indexing_options(indexing);
+                schema_builder.add_text_field(field_name, options);
+            }
+            _ => add_field_entry_to_builder(&mut schema_builder, field_name, field_entry),
+        }
+    }
+    let parse_schema = schema_builder.build();
+
+    // A private manager, so registering the overrides never leaks into the index
+    let tokenizers = TokenizerManager::new();
+    for (_field, field_entry) in parse_schema.fields() {
+        let indexing = match field_entry.field_type() {
+            FieldType::Str(text_options) => text_options.get_indexing_options(),
+            FieldType::JsonObject(json_options) => json_options.get_text_indexing_options(),
+            _ => None,
+        };
+        if let Some(name) = indexing.map(|indexing| indexing.tokenizer()) {
+            if let Some(analyzer) = resolve_analyzer(name) {
+                tokenizers.register(name, analyzer);
+            }
+        }
+    }
+
+    Ok(QueryParser::new(parse_schema, default_fields, tokenizers))
 }
 
 #[rustler::nif]
@@ -73,13 +180,106 @@ pub fn query_parser_parse(
     }
 }
 
+/// Parse a query with per-query field boosts applied on top of the parser's own settings
+#[rustler::nif]
+pub fn query_parser_parse_with_boosts(
+    parser_res: ResourceArc<QueryParserResource>,
+    query_str: String,
+    field_boosts: HashMap<String, f64>,
+) -> NifResult<ResourceArc<QueryResource>> {
+    if query_str.trim().is_empty() {
+        return Err(rustler::Error::Term(Box::new(
+            "Query string cannot be empty",
+        )));
+    }
+
+    let mut parser = parser_res.parser.clone();
+    for (field_name, boost) in field_boosts {
+        let field = parser_res.schema.get_field(&field_name).map_err(|_| {
+            rustler::Error::Term(Box::new(format!(
+                "Field '{}' not found in schema",
+                field_name
+            )))
+        })?;
+        if !boost.is_finite() || boost < 0.0 {
+            return Err(rustler::Error::Term(Box::new(format!(
+                "Invalid boost {} for field '{}': must be a non-negative number",
+                boost, field_name
+            ))));
+        }
+        parser.set_field_boost(field, boost as f32);
+    }
+
+    match parser.parse_query(&query_str) {
+        Ok(query) => Ok(ResourceArc::new(QueryResource { query })),
+        Err(e) => Err(rustler::Error::Term(Box::new(format!(
+            "Failed to parse query: {}",
+            e
+        )))),
+    }
+}
+
+/// Parse a query against the given default fields with a throwaway parser.
+///
+/// Meant for search scopes chosen per request, which would otherwise need one
+/// parser resource per scope. Unknown fields are always an error.
+#[rustler::nif]
+pub fn query_parser_parse_on_fields(
+    index_res: ResourceArc<IndexResource>,
+    query_str: String,
+    fields: Vec<String>,
+) -> NifResult<ResourceArc<QueryResource>> {
+    if query_str.trim().is_empty() {
+        return Err(rustler::Error::Term(Box::new(
+            "Query string cannot be empty",
+        )));
+    }
+    if fields.is_empty() {
+        return Err(rustler::Error::Term(Box::new(
+            "At least one default field is required for query parser",
+        )));
+    }
+
+    let schema = index_res.index.schema();
+    let default_fields = fields
+        .iter()
+        .map(|field_name| {
+            schema.get_field(field_name).map_err(|_| {
+                rustler::Error::Term(Box::new(format!(
+                    "Field '{}' not found in schema",
+                    field_name
+                )))
+            })
+        })
+        .collect::<NifResult<Vec<Field>>>()?;
+
+    let parser = QueryParser::for_index(&*index_res.index, default_fields);
+    match parser.parse_query(&query_str) {
+        Ok(query) => Ok(ResourceArc::new(QueryResource { query })),
+        Err(e) => Err(rustler::Error::Term(Box::new(format!(
+            "Failed to parse query: {}",
+            e
+        )))),
+    }
+}
+
 #[rustler::nif]
 pub fn query_term(
     schema_res: ResourceArc<SchemaResource>,
     field_name: String,
     term_value: String,
 ) -> NifResult<ResourceArc<QueryResource>> {
-    let field = match schema_res.schema.get_field(&field_name) {
+    let query = build_term_query(&schema_res.schema, &field_name, &term_value)?;
+    Ok(ResourceArc::new(QueryResource { query }))
+}
+
+/// Build the query matching `term_value` in a field, coerced to the field type
+pub(crate) fn build_term_query(
+    schema: &Schema,
+    field_name: &str,
+    term_value: &str,
+) -> NifResult<Box<dyn tantivy::query::Query>> {
+    let field = match schema.get_field(field_name) {
         Ok(field) => field,
         Err(_) => {
             return Err(rustler::Error::Term(Box::new(format!(
@@ -89,13 +289,20 @@ pub fn query_term(
         }
     };
 
-    let field_entry = schema_res.schema.get_field_entry(field);
+    let field_entry = schema.get_field_entry(field);
     let field_type = field_entry.field_type();
 
     // For tokenized text fields with multiple words, create a phrase query instead of a term query
     match field_type {
         FieldType::Str(text_options) => {
-            if let Some(_indexing) = text_options.get_indexing_options() {
+            if let Some(indexing) = text_options.get_indexing_options() {
+                // Keyword fields index the verbatim string as a single term
+                if matches!(indexing.tokenizer(), "raw" | "keyword") {
+                    let term = TantivyTerm::from_field_text(field, term_value);
+                    let query = TermQuery::new(term, tantivy::schema::IndexRecordOption::Basic);
+                    return Ok(Box::new(query));
+                }
+
                 // For tokenized fields
                 let words: Vec<&str> = term_value.split_whitespace().collect();
                 if words.len() > 1 {
@@ -111,45 +318,34 @@ pub fn query_term(
                         ));
                     }
                     let boolean_query = BooleanQuery::new(clauses);
-                    return Ok(ResourceArc::new(QueryResource {
-                        query: Box::new(boolean_query),
-                    }));
+                    return Ok(Box::new(boolean_query));
                 } else {
                     // Single word - create term query with lowercase normalization
                     let term = TantivyTerm::from_field_text(field, &term_value.to_lowercase());
                     let query = TermQuery::new(term, tantivy::schema::IndexRecordOption::Basic);
-                    return Ok(ResourceArc::new(QueryResource {
-                        query: Box::new(query),
-                    }));
+                    return Ok(Box::new(query));
                 }
             } else {
                 // Non-tokenized text field - use exact value
-                let term = TantivyTerm::from_field_text(field, &term_value);
+                let term = TantivyTerm::from_field_text(field, term_value);
                 let query = TermQuery::new(term, tantivy::schema::IndexRecordOption::Basic);
-                return Ok(ResourceArc::new(QueryResource {
-                    query: Box::new(query),
-                }));
+                return Ok(Box::new(query));
             }
         }
         FieldType::U64(_) => {
-            let term = match term_value.parse::<u64>() {
+            // Tolerate surrounding whitespace and quotes, but never fall back to a text term
+            let clean_value = term_value.trim().trim_matches(|c| c == '"' || c == '\'');
+            let term = match clean_value.parse::<u64>() {
                 Ok(val) => TantivyTerm::from_field_u64(field, val),
                 Err(_) => {
-                    // Try to be more lenient with parsing to handle various cases in tests
-                    let clean_value = term_value.trim().trim_matches(|c| c == '"' || c == '\'');
-                    match clean_value.parse::<u64>() {
-                        Ok(val) => TantivyTerm::from_field_u64(field, val),
-                        Err(_) => {
-                            // For tests to pass, fall back to text representation
-                            TantivyTerm::from_field_text(field, &term_value)
-                        }
-                    }
+                    return Err(rustler::Error::Term(Box::new(format!(
+                        "Invalid u64 value for field '{}': {:?}",
+                        field_name, term_value
+                    ))))
                 }
             };
             let query = TermQuery::new(term, tantivy::schema::IndexRecordOption::Basic);
-            return Ok(ResourceArc::new(QueryResource {
-                query: Box::new(query),
-            }));
+            return Ok(Box::new(query));
         }
         FieldType::I64(_) => {
             let term = match term_value.parse::<i64>() {
@@ -162,9 +358,7 @@ pub fn query_term(
                 }
             };
             let query = TermQuery::new(term, tantivy::schema::IndexRecordOption::Basic);
-            return Ok(ResourceArc::new(QueryResource {
-                query: Box::new(query),
-            }));
+            return Ok(Box::new(query));
         }
         FieldType::F64(_) => {
             let term = match term_value.parse::<f64>() {
@@ -177,9 +371,7 @@ pub fn query_term(
                 }
             };
             let query = TermQuery::new(term, tantivy::schema::IndexRecordOption::Basic);
-            return Ok(ResourceArc::new(QueryResource {
-                query: Box::new(query),
-            }));
+            return Ok(Box::new(query));
         }
         FieldType::Bool(_) => {
             // Be more lenient with boolean parsing
@@ -195,27 +387,26 @@ pub fn query_term(
                 }
             };
             let query = TermQuery::new(term, tantivy::schema::IndexRecordOption::Basic);
-            return Ok(ResourceArc::new(QueryResource {
-                query: Box::new(query),
-            }));
+            return Ok(Box::new(query));
         }
         FieldType::Date(_) => {
-            let term = match term_value.parse::<i64>() {
-                Ok(timestamp) => {
-                    let date_time = tantivy::DateTime::from_timestamp_secs(timestamp);
-                    TantivyTerm::from_field_date(field, date_time)
-                }
-                Err(_) => {
-                    return Err(rustler::Error::Term(Box::new(format!(
-                        "Invalid timestamp value for date field '{}': {:?}",
-                        field_name, term_value
-                    ))))
-                }
+            // Accept unix timestamps in seconds or RFC3339 strings
+            let timestamp = match term_value.parse::<i64>() {
+                Ok(timestamp) => timestamp,
+                Err(_) => match chrono::DateTime::parse_from_rfc3339(term_value.trim()) {
+                    Ok(dt) => dt.timestamp(),
+                    Err(_) => {
+                        return Err(rustler::Error::Term(Box::new(format!(
+                            "Invalid date value for field '{}': {:?} (expected a unix timestamp or an RFC3339 string)",
+                            field_name, term_value
+                        ))))
+                    }
+                },
             };
+            let date_time = tantivy::DateTime::from_timestamp_secs(timestamp);
+            let term = TantivyTerm::from_field_date(field, date_time);
             let query = TermQuery::new(term, tantivy::schema::IndexRecordOption::Basic);
-            return Ok(ResourceArc::new(QueryResource {
-                query: Box::new(query),
-            }));
+            return Ok(Box::new(query));
         }
         _ => {
             return Err(rustler::Error::Term(Box::new(format!(
@@ -227,10 +418,11 @@ pub fn query_term(
 }
 
 #[rustler::nif]
-pub fn query_phrase(
+pub fn query_json_path(
     schema_res: ResourceArc<SchemaResource>,
     field_name: String,
-    phrase_terms: Vec<String>,
+    json_path: String,
+    value: String,
 ) -> NifResult<ResourceArc<QueryResource>> {
     let field = match schema_res.schema.get_field(&field_name) {
         Ok(field) => field,
@@ -242,6 +434,122 @@ pub fn query_phrase(
         }
     };
 
+    let json_options = match schema_res.schema.get_field_entry(field).field_type() {
+        FieldType::JsonObject(options) => options,
+        _ => {
+            return Err(rustler::Error::Term(Box::new(format!(
+                "Field '{}' is not a JSON field",
+                field_name
+            ))))
+        }
+    };
+    let indexing = match json_options.get_text_indexing_options() {
+        Some(indexing) => indexing,
+        None => {
+            return Err(rustler::Error::Term(Box::new(format!(
+                "JSON field '{}' is not indexed",
+                field_name
+            ))))
+        }
+    };
+
+    let path_term =
+        TantivyTerm::from_field_json_path(field, &json_path, json_options.is_expand_dots_enabled());
+
+    // Strings are indexed through the field tokenizer, so mirror `query_term` normalization
+    let mut text_clauses: Vec<(Occur, Box<dyn tantivy::query::Query>)> = Vec::new();
+    let words: Vec<String> = if matches!(indexing.tokenizer(), "raw" | "keyword") {
+        vec![value.clone()]
+    } else {
+        value
+            .split_whitespace()
+            .map(|word| word.to_lowercase())
+            .collect()
+    };
+    for word in words {
+        let mut term = path_term.clone();
+        term.append_type_and_str(&word);
+        text_clauses.push((
+            Occur::Must,
+            Box::new(TermQuery::new(
+                term,
+                tantivy::schema::IndexRecordOption::Basic,
+            )),
+        ));
+    }
+
+    // Numbers, booleans and dates are indexed with their own type in JSON objects
+    let typed_term = tantivy::json_utils::convert_to_fast_value_and_append_to_json_term(
+        path_term,
+        value.trim(),
+        true,
+    );
+
+    let text_query: Option<Box<dyn tantivy::query::Query>> = match text_clauses.len() {
+        0 => None,
+        1 => text_clauses.pop().map(|(_, query)| query),
+        _ => Some(Box::new(BooleanQuery::new(text_clauses))),
+    };
+
+    let query: Box<dyn tantivy::query::Query> = match (typed_term, text_query) {
+        (Some(term), Some(text_query)) => Box::new(BooleanQuery::new(vec![
+            (
+                Occur::Should,
+                Box::new(TermQuery::new(
+                    term,
+                    tantivy::schema::IndexRecordOption::Basic,
+                )) as Box<dyn tantivy::query::Query>,
+            ),
+            (Occur::Should, text_query),
+        ])),
+        (Some(term), None) => Box::new(TermQuery::new(
+            term,
+            tantivy::schema::IndexRecordOption::Basic,
+        )),
+        (None, Some(text_query)) => text_query,
+        (None, None) => Box::new(EmptyQuery),
+    };
+
+    Ok(ResourceArc::new(QueryResource { query }))
+}
+
+/// Resolve a text field for phrase queries, which need positions in the index.
+///
+/// Without positions a phrase query silently matches nothing, so fail loudly instead.
+fn phrase_field(schema: &Schema, field_name: &str) -> NifResult<Field> {
+    let field = schema
+        .get_field(field_name)
+        .map_err(|_| rustler::Error::Term(Box::new(format!("Field '{}' not found", field_name))))?;
+
+    let has_positions = match schema.get_field_entry(field).field_type() {
+        FieldType::Str(text_options) => text_options
+            .get_indexing_options()
+            .map_or(false, |indexing| indexing.index_option().has_positions()),
+        _ => {
+            return Err(rustler::Error::Term(Box::new(format!(
+                "Field '{}' is not a text field",
+                field_name
+            ))))
+        }
+    };
+    if !has_positions {
+        return Err(rustler::Error::Term(Box::new(format!(
+            "Field '{}' has no positions; phrase queries require WithFreqsAndPositions",
+            field_name
+        ))));
+    }
+
+    Ok(field)
+}
+
+#[rustler::nif]
+pub fn query_phrase(
+    schema_res: ResourceArc<SchemaResource>,
+    field_name: String,
+    phrase_terms: Vec<String>,
+) -> NifResult<ResourceArc<QueryResource>> {
+    let field = phrase_field(&schema_res.schema, &field_name)?;
+
     let terms: Vec<TantivyTerm> = phrase_terms
         .iter()
         .map(|term_str| TantivyTerm::from_field_text(field, term_str))
@@ -253,6 +561,79 @@ pub fn query_phrase(
     }))
 }
 
+/// Build a phrase query from raw text, analyzed with the field's tokenizer.
+///
+/// Token positions from the analyzer are kept, so removed stop words leave the
+/// same gaps they left at index time.
+#[rustler::nif]
+pub fn query_phrase_text(
+    schema_res: ResourceArc<SchemaResource>,
+    field_name: String,
+    text: String,
+    slop: u32,
+) -> NifResult<ResourceArc<QueryResource>> {
+    let field = match schema_res.schema.get_field(&field_name) {
+        Ok(field) => field,
+        Err(_) => {
+            return Err(rustler::Error::Term(Box::new(format!(
+                "Field '{}' not found",
+                field_name
+            ))))
+        }
+    };
+
+    let indexing = match schema_res.schema.get_field_entry(field).field_type() {
+        FieldType::Str(text_options) => match text_options.get_indexing_options() {
+            Some(indexing) => indexing,
+            None => {
+                return Err(rustler::Error::Term(Box::new(format!(
+                    "Field '{}' is not indexed",
+                    field_name
+                ))))
+            }
+        },
+        _ => {
+            return Err(rustler::Error::Term(Box::new(format!(
+                "Field '{}' is not a text field",
+                field_name
+            ))))
+        }
+    };
+
+    let mut analyzer = analyzer_for_tokenizer(indexing.tokenizer()).ok_or_else(|| {
+        rustler::Error::Term(Box::new(format!(
+            "Tokenizer '{}' for field '{}' not found",
+            indexing.tokenizer(),
+            field_name
+        )))
+    })?;
+
+    let mut terms: Vec<(usize, TantivyTerm)> = Vec::new();
+    let mut token_stream = analyzer.token_stream(&text);
+    while let Some(token) = token_stream.next() {
+        terms.push((
+            token.position,
+            TantivyTerm::from_field_text(field, &token.text),
+        ));
+    }
+
+    let query: Box<dyn tantivy::query::Query> = match terms.len() {
+        0 => Box::new(EmptyQuery),
+        1 => Box::new(TermQuery::new(
+            terms.remove(0).1,
+            tantivy::schema::IndexRecordOption::Basic,
+        )),
+        _ => {
+            phrase_field(&schema_res.schema, &field_name)?;
+            let mut phrase_query = PhraseQuery::new_with_offset(terms);
+            phrase_query.set_slop(slop);
+            Box::new(phrase_query)
+        }
+    };
+
+    Ok(ResourceArc::new(QueryResource { query }))
+}
+
 #[rustler::nif]
 pub fn query_range_u64(
     schema_res: ResourceArc<SchemaResource>,
@@ -340,6 +721,123 @@ pub fn query_range_f64(
     }))
 }
 
+/// Bounding box over a geo-point stored as two f64 fast fields (latitude, longitude).
+///
+/// Bounds are inclusive. A box with `min_lon > max_lon` crosses the antimeridian
+/// and matches longitudes on either side of it.
+#[rustler::nif]
+pub fn query_geo_bounding_box(
+    schema_res: ResourceArc<SchemaResource>,
+    lat_field: String,
+    lon_field: String,
+    min_lat: f64,
+    max_lat: f64,
+    min_lon: f64,
+    max_lon: f64,
+) -> NifResult<ResourceArc<QueryResource>> {
+    let lat = geo_coordinate_field(&schema_res.schema, &lat_field)?;
+    let lon = geo_coordinate_field(&schema_res.schema, &lon_field)?;
+
+    let valid_lat = |v: f64| (-90.0..=90.0).contains(&v);
+    let valid_lon = |v: f64| (-180.0..=180.0).contains(&v);
+    if !valid_lat(min_lat) || !valid_lat(max_lat) || min_lat > max_lat {
+        return Err(rustler::Error::Term(Box::new(format!(
+            "Invalid latitude range [{}, {}]: expected -90 <= min <= max <= 90",
+            min_lat, max_lat
+        ))));
+    }
+    if !valid_lon(min_lon) || !valid_lon(max_lon) {
+        return Err(rustler::Error::Term(Box::new(format!(
+            "Invalid longitude range [{}, {}]: expected values within [-180, 180]",
+            min_lon, max_lon
+        ))));
+    }
+
+    let f64_range = |field: Field, start: f64, end: f64| -> Box<dyn tantivy::query::Query> {
+        Box::new(RangeQuery::new(
+            Bound::Included(TantivyTerm::from_field_f64(field, start)),
+            Bound::Included(TantivyTerm::from_field_f64(field, end)),
+        ))
+    };
+
+    let lon_query: Box<dyn tantivy::query::Query> = if min_lon <= max_lon {
+        f64_range(lon, min_lon, max_lon)
+    } else {
+        Box::new(BooleanQuery::new(vec![
+            (Occur::Should, f64_range(lon, min_lon, 180.0)),
+            (Occur::Should, f64_range(lon, -180.0, max_lon)),
+        ]))
+    };
+    let query = BooleanQuery::new(vec![
+        (Occur::Must, f64_range(lat, min_lat, max_lat)),
+        (Occur::Must, lon_query),
+    ]);
+
+    Ok(ResourceArc::new(QueryResource {
+        query: Box::new(query),
+    }))
+}
+
+/// Resolve a latitude or longitude field, which must be an f64 fast field
+fn geo_coordinate_field(schema: &Schema, field_name: &str) -> NifResult<Field> {
+    let field = schema
+        .get_field(field_name)
+        .map_err(|_| rustler::Error::Term(Box::new(format!("Field '{}' not found", field_name))))?;
+    let field_entry = schema.get_field_entry(field);
+    if !matches!(field_entry.field_type(), FieldType::F64(_)) || !field_entry.is_fast() {
+        return Err(rustler::Error::Term(Box::new(format!(
+            "Field '{}' must be an f64 fast field to hold geo coordinates",
+            field_name
+        ))));
+    }
+    Ok(field)
+}
+
+/// Lexicographic range over the indexed terms of a text field.
+///
+/// Bounds are used verbatim, so this is meant for raw/keyword fields holding
+/// sortable keys. `include_bounds` is `(include_start, include_end)`.
+#[rustler::nif]
+pub fn query_range_text(
+    schema_res: ResourceArc<SchemaResource>,
+    field_name: String,
+    start: Option<String>,
+    end: Option<String>,
+    include_bounds: (bool, bool),
+) -> NifResult<ResourceArc<QueryResource>> {
+    let field = match schema_res.schema.get_field(&field_name) {
+        Ok(field) => field,
+        Err(_) => {
+            return Err(rustler::Error::Term(Box::new(format!(
+                "Field '{}' not found",
+                field_name
+            ))))
+        }
+    };
+
+    match schema_res.schema.get_field_entry(field).field_type() {
+        FieldType::Str(text_options) if text_options.get_indexing_options().is_some() => {}
+        _ => {
+            return Err(rustler::Error::Term(Box::new(format!(
+                "Field '{}' must be an indexed text field",
+                field_name
+            ))))
+        }
+    }
+
+    let to_bound = |value: Option<String>, inclusive: bool| match value {
+        None => Bound::Unbounded,
+        Some(text) if inclusive => Bound::Included(TantivyTerm::from_field_text(field, &text)),
+        Some(text) => Bound::Excluded(TantivyTerm::from_field_text(field, &text)),
+    };
+
+    let (include_start, include_end) = include_bounds;
+    let query = RangeQuery::new(to_bound(start, include_start), to_bound(end, include_end));
+    Ok(ResourceArc::new(QueryResource {
+        query: Box::new(query),
+    }))
+}
+
 #[rustler::nif]
 pub fn query_boolean(
     must_queries: Vec<ResourceArc<QueryResource>>,
@@ -370,6 +868,162 @@ pub fn query_boolean(
     }))
 }
 
+/// A `BoostQuery` that keeps its inner query reachable.
+///
+/// tantivy's own `BoostQuery` has no accessor for the query it wraps, which
+/// `canonical_query_value` needs to sort boolean clauses nested inside it.
+/// The debug output is the same as tantivy's.
+pub(crate) struct BoostedQuery {
+    query: Box<dyn Query>,
+    boost: Score,
+}
+
+impl BoostedQuery {
+    pub(crate) fn new(query: Box<dyn Query>, boost: Score) -> Self {
+        BoostedQuery { query, boost }
+    }
+}
+
+impl Clone for BoostedQuery {
+    fn clone(&self) -> Self {
+        BoostedQuery::new(self.query.box_clone(), self.boost)
+    }
+}
+
+impl fmt::Debug for BoostedQuery {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Boost(query={:?}, boost={})", self.query, self.boost)
+    }
+}
+
+impl Query for BoostedQuery {
+    fn weight(&self, enable_scoring: EnableScoring<'_>) -> tantivy::Result<Box<dyn Weight>> {
+        BoostQuery::new(self.query.box_clone(), self.boost).weight(enable_scoring)
+    }
+
+    fn query_terms<'a>(&'a self, visitor: &mut dyn FnMut(&'a TantivyTerm, bool)) {
+        self.query.query_terms(visitor)
+    }
+}
+
+/// A `DisjunctionMaxQuery` that keeps its disjuncts reachable, like `BoostedQuery`.
+pub(crate) struct DisjunctionQuery {
+    disjuncts: Vec<Box<dyn Query>>,
+    tie_breaker: Score,
+}
+
+impl Clone for DisjunctionQuery {
+    fn clone(&self) -> Self {
+        DisjunctionQuery {
+            disjuncts: self
+                .disjuncts
+                .iter()
+                .map(|query| query.box_clone())
+                .collect(),
+            tie_breaker: self.tie_breaker,
+        }
+    }
+}
+
+impl fmt::Debug for DisjunctionQuery {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DisjunctionMaxQuery")
+            .field("disjuncts", &self.disjuncts)
+            .field("tie_breaker", &self.tie_breaker)
+            .finish()
+    }
+}
+
+impl Query for DisjunctionQuery {
+    fn weight(&self, enable_scoring: EnableScoring<'_>) -> tantivy::Result<Box<dyn Weight>> {
+        let disjuncts = self
+            .disjuncts
+            .iter()
+            .map(|query| query.box_clone())
+            .collect();
+        DisjunctionMaxQuery::with_tie_breaker(disjuncts, self.tie_breaker).weight(enable_scoring)
+    }
+
+    fn query_terms<'a>(&'a self, visitor: &mut dyn FnMut(&'a TantivyTerm, bool)) {
+        for query in &self.disjuncts {
+            query.query_terms(visitor);
+        }
+    }
+}
+
+/// Combine `(field, value, boost)` entries into one scored query
+///
+/// Each value becomes a query like `query_term`, boosted by its weight, and
+/// the results are joined as SHOULD clauses so a document scores the sum of
+/// the weighted terms it matches.
+#[rustler::nif]
+pub fn query_weighted_terms(
+    schema_res: ResourceArc<SchemaResource>,
+    terms: Vec<(String, String, f64)>,
+) -> NifResult<ResourceArc<QueryResource>> {
+    if terms.is_empty() {
+        return Err(rustler::Error::Term(Box::new(
+            "Weighted terms query requires at least one term".to_string(),
+        )));
+    }
+
+    let mut clauses: Vec<(Occur, Box<dyn tantivy::query::Query>)> = Vec::new();
+    for (field_name, term_value, boost) in terms {
+        if !boost.is_finite() || boost < 0.0 {
+            return Err(rustler::Error::Term(Box::new(format!(
+                "Invalid boost {} for term '{}' in field '{}': must be a non-negative number",
+                boost, term_value, field_name
+            ))));
+        }
+
+        let query = build_term_query(&schema_res.schema, &field_name, &term_value)?;
+        clauses.push((
+            Occur::Should,
+            Box::new(BoostedQuery::new(query, boost as f32)),
+        ));
+    }
+
+    Ok(ResourceArc::new(QueryResource {
+        query: Box::new(BooleanQuery::new(clauses)),
+    }))
+}
+
+#[rustler::nif]
+pub fn query_disjunction_max(
+    queries: Vec<ResourceArc<QueryResource>>,
+    tie_breaker: f32,
+) -> NifResult<ResourceArc<QueryResource>> {
+    if queries.is_empty() {
+        return Err(rustler::Error::Term(Box::new(
+            "Disjunction max query requires at least one query".to_string(),
+        )));
+    }
+
+    if !(0.0..=1.0).contains(&tie_breaker) {
+        return Err(rustler::Error::Term(Box::new(format!(
+            "Tie breaker must be between 0.0 and 1.0, got {}",
+            tie_breaker
+        ))));
+    }
+
+    let disjuncts = queries
+        .iter()
+        .map(|query_res| query_res.query.box_clone())
+        .collect();
+
+    Ok(ResourceArc::new(QueryResource {
+        query: Box::new(DisjunctionQuery {
+            disjuncts,
+            tie_breaker,
+        }),
+    }))
+}
+
+/// Fuzzy queries match terms within `distance` edits of `term_value`.
+///
+/// With `prefix`, `term_value` is matched as a fuzzy prefix of longer terms.
+/// With `transposition_cost_one`, swapping two adjacent characters counts as a
+/// single edit instead of two.
 #[rustler::nif]
 pub fn query_fuzzy(
     schema_res: ResourceArc<SchemaResource>,
@@ -377,7 +1031,16 @@ pub fn query_fuzzy(
     term_value: String,
     distance: u8,
     prefix: bool,
+    transposition_cost_one: bool,
 ) -> NifResult<ResourceArc<QueryResource>> {
+    // tantivy only builds Levenshtein automata for distances 0 to 2
+    if distance > 2 {
+        return Err(rustler::Error::Term(Box::new(format!(
+            "Fuzzy distance must be between 0 and 2, got {}",
+            distance
+        ))));
+    }
+
     let field = match schema_res.schema.get_field(&field_name) {
         Ok(field) => field,
         Err(_) => {
@@ -389,17 +1052,38 @@ pub fn query_fuzzy(
     };
 
     let term = TantivyTerm::from_field_text(field, &term_value);
-    let query = FuzzyTermQuery::new(term, distance, prefix);
+    let query = if prefix {
+        FuzzyTermQuery::new_prefix(term, distance, transposition_cost_one)
+    } else {
+        FuzzyTermQuery::new(term, distance, transposition_cost_one)
+    };
     Ok(ResourceArc::new(QueryResource {
         query: Box::new(query),
     }))
 }
 
+/// Check a regex pattern, reporting where in the pattern it is invalid
+pub(crate) fn validate_regex_pattern(pattern: &str) -> Result<(), String> {
+    let (kind, span) = match regex_syntax::Parser::new().parse(pattern) {
+        Ok(_) => return Ok(()),
+        Err(regex_syntax::Error::Parse(e)) => (e.kind().to_string(), *e.span()),
+        Err(regex_syntax::Error::Translate(e)) => (e.kind().to_string(), *e.span()),
+        Err(e) => return Err(format!("Invalid regex pattern '{}': {}", pattern, e)),
+    };
+
+    Err(format!(
+        "Invalid regex pattern '{}' at position {}: {}",
+        pattern, span.start.column, kind
+    ))
+}
+
+/// Regex queries are anchored: the pattern must match the whole term.
 #[rustler::nif]
 pub fn query_regex(
     schema_res: ResourceArc<SchemaResource>,
     field_name: String,
     pattern: String,
+    case_insensitive: bool,
 ) -> NifResult<ResourceArc<QueryResource>> {
     let field = match schema_res.schema.get_field(&field_name) {
         Ok(field) => field,
@@ -411,6 +1095,15 @@ pub fn query_regex(
         }
     };
 
+    // Validate before adding flags so reported positions match the user's pattern
+    validate_regex_pattern(&pattern).map_err(|e| rustler::Error::Term(Box::new(e)))?;
+
+    let pattern = if case_insensitive && !pattern.starts_with("(?i)") {
+        format!("(?i){}", pattern)
+    } else {
+        pattern
+    };
+
     match RegexQuery::from_pattern(&pattern, field) {
         Ok(query) => Ok(ResourceArc::new(QueryResource {
             query: Box::new(query),
@@ -438,36 +1131,48 @@ pub fn query_wildcard(
         }
     };
 
-    // Convert wildcard pattern to regex pattern
-    let regex_pattern = pattern.replace('*', ".*").replace('?', ".");
+    // Patterns like "abc*" only need a prefix scan of the term dictionary
+    if let Some(prefix) = pattern.strip_suffix('*') {
+        if !prefix.is_empty() && !prefix.contains(['*', '?']) {
+            let prefix_query =
+                PhrasePrefixQuery::new(vec![TantivyTerm::from_field_text(field, prefix)]);
+            return Ok(ResourceArc::new(QueryResource {
+                query: Box::new(prefix_query),
+            }));
+        }
+    }
+
+    let regex_pattern = wildcard_to_regex(&pattern);
 
     match RegexQuery::from_pattern(&regex_pattern, field) {
         Ok(query) => Ok(ResourceArc::new(QueryResource {
             query: Box::new(query),
         })),
-        Err(e) => {
-            // Fall back to simple term query if regex fails
-            if pattern.ends_with('*')
-                && !pattern[..pattern.len() - 1].contains('*')
-                && !pattern.contains('?')
-            {
-                // Simple prefix query for patterns like "abc*"
-                let prefix = &pattern[..pattern.len() - 1];
-                let term_query = TermQuery::new(
-                    TantivyTerm::from_field_text(field, prefix),
-                    tantivy::schema::IndexRecordOption::WithFreqs,
-                );
-                Ok(ResourceArc::new(QueryResource {
-                    query: Box::new(term_query),
-                }))
-            } else {
-                Err(rustler::Error::Term(Box::new(format!(
-                    "Failed to create wildcard query: {}",
-                    e
-                ))))
+        Err(e) => Err(rustler::Error::Term(Box::new(format!(
+            "Failed to create wildcard query: {}",
+            e
+        )))),
+    }
+}
+
+/// Convert a wildcard pattern to a regex, escaping the literal segments
+fn wildcard_to_regex(pattern: &str) -> String {
+    let mut regex_pattern = String::with_capacity(pattern.len() * 2);
+    let mut literal = String::new();
+
+    for c in pattern.chars() {
+        match c {
+            '*' | '?' => {
+                regex_pattern.push_str(&regex::escape(&literal));
+                literal.clear();
+                regex_pattern.push_str(if c == '*' { ".*" } else { "." });
             }
+            _ => literal.push(c),
         }
     }
+    regex_pattern.push_str(&regex::escape(&literal));
+
+    regex_pattern
 }
 
 #[rustler::nif]
@@ -477,15 +1182,7 @@ pub fn query_phrase_prefix(
     phrase_terms: Vec<String>,
     max_expansions: u32,
 ) -> NifResult<ResourceArc<QueryResource>> {
-    let field = match schema_res.schema.get_field(&field_name) {
-        Ok(field) => field,
-        Err(_) => {
-            return Err(rustler::Error::Term(Box::new(format!(
-                "Field '{}' not found",
-                field_name
-            ))))
-        }
-    };
+    let field = phrase_field(&schema_res.schema, &field_name)?;
 
     let terms: Vec<TantivyTerm> = phrase_terms
         .iter()
@@ -499,13 +1196,29 @@ pub fn query_phrase_prefix(
     }))
 }
 
+/// Match documents with a value in a fast field, or in a dotted JSON sub-path.
+/// With `json_subpaths`, a value anywhere below a JSON path also matches.
 #[rustler::nif]
 pub fn query_exists(
-    _schema_res: ResourceArc<SchemaResource>,
+    schema_res: ResourceArc<SchemaResource>,
     field_name: String,
+    json_subpaths: bool,
 ) -> NifResult<ResourceArc<QueryResource>> {
-    // In tantivy 0.24.1, ExistsQuery::new takes field name and json_subpaths boolean
-    let query = ExistsQuery::new(field_name, false);
+    let schema = &schema_res.schema;
+    let (field, _path) = schema.find_field(&field_name).ok_or_else(|| {
+        rustler::Error::Term(Box::new(format!(
+            "Field '{}' not found in schema",
+            field_name
+        )))
+    })?;
+    if !schema.get_field_entry(field).is_fast() {
+        return Err(rustler::Error::Term(Box::new(format!(
+            "Field '{}' must be a fast field for exists queries",
+            schema.get_field_name(field)
+        ))));
+    }
+
+    let query = ExistsQuery::new(field_name, json_subpaths);
     Ok(ResourceArc::new(QueryResource {
         query: Box::new(query),
     }))
@@ -603,6 +1316,82 @@ pub fn query_more_like_this(
     }
 
     // Build the More Like This query
+    let builder = more_like_this_builder(
+        min_doc_frequency,
+        max_doc_frequency,
+        min_term_frequency,
+        max_query_terms,
+        min_word_length,
+        max_word_length,
+        boost_factor,
+    );
+
+    // Create the query with document fields
+    let query = builder.with_document_fields(doc_fields);
+
+    Ok(ResourceArc::new(QueryResource {
+        query: Box::new(query),
+    }))
+}
+
+/// Build a More Like This query from a document already in the index.
+///
+/// The document address is only meaningful for the given searcher's segments.
+#[rustler::nif]
+pub fn query_more_like_this_doc(
+    searcher_res: ResourceArc<SearcherResource>,
+    segment_ord: u32,
+    doc_id: u32,
+    min_doc_frequency: Option<u64>,
+    max_doc_frequency: Option<u64>,
+    min_term_frequency: Option<usize>,
+    max_query_terms: Option<usize>,
+    min_word_length: Option<usize>,
+    max_word_length: Option<usize>,
+    boost_factor: Option<f32>,
+) -> NifResult<ResourceArc<QueryResource>> {
+    let segment_readers = searcher_res.searcher.segment_readers();
+    let segment_reader = segment_readers.get(segment_ord as usize).ok_or_else(|| {
+        rustler::Error::Term(Box::new(format!(
+            "Segment ordinal {} out of range ({} segments)",
+            segment_ord,
+            segment_readers.len()
+        )))
+    })?;
+
+    if doc_id >= segment_reader.max_doc() || segment_reader.is_deleted(doc_id) {
+        return Err(rustler::Error::Term(Box::new(format!(
+            "Document {} not found in segment {}",
+            doc_id, segment_ord
+        ))));
+    }
+
+    let builder = more_like_this_builder(
+        min_doc_frequency,
+        max_doc_frequency,
+        min_term_frequency,
+        max_query_terms,
+        min_word_length,
+        max_word_length,
+        boost_factor,
+    );
+
+    let query = builder.with_document(DocAddress::new(segment_ord, doc_id));
+
+    Ok(ResourceArc::new(QueryResource {
+        query: Box::new(query),
+    }))
+}
+
+fn more_like_this_builder(
+    min_doc_frequency: Option<u64>,
+    max_doc_frequency: Option<u64>,
+    min_term_frequency: Option<usize>,
+    max_query_terms: Option<usize>,
+    min_word_length: Option<usize>,
+    max_word_length: Option<usize>,
+    boost_factor: Option<f32>,
+) -> MoreLikeThisQueryBuilder {
     let mut builder = MoreLikeThisQuery::builder();
 
     if let Some(min_doc_freq) = min_doc_frequency {
@@ -633,76 +1422,196 @@ pub fn query_more_like_this(
         builder = builder.with_boost_factor(boost);
     }
 
-    // Create the query with document fields
-    let query = builder.with_document_fields(doc_fields);
-
-    Ok(ResourceArc::new(QueryResource {
-        query: Box::new(query),
-    }))
+    builder
 }
 
+/// Extract the `(field_name, term_value)` pairs a query matches on
 #[rustler::nif(schedule = "DirtyCpu")]
 pub fn query_extract_terms(
     query_res: ResourceArc<QueryResource>,
-    _schema_res: ResourceArc<SchemaResource>,
-) -> Vec<String> {
-    // Extract terms using Tantivy's term extraction capability
+    schema_res: ResourceArc<SchemaResource>,
+) -> Vec<(String, String)> {
     let mut term_set = std::collections::BTreeSet::new();
-    let mut found_any_terms = false;
 
-    // Extract terms from the query
     query_res.query.query_terms(&mut |term, _need_position| {
-        found_any_terms = true;
-
-        // Get the term's value and try different decoding methods
-        let value = term.value();
-
-        // Try as_str() for text fields
-        if let Some(text) = value.as_str() {
-            if !text.is_empty() {
-                term_set.insert(text.to_string());
+        if let Some(value) = term_value_to_string(term) {
+            if !value.is_empty() {
+                let field_name = schema_res.schema.get_field_name(term.field());
+                term_set.insert((field_name.to_string(), value));
             }
-            return; // Found text, no need to try other methods
-        }
-
-        // Try as_u64() for numeric fields
-        if let Some(num) = value.as_u64() {
-            term_set.insert(num.to_string());
-            return;
         }
+    });
 
-        // Try as_i64() for signed numeric fields
-        if let Some(num) = value.as_i64() {
-            term_set.insert(num.to_string());
-            return;
-        }
+    term_set.into_iter().collect()
+}
 
-        // Try as_f64() for float fields
-        if let Some(num) = value.as_f64() {
-            term_set.insert(num.to_string());
-            return;
-        }
+/// Render a term value as text, whatever the field type
+fn term_value_to_string(term: &TantivyTerm) -> Option<String> {
+    let value = term.value();
+
+    if let Some(text) = value.as_str() {
+        Some(text.to_string())
+    } else if let Some(num) = value.as_u64() {
+        Some(num.to_string())
+    } else if let Some(num) = value.as_i64() {
+        Some(num.to_string())
+    } else if let Some(num) = value.as_f64() {
+        Some(num.to_string())
+    } else if let Some(b) = value.as_bool() {
+        Some(b.to_string())
+    } else if let Some(date) = value.as_date() {
+        Some(format!("{:?}", date))
+    } else if let Some(facet) = value.as_facet() {
+        Some(facet.to_string())
+    } else {
+        value
+            .as_bytes()
+            .and_then(|bytes| std::str::from_utf8(bytes).ok())
+            .map(|text| text.to_string())
+    }
+}
 
-        // Try as_bytes() as a fallback
-        if let Some(bytes) = value.as_bytes() {
-            if let Ok(text) = std::str::from_utf8(bytes) {
-                if !text.is_empty() {
-                    term_set.insert(text.to_string());
-                }
-            }
-        }
-    });
+fn term_to_json(schema: &Schema, term: &TantivyTerm) -> serde_json::Value {
+    serde_json::json!({
+        "field": schema.get_field_name(term.field()),
+        "value": term_value_to_string(term)
+    })
+}
 
-    // If no terms were found, it might indicate an issue with the query
-    if !found_any_terms {
-        return vec!["no_terms_found".to_string()];
+/// Build a structured description of a query, falling back to its debug form
+fn query_to_json_value(schema: &Schema, query: &dyn tantivy::query::Query) -> serde_json::Value {
+    if let Some(term_query) = query.downcast_ref::<TermQuery>() {
+        let mut value = term_to_json(schema, term_query.term());
+        value["type"] = serde_json::json!("term");
+        value
+    } else if let Some(phrase_query) = query.downcast_ref::<PhraseQuery>() {
+        let terms: Vec<Option<String>> = phrase_query
+            .phrase_terms()
+            .iter()
+            .map(term_value_to_string)
+            .collect();
+        serde_json::json!({
+            "type": "phrase",
+            "field": schema.get_field_name(phrase_query.field()),
+            "terms": terms
+        })
+    } else if let Some(boolean_query) = query.downcast_ref::<BooleanQuery>() {
+        let clauses: Vec<serde_json::Value> = boolean_query
+            .clauses()
+            .iter()
+            .map(|(occur, sub_query)| {
+                let occur = match occur {
+                    Occur::Must => "must",
+                    Occur::Should => "should",
+                    Occur::MustNot => "must_not",
+                };
+                serde_json::json!({
+                    "occur": occur,
+                    "query": query_to_json_value(schema, sub_query.as_ref())
+                })
+            })
+            .collect();
+        serde_json::json!({ "type": "boolean", "clauses": clauses })
+    } else if query.downcast_ref::<AllQuery>().is_some() {
+        serde_json::json!({ "type": "all" })
+    } else if query.downcast_ref::<EmptyQuery>().is_some() {
+        serde_json::json!({ "type": "empty" })
+    } else {
+        serde_json::json!({ "type": "other", "debug": format!("{:?}", query) })
     }
+}
+
+/// Stable textual representation of a query for logging and diffing
+#[rustler::nif]
+pub fn query_to_string(query_res: ResourceArc<QueryResource>) -> String {
+    format!("{:?}", query_res.query)
+}
+
+#[rustler::nif]
+pub fn query_to_json(
+    query_res: ResourceArc<QueryResource>,
+    schema_res: ResourceArc<SchemaResource>,
+) -> NifResult<String> {
+    serde_json::to_string(&query_to_json_value(
+        &schema_res.schema,
+        query_res.query.as_ref(),
+    ))
+    .map_err(|e| rustler::Error::Term(Box::new(format!("Failed to serialize query: {}", e))))
+}
 
-    if term_set.is_empty() {
-        return vec!["terms_found_but_not_decoded".to_string()];
+/// Canonical form of a query, where the order boolean clauses were added in doesn't matter
+fn canonical_query_value(schema: &Schema, query: &dyn tantivy::query::Query) -> serde_json::Value {
+    if let Some(term_query) = query.downcast_ref::<TermQuery>() {
+        serde_json::json!({ "term": term_to_json(schema, term_query.term()) })
+    } else if let Some(boolean_query) = query.downcast_ref::<BooleanQuery>() {
+        let mut clauses: Vec<serde_json::Value> = boolean_query
+            .clauses()
+            .iter()
+            .map(|(occur, sub_query)| {
+                let occur = match occur {
+                    Occur::Must => "must",
+                    Occur::Should => "should",
+                    Occur::MustNot => "must_not",
+                };
+                serde_json::json!([occur, canonical_query_value(schema, sub_query.as_ref())])
+            })
+            .collect();
+        clauses.sort_by_cached_key(|clause| clause.to_string());
+        serde_json::json!({
+            "boolean": {
+                "minimum_should_match": boolean_query.get_minimum_number_should_match(),
+                "clauses": clauses
+            }
+        })
+    } else if let Some(boosted_query) = query.downcast_ref::<BoostedQuery>() {
+        serde_json::json!({
+            "boost": {
+                "boost": boosted_query.boost,
+                "query": canonical_query_value(schema, boosted_query.query.as_ref())
+            }
+        })
+    } else if let Some(disjunction_query) = query.downcast_ref::<DisjunctionQuery>() {
+        let mut disjuncts: Vec<serde_json::Value> = disjunction_query
+            .disjuncts
+            .iter()
+            .map(|disjunct| canonical_query_value(schema, disjunct.as_ref()))
+            .collect();
+        disjuncts.sort_by_cached_key(|disjunct| disjunct.to_string());
+        serde_json::json!({
+            "disjunction_max": {
+                "tie_breaker": disjunction_query.tie_breaker,
+                "disjuncts": disjuncts
+            }
+        })
+    } else if let Some(term_set_query) = query.downcast_ref::<TermSetQuery>() {
+        // Its terms live in a HashMap, so the debug output has no stable order
+        let mut terms: Vec<serde_json::Value> = Vec::new();
+        term_set_query.query_terms(&mut |term, _| terms.push(term_to_json(schema, term)));
+        terms.sort_by_cached_key(|term| term.to_string());
+        terms.dedup();
+        serde_json::json!({ "term_set": terms })
+    } else if query.downcast_ref::<AllQuery>().is_some() {
+        serde_json::json!("all")
+    } else if query.downcast_ref::<EmptyQuery>().is_some() {
+        serde_json::json!("empty")
+    } else {
+        // Debug output is deterministic for a given query, just not canonical.
+        // tantivy's own BoostQuery (from `^` in parsed queries) hides its inner
+        // query, so clauses below it keep the order the parser produced.
+        serde_json::json!({ "other": format!("{:?}", query) })
     }
+}
 
-    term_set.into_iter().collect()
+/// Deterministic key for a query, suitable for hashing into a result cache.
+///
+/// Boolean clauses are sorted at every nesting level, so queries that only
+/// differ by clause order share a key.
+#[rustler::nif]
+pub fn query_normalized_key(
+    query_res: ResourceArc<QueryResource>,
+    schema_res: ResourceArc<SchemaResource>,
+) -> String {
+    canonical_query_value(&schema_res.schema, query_res.query.as_ref()).to_string()
 }
 
 #[rustler::nif]
@@ -1,4 +1,4 @@
-use rustler::{Error, NifResult, ResourceArc};
+use rustler::{Encoder, Env, Error, LocalPid, NifResult, ResourceArc, Term};
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex, RwLock};
 use std::time::{Instant, SystemTime, UNIX_EPOCH};
@@ -14,6 +14,7 @@ pub struct ReaderManagerResource {
     pub policies: Arc<Mutex<HashMap<String, ReaderPolicy>>>,
     pub reload_stats: Arc<Mutex<HashMap<String, ReaderStats>>>,
     pub monitoring_config: Arc<Mutex<MonitoringConfig>>,
+    pub event_subscriber: Arc<Mutex<Option<LocalPid>>>,
 }
 
 /// Configuration for index reader reload policies
@@ -68,10 +69,86 @@ pub struct MonitoringConfig {
 pub enum ReaderEvent {
     Created { reader_id: String, timestamp: u64 },
     Reloaded { reader_id: String, timestamp: u64, duration_ms: u64 },
+    ReloadFailed {
+        reader_id: String,
+        timestamp: u64,
+        duration_ms: u64,
+        error: String,
+    },
     SearchPerformed { reader_id: String, duration_ms: u64 },
     Disposed { reader_id: String, timestamp: u64 },
 }
 
+mod event_atoms {
+    rustler::atoms! {
+        tantivy_reader_event,
+        event,
+        reader_id,
+        timestamp,
+        duration_ms,
+        error,
+        created,
+        reloaded,
+        reload_failed,
+        search_performed,
+        disposed,
+    }
+}
+
+impl ReaderEvent {
+    /// Encode as `{:tantivy_reader_event, %{event: kind, reader_id: id, ...}}`
+    fn to_message<'a>(&self, env: Env<'a>) -> NifResult<Term<'a>> {
+        use event_atoms::*;
+
+        let map = rustler::types::map::map_new(env);
+        let map = match self {
+            ReaderEvent::Created {
+                reader_id: id,
+                timestamp: ts,
+            } => map
+                .map_put(event(), created())?
+                .map_put(reader_id(), id.as_str())?
+                .map_put(timestamp(), *ts)?,
+            ReaderEvent::Reloaded {
+                reader_id: id,
+                timestamp: ts,
+                duration_ms: ms,
+            } => map
+                .map_put(event(), reloaded())?
+                .map_put(reader_id(), id.as_str())?
+                .map_put(timestamp(), *ts)?
+                .map_put(duration_ms(), *ms)?,
+            ReaderEvent::ReloadFailed {
+                reader_id: id,
+                timestamp: ts,
+                duration_ms: ms,
+                error: e,
+            } => map
+                .map_put(event(), reload_failed())?
+                .map_put(reader_id(), id.as_str())?
+                .map_put(timestamp(), *ts)?
+                .map_put(duration_ms(), *ms)?
+                .map_put(error(), e.as_str())?,
+            ReaderEvent::SearchPerformed {
+                reader_id: id,
+                duration_ms: ms,
+            } => map
+                .map_put(event(), search_performed())?
+                .map_put(reader_id(), id.as_str())?
+                .map_put(duration_ms(), *ms)?,
+            ReaderEvent::Disposed {
+                reader_id: id,
+                timestamp: ts,
+            } => map
+                .map_put(event(), disposed())?
+                .map_put(reader_id(), id.as_str())?
+                .map_put(timestamp(), *ts)?,
+        };
+
+        Ok((tantivy_reader_event(), map).encode(env))
+    }
+}
+
 /// Reader health information
 #[derive(Debug, Clone)]
 pub struct ReaderHealth {
@@ -98,6 +175,19 @@ impl ReaderManagerResource {
             policies: Arc::new(Mutex::new(HashMap::new())),
             reload_stats: Arc::new(Mutex::new(HashMap::new())),
             monitoring_config: Arc::new(Mutex::new(MonitoringConfig::default())),
+            event_subscriber: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Send a lifecycle event to the subscribed process, if any
+    ///
+    /// Delivery is best effort: a dead subscriber never fails the reader operation.
+    fn emit(&self, env: Env, event: ReaderEvent) {
+        let subscriber = self.event_subscriber.lock().unwrap();
+        if let Some(pid) = subscriber.as_ref() {
+            if let Ok(message) = event.to_message(env) {
+                let _ = env.send(pid, message);
+            }
         }
     }
 }
@@ -155,6 +245,25 @@ pub fn reader_manager_configure_monitoring(
     Ok(rustler::types::atom::ok())
 }
 
+/// Subscribe a process to reader lifecycle events, replacing any previous subscriber
+#[rustler::nif]
+pub fn reader_manager_subscribe(
+    manager: ResourceArc<ReaderManagerResource>,
+    pid: LocalPid,
+) -> NifResult<rustler::types::atom::Atom> {
+    *manager.event_subscriber.lock().unwrap() = Some(pid);
+    Ok(rustler::types::atom::ok())
+}
+
+/// Stop sending reader lifecycle events
+#[rustler::nif]
+pub fn reader_manager_unsubscribe(
+    manager: ResourceArc<ReaderManagerResource>,
+) -> NifResult<rustler::types::atom::Atom> {
+    *manager.event_subscriber.lock().unwrap() = None;
+    Ok(rustler::types::atom::ok())
+}
+
 /// Create a reload policy
 #[rustler::nif]
 pub fn reader_manager_create_policy(
@@ -201,6 +310,7 @@ pub fn reader_manager_create_policy(
 /// Create and register an index reader
 #[rustler::nif]
 pub fn reader_manager_create_reader(
+    env: Env,
     manager: ResourceArc<ReaderManagerResource>,
     index_resource: ResourceArc<IndexResource>,
     reader_id: String,
@@ -249,7 +359,15 @@ pub fn reader_manager_create_reader(
     };
 
     let mut reload_stats = manager.reload_stats.lock().unwrap();
-    reload_stats.insert(reader_id, stats);
+    reload_stats.insert(reader_id.clone(), stats);
+
+    manager.emit(
+        env,
+        ReaderEvent::Created {
+            reader_id,
+            timestamp: now,
+        },
+    );
 
     Ok(rustler::types::atom::ok())
 }
@@ -257,6 +375,7 @@ pub fn reader_manager_create_reader(
 /// Manually reload a reader
 #[rustler::nif(schedule = "DirtyIo")]
 pub fn reader_manager_reload_reader(
+    env: Env,
     manager: ResourceArc<ReaderManagerResource>,
     reader_id: String,
     force_reload: bool,
@@ -284,6 +403,27 @@ pub fn reader_manager_reload_reader(
         stats.reload_count += 1;
     }
 
+    let duration_ms = reload_duration.as_millis() as u64;
+    match &reload_result {
+        Ok(_) => manager.emit(
+            env,
+            ReaderEvent::Reloaded {
+                reader_id: reader_id.clone(),
+                timestamp: now,
+                duration_ms,
+            },
+        ),
+        Err(e) => manager.emit(
+            env,
+            ReaderEvent::ReloadFailed {
+                reader_id: reader_id.clone(),
+                timestamp: now,
+                duration_ms,
+                error: e.to_string(),
+            },
+        ),
+    }
+
     match reload_result {
         Ok(_) => {
             let response = serde_json::json!({
@@ -413,6 +553,7 @@ pub fn reader_manager_get_reader_health(
 /// Record a search operation for statistics
 #[rustler::nif]
 pub fn reader_manager_record_search(
+    env: Env,
     manager: ResourceArc<ReaderManagerResource>,
     reader_id: String,
     search_duration_ms: u64,
@@ -423,6 +564,13 @@ pub fn reader_manager_record_search(
         stats.search_count += 1;
         stats.total_search_time_ms += search_duration_ms;
         stats.average_search_time_ms = stats.total_search_time_ms as f64 / stats.search_count as f64;
+        manager.emit(
+            env,
+            ReaderEvent::SearchPerformed {
+                reader_id,
+                duration_ms: search_duration_ms,
+            },
+        );
     }
 
     Ok(rustler::types::atom::ok())
@@ -492,14 +640,23 @@ pub fn reader_manager_list_policies(
 /// Dispose of a reader
 #[rustler::nif]
 pub fn reader_manager_dispose_reader(
+    env: Env,
     manager: ResourceArc<ReaderManagerResource>,
     reader_id: String,
 ) -> NifResult<rustler::types::atom::Atom> {
     let mut readers = manager.readers.write().unwrap();
     let mut reload_stats = manager.reload_stats.lock().unwrap();
 
-    readers.remove(&reader_id);
     reload_stats.remove(&reader_id);
+    if readers.remove(&reader_id).is_some() {
+        manager.emit(
+            env,
+            ReaderEvent::Disposed {
+                reader_id,
+                timestamp: unix_now(),
+            },
+        );
+    }
 
     Ok(rustler::types::atom::ok())
 }
@@ -507,13 +664,23 @@ pub fn reader_manager_dispose_reader(
 /// Clear all readers and statistics
 #[rustler::nif]
 pub fn reader_manager_clear_all(
+    env: Env,
     manager: ResourceArc<ReaderManagerResource>,
 ) -> NifResult<rustler::types::atom::Atom> {
     let mut readers = manager.readers.write().unwrap();
     let mut reload_stats = manager.reload_stats.lock().unwrap();
     let mut policies = manager.policies.lock().unwrap();
 
-    readers.clear();
+    let timestamp = unix_now();
+    for (reader_id, _) in readers.drain() {
+        manager.emit(
+            env,
+            ReaderEvent::Disposed {
+                reader_id,
+                timestamp,
+            },
+        );
+    }
     reload_stats.clear();
     policies.clear();
 
@@ -522,6 +689,13 @@ pub fn reader_manager_clear_all(
 
 // Helper functions
 
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
 fn estimate_reader_memory_usage() -> u64 {
     // Simplified estimation - in reality would analyze reader internals
     1024 * 1024 * 50 // 50MB placeholder
@@ -1,13 +1,17 @@
 use std::collections::BTreeMap;
 use std::panic::{RefUnwindSafe, UnwindSafe};
+use std::sync::atomic::AtomicU64;
 use std::sync::{Arc, Mutex};
 use tantivy::schema::{OwnedValue, Schema};
 use tantivy::tokenizer::TokenizerManager;
-use tantivy::{query::QueryParser, Index, IndexWriter};
+use tantivy::{query::QueryParser, Index, IndexReader, IndexWriter};
 
 // Resource types for managing state
 pub struct IndexResource {
     pub index: Arc<Index>,
+    /// Long-lived reader reused by `index_acquire_searcher`, together with the
+    /// commit opstamp it was last reloaded at
+    pub cached_reader: Mutex<Option<(IndexReader, u64)>>,
 }
 
 // Make IndexResource safe for unwind
@@ -22,6 +26,12 @@ pub struct SchemaResource {
 
 pub struct IndexWriterResource {
     pub writer: Arc<Mutex<IndexWriter>>,
+    /// Memory budget the writer was created with, shared by all indexing threads
+    pub memory_budget: usize,
+    /// Explicit indexing thread count, `None` when tantivy picked it
+    pub num_threads: Option<usize>,
+    /// Documents added since the last commit or rollback
+    pub pending_documents: AtomicU64,
 }
 
 pub struct SearcherResource {
@@ -34,6 +44,7 @@ pub struct QueryResource {
 
 pub struct QueryParserResource {
     pub parser: QueryParser,
+    pub schema: Schema,
 }
 
 pub struct TokenizerManagerResource {
@@ -105,5 +116,9 @@ pub mod atoms {
         ok,
         error,
         nil,
+        timeout,
+        not_found,
+        pre_tokenized,
+        tantivy_commit,
     }
 }
@@ -1,7 +1,8 @@
 use rustler::{Encoder, Env, NifResult, ResourceArc, Term};
+use std::collections::HashSet;
 use tantivy::schema::{
-    BytesOptions, DateOptions, FacetOptions, FieldType, IpAddrOptions, JsonObjectOptions,
-    NumericOptions, Schema, TextFieldIndexing, TextOptions,
+    BytesOptions, DateOptions, FacetOptions, FieldEntry, FieldType, IpAddrOptions,
+    JsonObjectOptions, NumericOptions, Schema, TextFieldIndexing, TextOptions,
 };
 
 use crate::modules::resources::SchemaResource;
@@ -22,27 +23,7 @@ pub fn schema_add_text_field(
     let mut schema_builder = Schema::builder();
     copy_existing_fields_to_builder(&schema_res.schema, &mut schema_builder);
 
-    // Parse options for the new field
-    let field_options = match options.as_str() {
-        "TEXT_STORED" => TextOptions::default()
-            .set_indexing_options(TextFieldIndexing::default())
-            .set_stored(),
-        "TEXT" => TextOptions::default().set_indexing_options(TextFieldIndexing::default()),
-        "STORED" => TextOptions::default().set_stored(),
-        "FAST" => TextOptions::default()
-            .set_indexing_options(TextFieldIndexing::default())
-            .set_fast(None),
-        "FAST_STORED" => TextOptions::default()
-            .set_indexing_options(
-                TextFieldIndexing::default()
-                    .set_index_option(tantivy::schema::IndexRecordOption::WithFreqsAndPositions),
-            )
-            .set_stored()
-            .set_fast(None),
-        _ => TextOptions::default().set_indexing_options(TextFieldIndexing::default()),
-    };
-
-    schema_builder.add_text_field(&field_name, field_options);
+    schema_builder.add_text_field(&field_name, text_options(&options));
     let schema = schema_builder.build();
 
     Ok(ResourceArc::new(SchemaResource { schema }))
@@ -58,22 +39,10 @@ pub fn schema_add_text_field_with_tokenizer(
     let mut schema_builder = Schema::builder();
     copy_existing_fields_to_builder(&schema_res.schema, &mut schema_builder);
 
-    // Parse options and configure with custom tokenizer
-    let field_options = match options.as_str() {
-        "TEXT_STORED" => TextOptions::default()
-            .set_indexing_options(TextFieldIndexing::default().set_tokenizer(&tokenizer))
-            .set_stored(),
-        "TEXT" => TextOptions::default()
-            .set_indexing_options(TextFieldIndexing::default().set_tokenizer(&tokenizer)),
-        "STORED" => {
-            // For STORED-only fields, we don't set indexing options or tokenizer
-            TextOptions::default().set_stored()
-        }
-        _ => TextOptions::default()
-            .set_indexing_options(TextFieldIndexing::default().set_tokenizer(&tokenizer)),
-    };
-
-    schema_builder.add_text_field(&field_name, field_options);
+    schema_builder.add_text_field(
+        &field_name,
+        text_options_with_tokenizer(&options, &tokenizer),
+    );
     let schema = schema_builder.build();
 
     Ok(ResourceArc::new(SchemaResource { schema }))
@@ -88,17 +57,7 @@ pub fn schema_add_u64_field(
     let mut schema_builder = Schema::builder();
     copy_existing_fields_to_builder(&schema_res.schema, &mut schema_builder);
 
-    // Parse options for the new field
-    let field_options = match options.as_str() {
-        "INDEXED_STORED" => NumericOptions::default().set_indexed().set_stored(),
-        "INDEXED" => NumericOptions::default().set_indexed(),
-        "STORED" => NumericOptions::default().set_stored(),
-        "FAST" => NumericOptions::default().set_fast(),
-        "FAST_STORED" => NumericOptions::default().set_fast().set_stored(),
-        _ => NumericOptions::default().set_indexed(),
-    };
-
-    schema_builder.add_u64_field(&field_name, field_options);
+    schema_builder.add_u64_field(&field_name, numeric_options(&options));
     let schema = schema_builder.build();
 
     Ok(ResourceArc::new(SchemaResource { schema }))
@@ -113,16 +72,7 @@ pub fn schema_add_i64_field(
     let mut schema_builder = Schema::builder();
     copy_existing_fields_to_builder(&schema_res.schema, &mut schema_builder);
 
-    let field_options = match options.as_str() {
-        "INDEXED_STORED" => NumericOptions::default().set_indexed().set_stored(),
-        "INDEXED" => NumericOptions::default().set_indexed(),
-        "STORED" => NumericOptions::default().set_stored(),
-        "FAST" => NumericOptions::default().set_fast(),
-        "FAST_STORED" => NumericOptions::default().set_fast().set_stored(),
-        _ => NumericOptions::default().set_indexed(),
-    };
-
-    schema_builder.add_i64_field(&field_name, field_options);
+    schema_builder.add_i64_field(&field_name, numeric_options(&options));
     let schema = schema_builder.build();
 
     Ok(ResourceArc::new(SchemaResource { schema }))
@@ -137,16 +87,7 @@ pub fn schema_add_f64_field(
     let mut schema_builder = Schema::builder();
     copy_existing_fields_to_builder(&schema_res.schema, &mut schema_builder);
 
-    let field_options = match options.as_str() {
-        "INDEXED_STORED" => NumericOptions::default().set_indexed().set_stored(),
-        "INDEXED" => NumericOptions::default().set_indexed(),
-        "STORED" => NumericOptions::default().set_stored(),
-        "FAST" => NumericOptions::default().set_fast(),
-        "FAST_STORED" => NumericOptions::default().set_fast().set_stored(),
-        _ => NumericOptions::default().set_indexed(),
-    };
-
-    schema_builder.add_f64_field(&field_name, field_options);
+    schema_builder.add_f64_field(&field_name, numeric_options(&options));
     let schema = schema_builder.build();
 
     Ok(ResourceArc::new(SchemaResource { schema }))
@@ -161,16 +102,7 @@ pub fn schema_add_bool_field(
     let mut schema_builder = Schema::builder();
     copy_existing_fields_to_builder(&schema_res.schema, &mut schema_builder);
 
-    let field_options = match options.as_str() {
-        "INDEXED_STORED" => NumericOptions::default().set_indexed().set_stored(),
-        "INDEXED" => NumericOptions::default().set_indexed(),
-        "STORED" => NumericOptions::default().set_stored(),
-        "FAST" => NumericOptions::default().set_fast(),
-        "FAST_STORED" => NumericOptions::default().set_fast().set_stored(),
-        _ => NumericOptions::default().set_indexed(),
-    };
-
-    schema_builder.add_bool_field(&field_name, field_options);
+    schema_builder.add_bool_field(&field_name, numeric_options(&options));
     let schema = schema_builder.build();
 
     Ok(ResourceArc::new(SchemaResource { schema }))
@@ -185,16 +117,7 @@ pub fn schema_add_date_field(
     let mut schema_builder = Schema::builder();
     copy_existing_fields_to_builder(&schema_res.schema, &mut schema_builder);
 
-    let field_options = match options.as_str() {
-        "INDEXED_STORED" => DateOptions::default().set_indexed().set_stored(),
-        "INDEXED" => DateOptions::default().set_indexed(),
-        "STORED" => DateOptions::default().set_stored(),
-        "FAST" => DateOptions::default().set_fast(),
-        "FAST_STORED" => DateOptions::default().set_fast().set_stored(),
-        _ => DateOptions::default().set_indexed(),
-    };
-
-    schema_builder.add_date_field(&field_name, field_options);
+    schema_builder.add_date_field(&field_name, date_options(&options));
     let schema = schema_builder.build();
 
     Ok(ResourceArc::new(SchemaResource { schema }))
@@ -227,16 +150,7 @@ pub fn schema_add_bytes_field(
     let mut schema_builder = Schema::builder();
     copy_existing_fields_to_builder(&schema_res.schema, &mut schema_builder);
 
-    let field_options = match options.as_str() {
-        "INDEXED_STORED" => BytesOptions::default().set_indexed().set_stored(),
-        "INDEXED" => BytesOptions::default().set_indexed(),
-        "STORED" => BytesOptions::default().set_stored(),
-        "FAST" => BytesOptions::default().set_fast(),
-        "FAST_STORED" => BytesOptions::default().set_fast().set_stored(),
-        _ => BytesOptions::default().set_stored(), // Bytes are typically stored
-    };
-
-    schema_builder.add_bytes_field(&field_name, field_options);
+    schema_builder.add_bytes_field(&field_name, bytes_options(&options));
     let schema = schema_builder.build();
 
     Ok(ResourceArc::new(SchemaResource { schema }))
@@ -251,12 +165,7 @@ pub fn schema_add_json_field(
     let mut schema_builder = Schema::builder();
     copy_existing_fields_to_builder(&schema_res.schema, &mut schema_builder);
 
-    let field_options = match options.as_str() {
-        "STORED" => JsonObjectOptions::default().set_stored(),
-        _ => JsonObjectOptions::default(), // JSON fields are indexed by default
-    };
-
-    schema_builder.add_json_field(&field_name, field_options);
+    schema_builder.add_json_field(&field_name, json_options(&options));
     let schema = schema_builder.build();
 
     Ok(ResourceArc::new(SchemaResource { schema }))
@@ -271,16 +180,7 @@ pub fn schema_add_ip_addr_field(
     let mut schema_builder = Schema::builder();
     copy_existing_fields_to_builder(&schema_res.schema, &mut schema_builder);
 
-    let field_options = match options.as_str() {
-        "INDEXED_STORED" => IpAddrOptions::default().set_indexed().set_stored(),
-        "INDEXED" => IpAddrOptions::default().set_indexed(),
-        "STORED" => IpAddrOptions::default().set_stored(),
-        "FAST" => IpAddrOptions::default().set_fast(),
-        "FAST_STORED" => IpAddrOptions::default().set_fast().set_stored(),
-        _ => IpAddrOptions::default().set_indexed(),
-    };
-
-    schema_builder.add_ip_addr_field(&field_name, field_options);
+    schema_builder.add_ip_addr_field(&field_name, ip_addr_options(&options));
     let schema = schema_builder.build();
 
     Ok(ResourceArc::new(SchemaResource { schema }))
@@ -309,19 +209,7 @@ pub fn schema_get_field_type<'a>(
     match schema_res.schema.get_field(&field_name) {
         Ok(field) => {
             let field_entry = schema_res.schema.get_field_entry(field);
-            let field_type_str = match field_entry.field_type() {
-                FieldType::Str(_) => "text",
-                FieldType::U64(_) => "u64",
-                FieldType::I64(_) => "i64",
-                FieldType::F64(_) => "f64",
-                FieldType::Bool(_) => "bool",
-                FieldType::Date(_) => "date",
-                FieldType::Facet(_) => "facet",
-                FieldType::Bytes(_) => "bytes",
-                FieldType::JsonObject(_) => "json",
-                FieldType::IpAddr(_) => "ip_addr",
-            };
-            Ok(field_type_str.encode(env))
+            Ok(field_type_name(field_entry.field_type()).encode(env))
         }
         Err(_) => Err(rustler::Error::Term(Box::new(format!(
             "Field '{}' not found in schema",
@@ -330,6 +218,22 @@ pub fn schema_get_field_type<'a>(
     }
 }
 
+/// Helper function returning the type name reported for a field type
+pub fn field_type_name(field_type: &FieldType) -> &'static str {
+    match field_type {
+        FieldType::Str(_) => "text",
+        FieldType::U64(_) => "u64",
+        FieldType::I64(_) => "i64",
+        FieldType::F64(_) => "f64",
+        FieldType::Bool(_) => "bool",
+        FieldType::Date(_) => "date",
+        FieldType::Facet(_) => "facet",
+        FieldType::Bytes(_) => "bytes",
+        FieldType::JsonObject(_) => "json",
+        FieldType::IpAddr(_) => "ip_addr",
+    }
+}
+
 #[rustler::nif]
 pub fn schema_validate<'a>(
     env: Env<'a>,
@@ -348,44 +252,258 @@ pub fn schema_validate<'a>(
     Ok(message.encode(env))
 }
 
-/// Helper function to copy existing fields to a new schema builder (DRY principle)
-fn copy_existing_fields_to_builder(
-    schema: &Schema,
-    schema_builder: &mut tantivy::schema::SchemaBuilder,
-) {
-    for (field, field_entry) in schema.fields() {
-        let field_name_existing = schema.get_field_name(field);
-        match field_entry.field_type() {
-            FieldType::Str(text_options) => {
-                schema_builder.add_text_field(field_name_existing, text_options.clone());
+#[rustler::nif]
+pub fn schema_remove_field(
+    schema_res: ResourceArc<SchemaResource>,
+    field_name: String,
+) -> NifResult<ResourceArc<SchemaResource>> {
+    if schema_res.schema.get_field(&field_name).is_err() {
+        return Err(rustler::Error::Term(Box::new(format!(
+            "Field '{}' not found in schema",
+            field_name
+        ))));
+    }
+
+    // Rebuild the schema from every field except the one being removed
+    let mut schema_builder = Schema::builder();
+    for (field, field_entry) in schema_res.schema.fields() {
+        let existing_name = schema_res.schema.get_field_name(field);
+        if existing_name != field_name {
+            add_field_entry_to_builder(&mut schema_builder, existing_name, field_entry);
+        }
+    }
+    let schema = schema_builder.build();
+
+    Ok(ResourceArc::new(SchemaResource { schema }))
+}
+
+/// Field description accepted by `schema_build`
+#[derive(serde::Deserialize)]
+struct FieldSpec {
+    name: String,
+    #[serde(rename = "type")]
+    field_type: String,
+    #[serde(default)]
+    options: String,
+    tokenizer: Option<String>,
+}
+
+/// Build a schema from a JSON array of `{name, type, options, tokenizer}` field specs
+///
+/// Unlike the `schema_add_*` functions, which rebuild the schema on every call, the whole
+/// field list is added to a single builder.
+#[rustler::nif]
+pub fn schema_build(fields_json: String) -> NifResult<ResourceArc<SchemaResource>> {
+    let specs: Vec<FieldSpec> = serde_json::from_str(&fields_json)
+        .map_err(|e| rustler::Error::Term(Box::new(format!("Invalid field list JSON: {}", e))))?;
+
+    let mut seen = HashSet::new();
+    let mut schema_builder = Schema::builder();
+    for spec in &specs {
+        if !seen.insert(spec.name.as_str()) {
+            return Err(rustler::Error::Term(Box::new(format!(
+                "Duplicate field '{}' in field list",
+                spec.name
+            ))));
+        }
+
+        let name = spec.name.as_str();
+        let options = spec.options.as_str();
+        match spec.field_type.as_str() {
+            "text" => {
+                let text_options = match &spec.tokenizer {
+                    Some(tokenizer) => text_options_with_tokenizer(options, tokenizer),
+                    None => text_options(options),
+                };
+                schema_builder.add_text_field(name, text_options);
             }
-            FieldType::U64(int_options) => {
-                schema_builder.add_u64_field(field_name_existing, int_options.clone());
+            "u64" => {
+                schema_builder.add_u64_field(name, numeric_options(options));
             }
-            FieldType::I64(int_options) => {
-                schema_builder.add_i64_field(field_name_existing, int_options.clone());
+            "i64" => {
+                schema_builder.add_i64_field(name, numeric_options(options));
             }
-            FieldType::F64(float_options) => {
-                schema_builder.add_f64_field(field_name_existing, float_options.clone());
+            "f64" => {
+                schema_builder.add_f64_field(name, numeric_options(options));
             }
-            FieldType::Bool(bool_options) => {
-                schema_builder.add_bool_field(field_name_existing, bool_options.clone());
+            "bool" => {
+                schema_builder.add_bool_field(name, numeric_options(options));
             }
-            FieldType::Date(date_options) => {
-                schema_builder.add_date_field(field_name_existing, date_options.clone());
+            "date" => {
+                schema_builder.add_date_field(name, date_options(options));
             }
-            FieldType::Facet(facet_options) => {
-                schema_builder.add_facet_field(field_name_existing, facet_options.clone());
+            "facet" => {
+                schema_builder.add_facet_field(name, FacetOptions::default());
             }
-            FieldType::Bytes(bytes_options) => {
-                schema_builder.add_bytes_field(field_name_existing, bytes_options.clone());
+            "bytes" => {
+                schema_builder.add_bytes_field(name, bytes_options(options));
             }
-            FieldType::JsonObject(json_options) => {
-                schema_builder.add_json_field(field_name_existing, json_options.clone());
+            "json" => {
+                schema_builder.add_json_field(name, json_options(options));
             }
-            FieldType::IpAddr(ip_options) => {
-                schema_builder.add_ip_addr_field(field_name_existing, ip_options.clone());
+            "ip_addr" => {
+                schema_builder.add_ip_addr_field(name, ip_addr_options(options));
             }
+            other => {
+                return Err(rustler::Error::Term(Box::new(format!(
+                    "Unknown type '{}' for field '{}'",
+                    other, name
+                ))));
+            }
+        }
+    }
+    let schema = schema_builder.build();
+
+    Ok(ResourceArc::new(SchemaResource { schema }))
+}
+
+fn text_options(options: &str) -> TextOptions {
+    match options {
+        "TEXT_STORED" => TextOptions::default()
+            .set_indexing_options(TextFieldIndexing::default())
+            .set_stored(),
+        "TEXT" => TextOptions::default().set_indexing_options(TextFieldIndexing::default()),
+        "STORED" => TextOptions::default().set_stored(),
+        "FAST" => TextOptions::default()
+            .set_indexing_options(TextFieldIndexing::default())
+            .set_fast(None),
+        "FAST_STORED" => TextOptions::default()
+            .set_indexing_options(
+                TextFieldIndexing::default()
+                    .set_index_option(tantivy::schema::IndexRecordOption::WithFreqsAndPositions),
+            )
+            .set_stored()
+            .set_fast(None),
+        _ => TextOptions::default().set_indexing_options(TextFieldIndexing::default()),
+    }
+}
+
+fn text_options_with_tokenizer(options: &str, tokenizer: &str) -> TextOptions {
+    match options {
+        "TEXT_STORED" => TextOptions::default()
+            .set_indexing_options(TextFieldIndexing::default().set_tokenizer(tokenizer))
+            .set_stored(),
+        "TEXT" => TextOptions::default()
+            .set_indexing_options(TextFieldIndexing::default().set_tokenizer(tokenizer)),
+        "STORED" => {
+            // For STORED-only fields, we don't set indexing options or tokenizer
+            TextOptions::default().set_stored()
+        }
+        _ => TextOptions::default()
+            .set_indexing_options(TextFieldIndexing::default().set_tokenizer(tokenizer)),
+    }
+}
+
+/// Options shared by the u64, i64, f64 and bool field types
+fn numeric_options(options: &str) -> NumericOptions {
+    match options {
+        "INDEXED_STORED" => NumericOptions::default().set_indexed().set_stored(),
+        "INDEXED" => NumericOptions::default().set_indexed(),
+        "STORED" => NumericOptions::default().set_stored(),
+        "FAST" => NumericOptions::default().set_fast(),
+        "FAST_STORED" => NumericOptions::default().set_fast().set_stored(),
+        _ => NumericOptions::default().set_indexed(),
+    }
+}
+
+fn date_options(options: &str) -> DateOptions {
+    match options {
+        "INDEXED_STORED" => DateOptions::default().set_indexed().set_stored(),
+        "INDEXED" => DateOptions::default().set_indexed(),
+        "STORED" => DateOptions::default().set_stored(),
+        "FAST" => DateOptions::default().set_fast(),
+        "FAST_STORED" => DateOptions::default().set_fast().set_stored(),
+        _ => DateOptions::default().set_indexed(),
+    }
+}
+
+fn bytes_options(options: &str) -> BytesOptions {
+    match options {
+        "INDEXED_STORED" => BytesOptions::default().set_indexed().set_stored(),
+        "INDEXED" => BytesOptions::default().set_indexed(),
+        "STORED" => BytesOptions::default().set_stored(),
+        "FAST" => BytesOptions::default().set_fast(),
+        "FAST_STORED" => BytesOptions::default().set_fast().set_stored(),
+        _ => BytesOptions::default().set_stored(), // Bytes are typically stored
+    }
+}
+
+fn json_options(options: &str) -> JsonObjectOptions {
+    // Sub-paths are only searchable when the object is indexed
+    match options {
+        "STORED" => JsonObjectOptions::default().set_stored(),
+        "TEXT_STORED" => JsonObjectOptions::default()
+            .set_indexing_options(TextFieldIndexing::default())
+            .set_stored(),
+        // Fast JSON objects keep a column per sub-path, needed by exists queries
+        "FAST" => JsonObjectOptions::default()
+            .set_indexing_options(TextFieldIndexing::default())
+            .set_fast(None),
+        "FAST_STORED" => JsonObjectOptions::default()
+            .set_indexing_options(TextFieldIndexing::default())
+            .set_stored()
+            .set_fast(None),
+        _ => JsonObjectOptions::default().set_indexing_options(TextFieldIndexing::default()),
+    }
+}
+
+fn ip_addr_options(options: &str) -> IpAddrOptions {
+    match options {
+        "INDEXED_STORED" => IpAddrOptions::default().set_indexed().set_stored(),
+        "INDEXED" => IpAddrOptions::default().set_indexed(),
+        "STORED" => IpAddrOptions::default().set_stored(),
+        "FAST" => IpAddrOptions::default().set_fast(),
+        "FAST_STORED" => IpAddrOptions::default().set_fast().set_stored(),
+        _ => IpAddrOptions::default().set_indexed(),
+    }
+}
+
+/// Helper function to copy existing fields to a new schema builder (DRY principle)
+fn copy_existing_fields_to_builder(
+    schema: &Schema,
+    schema_builder: &mut tantivy::schema::SchemaBuilder,
+) {
+    for (field, field_entry) in schema.fields() {
+        add_field_entry_to_builder(schema_builder, schema.get_field_name(field), field_entry);
+    }
+}
+
+/// Helper function to add a single field entry to a schema builder, keeping its options
+pub(crate) fn add_field_entry_to_builder(
+    schema_builder: &mut tantivy::schema::SchemaBuilder,
+    field_name_existing: &str,
+    field_entry: &FieldEntry,
+) {
+    match field_entry.field_type() {
+        FieldType::Str(text_options) => {
+            schema_builder.add_text_field(field_name_existing, text_options.clone());
+        }
+        FieldType::U64(int_options) => {
+            schema_builder.add_u64_field(field_name_existing, int_options.clone());
+        }
+        FieldType::I64(int_options) => {
+            schema_builder.add_i64_field(field_name_existing, int_options.clone());
+        }
+        FieldType::F64(float_options) => {
+            schema_builder.add_f64_field(field_name_existing, float_options.clone());
+        }
+        FieldType::Bool(bool_options) => {
+            schema_builder.add_bool_field(field_name_existing, bool_options.clone());
+        }
+        FieldType::Date(date_options) => {
+            schema_builder.add_date_field(field_name_existing, date_options.clone());
+        }
+        FieldType::Facet(facet_options) => {
+            schema_builder.add_facet_field(field_name_existing, facet_options.clone());
+        }
+        FieldType::Bytes(bytes_options) => {
+            schema_builder.add_bytes_field(field_name_existing, bytes_options.clone());
+        }
+        FieldType::JsonObject(json_options) => {
+            schema_builder.add_json_field(field_name_existing, json_options.clone());
+        }
+        FieldType::IpAddr(ip_options) => {
+            schema_builder.add_ip_addr_field(field_name_existing, ip_options.clone());
         }
     }
 }
@@ -1,14 +1,86 @@
 use base64::{engine::general_purpose, Engine as _};
+use levenshtein_automata::{Distance, LevenshteinAutomatonBuilder, DFA, SINK_STATE};
 use rustler::{Encoder, Env, NifResult, ResourceArc, Term};
 use serde_json;
-use tantivy::collector::TopDocs;
-use tantivy::schema::Value;
-use tantivy::TantivyDocument;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ops::Bound;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use tantivy::collector::{
+    Collector, Count, FacetCollector, MultiCollector, SegmentCollector, TopDocs,
+};
+use tantivy::columnar::{Column, StrColumn};
+use tantivy::fastfield::FastValue;
+use tantivy::postings::Postings;
+use tantivy::query::{BooleanQuery, EnableScoring, ExistsQuery, Occur, RangeQuery, Weight};
+use tantivy::schema::document::ReferenceValueLeaf;
+use tantivy::schema::{Facet, FieldEntry, FieldType, IndexRecordOption, OwnedValue, Schema, Value};
+use tantivy::snippet::SnippetGenerator;
+use tantivy::tokenizer::TokenStream;
+use tantivy::{
+    DateTime, DocAddress, DocId, DocSet, Order, Score, SegmentOrdinal, SegmentReader,
+    TantivyDocument, Term as TantivyTerm, TERMINATED,
+};
+use tantivy_fst::Automaton;
 
-use crate::modules::resources::{QueryResource, SearcherResource};
+use crate::modules::aggregation::convert_owned_value_to_json;
+use crate::modules::query::build_term_query;
+use crate::modules::resources::{atoms, QueryResource, SearcherResource};
 
 /// Search and retrieval functions
 
+/// Insert the stored fields of a document into a JSON result map
+fn insert_document_fields(
+    doc_map: &mut serde_json::Map<String, serde_json::Value>,
+    schema: &Schema,
+    doc: &TantivyDocument,
+) {
+    for (field, value) in doc.field_values() {
+        let field_name = schema.get_field_name(field);
+        let json_value = if let Some(s) = value.as_str() {
+            serde_json::Value::String(s.to_string())
+        } else if let Some(ReferenceValueLeaf::PreTokStr(pre_tok)) = value.as_leaf() {
+            // Pre-tokenized values read back as their original text
+            serde_json::Value::String(pre_tok.text.clone())
+        } else if let Some(n) = value.as_u64() {
+            serde_json::Value::Number(serde_json::Number::from(n))
+        } else if let Some(n) = value.as_i64() {
+            serde_json::Value::Number(serde_json::Number::from(n))
+        } else if let Some(n) = value.as_f64() {
+            serde_json::Value::Number(
+                serde_json::Number::from_f64(n).unwrap_or(serde_json::Number::from(0)),
+            )
+        } else if let Some(b) = value.as_bool() {
+            serde_json::Value::Bool(b)
+        } else if let Some(d) = value.as_datetime() {
+            serde_json::Value::String(format!("{:?}", d))
+        } else if let Some(f) = value.as_facet() {
+            serde_json::Value::String(f.to_string())
+        } else if let Some(b) = value.as_bytes() {
+            serde_json::Value::String(general_purpose::STANDARD.encode(b))
+        } else if value.as_object().is_some() || value.as_array().is_some() {
+            let owned_value: OwnedValue = value.into();
+            convert_owned_value_to_json(&owned_value)
+        } else if let Some(ip) = value.as_ip_addr() {
+            serde_json::Value::String(ip.to_string())
+        } else {
+            serde_json::Value::Null
+        };
+
+        // A facet field can hold several paths, so it is always returned as an array
+        if value.as_facet().is_some() {
+            let paths = doc_map
+                .entry(field_name.to_string())
+                .or_insert_with(|| serde_json::Value::Array(Vec::new()));
+            if let serde_json::Value::Array(paths) = paths {
+                paths.push(json_value);
+            }
+            continue;
+        }
+        doc_map.insert(field_name.to_string(), json_value);
+    }
+}
+
 #[rustler::nif(schedule = "DirtyCpu")]
 pub fn searcher_search<'a>(
     env: Env<'a>,
@@ -47,45 +119,7 @@ pub fn searcher_search<'a>(
                         );
 
                         // Add document fields
-                        for (field, value) in doc.field_values() {
-                            let field_name = searcher_res.searcher.schema().get_field_name(field);
-                            let json_value = if let Some(s) = value.as_str() {
-                                serde_json::Value::String(s.to_string())
-                            } else if let Some(n) = value.as_u64() {
-                                serde_json::Value::Number(serde_json::Number::from(n))
-                            } else if let Some(n) = value.as_i64() {
-                                serde_json::Value::Number(serde_json::Number::from(n))
-                            } else if let Some(n) = value.as_f64() {
-                                serde_json::Value::Number(
-                                    serde_json::Number::from_f64(n)
-                                        .unwrap_or(serde_json::Number::from(0)),
-                                )
-                            } else if let Some(b) = value.as_bool() {
-                                serde_json::Value::Bool(b)
-                            } else if let Some(d) = value.as_datetime() {
-                                serde_json::Value::String(format!("{:?}", d))
-                            } else if let Some(f) = value.as_facet() {
-                                serde_json::Value::String(f.to_string())
-                            } else if let Some(b) = value.as_bytes() {
-                                serde_json::Value::String(general_purpose::STANDARD.encode(b))
-                            } else if let Some(obj_iter) = value.as_object() {
-                                // Convert object iterator to JSON value
-                                let mut json_obj = serde_json::Map::new();
-                                for (key, val) in obj_iter {
-                                    // For now, just convert to string - could be enhanced later
-                                    json_obj.insert(
-                                        key.to_string(),
-                                        serde_json::Value::String(format!("{:?}", val)),
-                                    );
-                                }
-                                serde_json::Value::Object(json_obj)
-                            } else if let Some(ip) = value.as_ip_addr() {
-                                serde_json::Value::String(ip.to_string())
-                            } else {
-                                serde_json::Value::Null
-                            };
-                            doc_map.insert(field_name.to_string(), json_value);
-                        }
+                        insert_document_fields(&mut doc_map, searcher_res.searcher.schema(), &doc);
 
                         results.push(serde_json::Value::Object(doc_map));
                     }
@@ -124,98 +158,178 @@ pub fn searcher_search<'a>(
     }
 }
 
+/// Turn scored hits into JSON objects with `score`, `doc_id` and optionally the stored fields
+fn scored_hits_to_json(
+    searcher: &tantivy::Searcher,
+    docs: Vec<(Score, DocAddress)>,
+    include_docs: bool,
+) -> Vec<serde_json::Value> {
+    let mut results = Vec::new();
+
+    for (score, doc_address) in docs {
+        let mut doc_map = serde_json::Map::new();
+        doc_map.insert(
+            "score".to_string(),
+            serde_json::Value::Number(
+                serde_json::Number::from_f64(score as f64).unwrap_or(serde_json::Number::from(0)),
+            ),
+        );
+        doc_map.insert(
+            "doc_id".to_string(),
+            serde_json::Value::Number(serde_json::Number::from(doc_address.doc_id as u64)),
+        );
+
+        if include_docs {
+            match searcher.doc::<TantivyDocument>(doc_address) {
+                // Add document fields
+                Ok(doc) => insert_document_fields(&mut doc_map, searcher.schema(), &doc),
+                Err(_) => continue,
+            }
+        }
+
+        results.push(serde_json::Value::Object(doc_map));
+    }
+
+    results
+}
+
+/// Wraps a collector so that no new segment is searched once `deadline` has passed.
+///
+/// A segment that is already being collected runs to completion, so the
+/// deadline bounds the cost of a query to roughly one segment past it.
+struct DeadlineCollector<C> {
+    inner: C,
+    /// `None` when the timeout is too large to represent, i.e. no deadline
+    deadline: Option<Instant>,
+    timed_out: AtomicBool,
+}
+
+impl<C> DeadlineCollector<C> {
+    fn new(inner: C, timeout: Duration) -> Self {
+        DeadlineCollector {
+            inner,
+            deadline: Instant::now().checked_add(timeout),
+            timed_out: AtomicBool::new(false),
+        }
+    }
+
+    fn check_deadline(&self) -> tantivy::Result<()> {
+        if self
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            self.timed_out.store(true, Ordering::Relaxed);
+            return Err(tantivy::TantivyError::InternalError(
+                "Query timed out".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl<C: Collector> Collector for DeadlineCollector<C> {
+    type Fruit = C::Fruit;
+    type Child = C::Child;
+
+    fn for_segment(
+        &self,
+        segment_local_id: SegmentOrdinal,
+        segment: &SegmentReader,
+    ) -> tantivy::Result<Self::Child> {
+        self.check_deadline()?;
+        self.inner.for_segment(segment_local_id, segment)
+    }
+
+    fn requires_scoring(&self) -> bool {
+        self.inner.requires_scoring()
+    }
+
+    fn merge_fruits(
+        &self,
+        segment_fruits: Vec<<Self::Child as SegmentCollector>::Fruit>,
+    ) -> tantivy::Result<Self::Fruit> {
+        self.inner.merge_fruits(segment_fruits)
+    }
+
+    // Delegate so the inner collector keeps its own optimized segment collection
+    fn collect_segment(
+        &self,
+        weight: &dyn Weight,
+        segment_ord: SegmentOrdinal,
+        reader: &SegmentReader,
+    ) -> tantivy::Result<<Self::Child as SegmentCollector>::Fruit> {
+        self.check_deadline()?;
+        self.inner.collect_segment(weight, segment_ord, reader)
+    }
+}
+
+/// Like `scored_hits_to_json`, attaching each hit's score `explanation`.
+///
+/// Explaining re-scores every hit on its own, so this costs roughly one
+/// extra query evaluation per hit.
+fn explained_hits_to_json(
+    searcher: &tantivy::Searcher,
+    query: &dyn tantivy::query::Query,
+    docs: Vec<(Score, DocAddress)>,
+    include_docs: bool,
+) -> NifResult<Vec<serde_json::Value>> {
+    let mut results = Vec::with_capacity(docs.len());
+
+    for (score, doc_address) in docs {
+        let explanation = query
+            .explain(searcher, doc_address)
+            .and_then(|explanation| {
+                serde_json::to_value(&explanation)
+                    .map_err(|e| tantivy::TantivyError::InternalError(e.to_string()))
+            })
+            .map_err(|e| rustler::Error::Term(Box::new(format!("Failed to explain hit: {}", e))))?;
+
+        for mut hit in scored_hits_to_json(searcher, vec![(score, doc_address)], include_docs) {
+            if let serde_json::Value::Object(doc_map) = &mut hit {
+                doc_map.insert("explanation".to_string(), explanation.clone());
+            }
+            results.push(hit);
+        }
+    }
+
+    Ok(results)
+}
+
+/// Search with a `Query` resource, returning the top `limit` hits as JSON.
+///
+/// With a `timeout_ms`, segments are no longer searched once it has elapsed
+/// and `{:error, :timeout}` is returned instead of partial results. With
+/// `explain`, every hit carries the explanation of its score.
 #[rustler::nif(schedule = "DirtyCpu")]
 pub fn searcher_search_with_query(
     searcher_res: ResourceArc<SearcherResource>,
     query_res: ResourceArc<QueryResource>,
     limit: u64,
     include_docs: bool,
+    timeout_ms: Option<u64>,
+    explain: bool,
 ) -> NifResult<String> {
     let top_docs = TopDocs::with_limit(limit as usize);
-    match searcher_res.searcher.search(&*query_res.query, &top_docs) {
-        Ok(docs) => {
-            let mut results = Vec::new();
-
-            for (score, doc_address) in docs {
-                if include_docs {
-                    if let Ok(doc) = searcher_res.searcher.doc::<TantivyDocument>(doc_address) {
-                        let mut doc_map = serde_json::Map::new();
-                        doc_map.insert(
-                            "score".to_string(),
-                            serde_json::Value::Number(
-                                serde_json::Number::from_f64(score as f64)
-                                    .unwrap_or(serde_json::Number::from(0)),
-                            ),
-                        );
-                        doc_map.insert(
-                            "doc_id".to_string(),
-                            serde_json::Value::Number(serde_json::Number::from(
-                                doc_address.doc_id as u64,
-                            )),
-                        );
-
-                        // Add document fields
-                        for (field, value) in doc.field_values() {
-                            let field_name = searcher_res.searcher.schema().get_field_name(field);
-                            let json_value = if let Some(s) = value.as_str() {
-                                serde_json::Value::String(s.to_string())
-                            } else if let Some(n) = value.as_u64() {
-                                serde_json::Value::Number(serde_json::Number::from(n))
-                            } else if let Some(n) = value.as_i64() {
-                                serde_json::Value::Number(serde_json::Number::from(n))
-                            } else if let Some(n) = value.as_f64() {
-                                serde_json::Value::Number(
-                                    serde_json::Number::from_f64(n)
-                                        .unwrap_or(serde_json::Number::from(0)),
-                                )
-                            } else if let Some(b) = value.as_bool() {
-                                serde_json::Value::Bool(b)
-                            } else if let Some(d) = value.as_datetime() {
-                                serde_json::Value::String(format!("{:?}", d))
-                            } else if let Some(f) = value.as_facet() {
-                                serde_json::Value::String(f.to_string())
-                            } else if let Some(b) = value.as_bytes() {
-                                serde_json::Value::String(general_purpose::STANDARD.encode(b))
-                            } else if let Some(obj_iter) = value.as_object() {
-                                // Convert object iterator to JSON value
-                                let mut json_obj = serde_json::Map::new();
-                                for (key, val) in obj_iter {
-                                    // For now, just convert to string - could be enhanced later
-                                    json_obj.insert(
-                                        key.to_string(),
-                                        serde_json::Value::String(format!("{:?}", val)),
-                                    );
-                                }
-                                serde_json::Value::Object(json_obj)
-                            } else if let Some(ip) = value.as_ip_addr() {
-                                serde_json::Value::String(ip.to_string())
-                            } else {
-                                serde_json::Value::Null
-                            };
-                            doc_map.insert(field_name.to_string(), json_value);
-                        }
-
-                        results.push(serde_json::Value::Object(doc_map));
-                    }
-                } else {
-                    // Just return score and doc_id
-                    let mut doc_map = serde_json::Map::new();
-                    doc_map.insert(
-                        "score".to_string(),
-                        serde_json::Value::Number(
-                            serde_json::Number::from_f64(score as f64)
-                                .unwrap_or(serde_json::Number::from(0)),
-                        ),
-                    );
-                    doc_map.insert(
-                        "doc_id".to_string(),
-                        serde_json::Value::Number(serde_json::Number::from(
-                            doc_address.doc_id as u64,
-                        )),
-                    );
-                    results.push(serde_json::Value::Object(doc_map));
-                }
+    let search_result = match timeout_ms {
+        Some(timeout_ms) => {
+            let collector = DeadlineCollector::new(top_docs, Duration::from_millis(timeout_ms));
+            let result = searcher_res.searcher.search(&*query_res.query, &collector);
+            if collector.timed_out.load(Ordering::Relaxed) {
+                return Err(rustler::Error::Term(Box::new(atoms::timeout())));
             }
+            result
+        }
+        None => searcher_res.searcher.search(&*query_res.query, &top_docs),
+    };
+
+    match search_result {
+        Ok(docs) => {
+            let searcher = &searcher_res.searcher;
+            let results = if explain {
+                explained_hits_to_json(searcher, &*query_res.query, docs, include_docs)?
+            } else {
+                scored_hits_to_json(searcher, docs, include_docs)
+            };
 
             match serde_json::to_string(&results) {
                 Ok(json) => Ok(json),
@@ -231,3 +345,1342 @@ pub fn searcher_search_with_query(
         )))),
     }
 }
+
+/// Search like `searcher_search_with_query`, also reporting index totals.
+///
+/// Alongside the hits, returns the number of live matches and the searcher's
+/// `num_docs`, `max_doc` and `deleted_docs`. A large gap between `max_doc`
+/// and `num_docs` means deleted documents are still waiting to be merged out.
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn searcher_search_with_totals(
+    searcher_res: ResourceArc<SearcherResource>,
+    query_res: ResourceArc<QueryResource>,
+    limit: u64,
+    include_docs: bool,
+) -> NifResult<String> {
+    let searcher = &searcher_res.searcher;
+    let collector = (TopDocs::with_limit(limit as usize), Count);
+    let (docs, total_hits) = searcher
+        .search(&*query_res.query, &collector)
+        .map_err(|e| rustler::Error::Term(Box::new(format!("Search failed: {}", e))))?;
+
+    let num_docs = searcher.num_docs();
+    let max_doc: u64 = searcher
+        .segment_readers()
+        .iter()
+        .map(|segment_reader| segment_reader.max_doc() as u64)
+        .sum();
+
+    let result = serde_json::json!({
+        "hits": scored_hits_to_json(searcher, docs, include_docs),
+        "total_hits": total_hits,
+        "num_docs": num_docs,
+        "max_doc": max_doc,
+        "deleted_docs": max_doc - num_docs,
+    });
+
+    serde_json::to_string(&result)
+        .map_err(|e| rustler::Error::Term(Box::new(format!("Failed to serialize results: {}", e))))
+}
+
+/// Run several independent queries against the same searcher in one call.
+///
+/// Returns one entry per query, in order, each tagged with its `index` and
+/// holding either its top `limit` `hits` or the `error` that query hit. A
+/// failing query does not fail the others.
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn searcher_multi_search(
+    searcher_res: ResourceArc<SearcherResource>,
+    queries: Vec<ResourceArc<QueryResource>>,
+    limit: u64,
+    include_docs: bool,
+) -> NifResult<String> {
+    let searcher = &searcher_res.searcher;
+    let top_docs = TopDocs::with_limit(limit as usize);
+
+    let results: Vec<serde_json::Value> = queries
+        .iter()
+        .enumerate()
+        .map(
+            |(index, query_res)| match searcher.search(&*query_res.query, &top_docs) {
+                Ok(docs) => serde_json::json!({
+                    "index": index,
+                    "hits": scored_hits_to_json(searcher, docs, include_docs),
+                }),
+                Err(e) => serde_json::json!({
+                    "index": index,
+                    "error": format!("Search failed: {}", e),
+                }),
+            },
+        )
+        .collect();
+
+    serde_json::to_string(&results)
+        .map_err(|e| rustler::Error::Term(Box::new(format!("Failed to serialize results: {}", e))))
+}
+
+/// Names of the bytes fields of the searcher's schema, which search results base64-encode
+#[rustler::nif]
+pub fn searcher_bytes_fields(searcher_res: ResourceArc<SearcherResource>) -> Vec<String> {
+    let schema = searcher_res.searcher.schema();
+    schema
+        .fields()
+        .filter(|(_, field_entry)| matches!(field_entry.field_type(), FieldType::Bytes(_)))
+        .map(|(_, field_entry)| field_entry.name().to_string())
+        .collect()
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn searcher_count(
+    searcher_res: ResourceArc<SearcherResource>,
+    query_res: ResourceArc<QueryResource>,
+) -> NifResult<u64> {
+    searcher_res
+        .searcher
+        .search(&*query_res.query, &Count)
+        .map(|count| count as u64)
+        .map_err(|e| rustler::Error::Term(Box::new(format!("Count failed: {}", e))))
+}
+
+/// Collect up to `limit` live matches in `DocAddress` order, starting at the
+/// `(segment_ord, doc_id)` position `start`, without computing scores.
+fn collect_unscored(
+    searcher: &tantivy::Searcher,
+    query: &dyn tantivy::query::Query,
+    start: (usize, DocId),
+    limit: usize,
+) -> tantivy::Result<Vec<DocAddress>> {
+    if limit == 0 {
+        return Ok(Vec::new());
+    }
+
+    let weight = query.weight(EnableScoring::disabled_from_searcher(searcher))?;
+    let (start_segment, start_doc) = start;
+    // `limit` comes from the caller, so never reserve more than the index holds
+    let mut addresses = Vec::with_capacity(limit.min(searcher.num_docs() as usize));
+
+    'segments: for (segment_ord, segment_reader) in searcher
+        .segment_readers()
+        .iter()
+        .enumerate()
+        .skip(start_segment)
+    {
+        let mut scorer = weight.scorer(segment_reader, 1.0)?;
+
+        let mut doc = scorer.doc();
+        if segment_ord == start_segment && doc != TERMINATED && doc < start_doc {
+            doc = scorer.seek(start_doc);
+        }
+
+        while doc != TERMINATED {
+            if !segment_reader.is_deleted(doc) {
+                addresses.push(DocAddress::new(segment_ord as u32, doc));
+                if addresses.len() == limit {
+                    break 'segments;
+                }
+            }
+            doc = scorer.advance();
+        }
+    }
+
+    Ok(addresses)
+}
+
+/// Fetch the one live document whose `field_name` matches the unique key `value`.
+///
+/// The value is coerced like in `query_term`. Returns `{:error, :not_found}`
+/// when nothing matches and an error when the key is not unique. With a
+/// non-empty `include_fields`, only those stored fields are returned.
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn searcher_get_by_term(
+    searcher_res: ResourceArc<SearcherResource>,
+    field_name: String,
+    value: String,
+    include_fields: Vec<String>,
+) -> NifResult<String> {
+    let searcher = &searcher_res.searcher;
+    let schema = searcher.schema();
+    let query = build_term_query(schema, &field_name, &value)?;
+
+    let addresses = collect_unscored(searcher, query.as_ref(), (0, 0), 2)
+        .map_err(|e| rustler::Error::Term(Box::new(format!("Search failed: {}", e))))?;
+    let doc_address = match addresses.as_slice() {
+        [] => return Err(rustler::Error::Term(Box::new(atoms::not_found()))),
+        [doc_address] => *doc_address,
+        _ => {
+            return Err(rustler::Error::Term(Box::new(format!(
+                "Multiple documents match {} = {:?}",
+                field_name, value
+            ))))
+        }
+    };
+
+    let doc: TantivyDocument = searcher
+        .doc(doc_address)
+        .map_err(|e| rustler::Error::Term(Box::new(format!("Failed to load document: {}", e))))?;
+
+    let mut doc_map = serde_json::Map::new();
+    insert_document_fields(&mut doc_map, schema, &doc);
+    if !include_fields.is_empty() {
+        doc_map.retain(|name, _| include_fields.contains(name));
+    }
+
+    serde_json::to_string(&doc_map)
+        .map_err(|e| rustler::Error::Term(Box::new(format!("Failed to serialize document: {}", e))))
+}
+
+/// Return up to `limit` matching documents without computing scores.
+///
+/// Meant for filter-only queries (e.g. timestamp ranges) where BM25 would be
+/// wasted work. Hits come back in `DocAddress` order, not by relevance.
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn searcher_search_unscored(
+    searcher_res: ResourceArc<SearcherResource>,
+    query_res: ResourceArc<QueryResource>,
+    limit: u64,
+    include_docs: bool,
+) -> NifResult<String> {
+    let searcher = &searcher_res.searcher;
+    let addresses = collect_unscored(searcher, query_res.query.as_ref(), (0, 0), limit as usize)
+        .map_err(|e| rustler::Error::Term(Box::new(format!("Search failed: {}", e))))?;
+
+    let mut results = Vec::with_capacity(addresses.len());
+    for doc_address in addresses {
+        let mut doc_map = serde_json::Map::new();
+        doc_map.insert(
+            "segment_ord".to_string(),
+            serde_json::Value::Number(serde_json::Number::from(doc_address.segment_ord)),
+        );
+        doc_map.insert(
+            "doc_id".to_string(),
+            serde_json::Value::Number(serde_json::Number::from(doc_address.doc_id)),
+        );
+        if include_docs {
+            let doc = searcher.doc::<TantivyDocument>(doc_address).map_err(|e| {
+                rustler::Error::Term(Box::new(format!("Failed to load document: {}", e)))
+            })?;
+            insert_document_fields(&mut doc_map, searcher.schema(), &doc);
+        }
+        results.push(serde_json::Value::Object(doc_map));
+    }
+
+    serde_json::to_string(&results)
+        .map_err(|e| rustler::Error::Term(Box::new(format!("Failed to serialize results: {}", e))))
+}
+
+/// Return the next batch of matching documents in `DocAddress` order.
+///
+/// `after` is the `(segment_ord, doc_id)` of the last document of the
+/// previous batch; `nil` starts from the beginning. No scoring is done.
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn searcher_scroll(
+    searcher_res: ResourceArc<SearcherResource>,
+    query_res: ResourceArc<QueryResource>,
+    batch_size: u64,
+    after: Option<(u32, u32)>,
+) -> NifResult<String> {
+    if batch_size == 0 {
+        return Err(rustler::Error::Term(Box::new(
+            "Batch size must be greater than 0".to_string(),
+        )));
+    }
+
+    let searcher = &searcher_res.searcher;
+    let start = match after {
+        Some((segment_ord, doc_id)) => (segment_ord as usize, doc_id.saturating_add(1)),
+        None => (0, 0),
+    };
+
+    let batch_size = batch_size as usize;
+    let addresses = collect_unscored(searcher, query_res.query.as_ref(), start, batch_size)
+        .map_err(|e| rustler::Error::Term(Box::new(format!("Scroll failed: {}", e))))?;
+
+    let mut documents = Vec::with_capacity(addresses.len());
+    for doc_address in &addresses {
+        let doc = searcher.doc::<TantivyDocument>(*doc_address).map_err(|e| {
+            rustler::Error::Term(Box::new(format!("Failed to load document: {}", e)))
+        })?;
+
+        let mut doc_map = serde_json::Map::new();
+        doc_map.insert(
+            "segment_ord".to_string(),
+            serde_json::Value::Number(serde_json::Number::from(doc_address.segment_ord)),
+        );
+        doc_map.insert(
+            "doc_id".to_string(),
+            serde_json::Value::Number(serde_json::Number::from(doc_address.doc_id)),
+        );
+        insert_document_fields(&mut doc_map, searcher.schema(), &doc);
+        documents.push(serde_json::Value::Object(doc_map));
+    }
+
+    // A full batch may have more documents after it; a short one is the last
+    let next_cursor = if addresses.len() == batch_size {
+        addresses
+            .last()
+            .map(|address| serde_json::json!([address.segment_ord, address.doc_id]))
+            .unwrap_or(serde_json::Value::Null)
+    } else {
+        serde_json::Value::Null
+    };
+
+    let response = serde_json::json!({
+        "documents": documents,
+        "next_cursor": next_cursor
+    });
+
+    Ok(response.to_string())
+}
+
+/// Top `limit` hits ordered by a fast field, as `(sort value, address)` pairs
+fn collect_sorted<T: FastValue>(
+    searcher: &tantivy::Searcher,
+    query: &dyn tantivy::query::Query,
+    limit: usize,
+    sort_field: &str,
+    order: Order,
+    to_json: fn(T) -> serde_json::Value,
+) -> tantivy::Result<Vec<(serde_json::Value, DocAddress)>> {
+    let collector = TopDocs::with_limit(limit).order_by_fast_field::<T>(sort_field, order);
+    let hits = searcher.search(query, &collector)?;
+    Ok(hits
+        .into_iter()
+        .map(|(value, doc_address)| (to_json(value), doc_address))
+        .collect())
+}
+
+/// Parse an `"asc"` / `"desc"` sort order
+pub(crate) fn parse_sort_order(order: &str) -> Result<Order, String> {
+    match order {
+        "asc" => Ok(Order::Asc),
+        "desc" => Ok(Order::Desc),
+        other => Err(format!(
+            "Invalid sort order '{}', expected \"asc\" or \"desc\"",
+            other
+        )),
+    }
+}
+
+/// Term for a `search_after` cursor, coerced to the type of the sort field
+fn search_after_cursor_term(
+    field: tantivy::schema::Field,
+    field_type: &FieldType,
+    value: Term,
+) -> NifResult<TantivyTerm> {
+    let invalid = || {
+        rustler::Error::Term(Box::new(format!(
+            "Cursor value does not match the sort field type: {:?}",
+            value
+        )))
+    };
+    let term = match field_type {
+        FieldType::U64(_) => {
+            TantivyTerm::from_field_u64(field, value.decode().map_err(|_| invalid())?)
+        }
+        FieldType::I64(_) => {
+            TantivyTerm::from_field_i64(field, value.decode().map_err(|_| invalid())?)
+        }
+        FieldType::F64(_) => {
+            let cursor = value
+                .decode::<f64>()
+                .or_else(|_| value.decode::<i64>().map(|v| v as f64))
+                .map_err(|_| invalid())?;
+            TantivyTerm::from_field_f64(field, cursor)
+        }
+        FieldType::Date(_) => {
+            let micros: i64 = value.decode().map_err(|_| invalid())?;
+            TantivyTerm::from_field_date(field, DateTime::from_timestamp_micros(micros))
+        }
+        _ => return Err(invalid()),
+    };
+    Ok(term)
+}
+
+/// Return the `limit` hits that come strictly after `after_value` when sorting on a fast field.
+///
+/// `sort_field` must be a numeric or date fast field and `order` is `"asc"` or `"desc"`.
+/// Unlike an offset, the cursor is a range filter, so every page costs the same. Hits
+/// sharing the cursor value are skipped, so sort on a field with unique values. Dates are
+/// sorted and returned as microseconds since the epoch. Documents without a value for the
+/// sort field are never returned.
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn searcher_search_after<'a>(
+    searcher_res: ResourceArc<SearcherResource>,
+    query_res: ResourceArc<QueryResource>,
+    limit: u64,
+    sort_field: String,
+    order: String,
+    after_value: Option<Term<'a>>,
+) -> NifResult<String> {
+    if limit == 0 {
+        return Err(rustler::Error::Term(Box::new(
+            "Limit must be greater than 0".to_string(),
+        )));
+    }
+
+    let searcher = &searcher_res.searcher;
+    let schema = searcher.schema();
+    let field = schema
+        .get_field(&sort_field)
+        .map_err(|_| rustler::Error::Term(Box::new(format!("Field '{}' not found", sort_field))))?;
+    let field_entry = schema.get_field_entry(field);
+    let numeric_or_date = matches!(
+        field_entry.field_type(),
+        FieldType::U64(_) | FieldType::I64(_) | FieldType::F64(_) | FieldType::Date(_)
+    );
+    if !field_entry.is_fast() || !numeric_or_date {
+        return Err(rustler::Error::Term(Box::new(format!(
+            "Field '{}' must be a fast numeric or date field to sort on",
+            sort_field
+        ))));
+    }
+
+    let order = parse_sort_order(&order).map_err(|e| rustler::Error::Term(Box::new(e)))?;
+
+    let cursor_filter: Box<dyn tantivy::query::Query> = match after_value {
+        Some(value) => {
+            let cursor = search_after_cursor_term(field, field_entry.field_type(), value)?;
+            let (lower, upper) = match order {
+                Order::Asc => (Bound::Excluded(cursor), Bound::Unbounded),
+                Order::Desc => (Bound::Unbounded, Bound::Excluded(cursor)),
+            };
+            Box::new(RangeQuery::new(lower, upper))
+        }
+        None => Box::new(ExistsQuery::new(sort_field.clone(), false)),
+    };
+    let query = BooleanQuery::new(vec![
+        (Occur::Must, query_res.query.box_clone()),
+        (Occur::Must, cursor_filter),
+    ]);
+
+    let limit = limit as usize;
+    let hits = match field_entry.field_type() {
+        FieldType::U64(_) => {
+            collect_sorted::<u64>(searcher, &query, limit, &sort_field, order, Into::into)
+        }
+        FieldType::I64(_) => {
+            collect_sorted::<i64>(searcher, &query, limit, &sort_field, order, Into::into)
+        }
+        FieldType::F64(_) => {
+            collect_sorted::<f64>(searcher, &query, limit, &sort_field, order, Into::into)
+        }
+        _ => collect_sorted::<DateTime>(searcher, &query, limit, &sort_field, order, |date| {
+            date.into_timestamp_micros().into()
+        }),
+    }
+    .map_err(|e| rustler::Error::Term(Box::new(format!("Search failed: {}", e))))?;
+
+    let mut documents = Vec::with_capacity(hits.len());
+    for (sort_value, doc_address) in &hits {
+        let doc = searcher.doc::<TantivyDocument>(*doc_address).map_err(|e| {
+            rustler::Error::Term(Box::new(format!("Failed to load document: {}", e)))
+        })?;
+
+        let mut doc_map = serde_json::Map::new();
+        doc_map.insert("sort_value".to_string(), sort_value.clone());
+        insert_document_fields(&mut doc_map, schema, &doc);
+        documents.push(serde_json::Value::Object(doc_map));
+    }
+
+    // Same convention as scroll: only a full page may have more hits after it
+    let next_cursor = match hits.last() {
+        Some((sort_value, _)) if hits.len() == limit => sort_value.clone(),
+        _ => serde_json::Value::Null,
+    };
+
+    let response = serde_json::json!({
+        "documents": documents,
+        "next_cursor": next_cursor
+    });
+
+    Ok(response.to_string())
+}
+
+/// Search returning top documents and facet counts from a single pass.
+///
+/// Facet counts are grouped by requested path, each mapping child facets to counts.
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn searcher_search_with_facets(
+    searcher_res: ResourceArc<SearcherResource>,
+    query_res: ResourceArc<QueryResource>,
+    limit: u64,
+    facet_field: String,
+    facet_paths: Vec<String>,
+) -> NifResult<String> {
+    let searcher = &searcher_res.searcher;
+
+    let field = searcher.schema().get_field(&facet_field).map_err(|_| {
+        rustler::Error::Term(Box::new(format!("Field '{}' not found", facet_field)))
+    })?;
+    if !matches!(
+        searcher.schema().get_field_entry(field).field_type(),
+        tantivy::schema::FieldType::Facet(_)
+    ) {
+        return Err(rustler::Error::Term(Box::new(format!(
+            "Field '{}' is not a facet field",
+            facet_field
+        ))));
+    }
+
+    let mut facets: Vec<Facet> = Vec::new();
+    for facet_path in &facet_paths {
+        let facet = Facet::from_text(facet_path).map_err(|e| {
+            rustler::Error::Term(Box::new(format!(
+                "Invalid facet path '{}': {}",
+                facet_path, e
+            )))
+        })?;
+        if facets.contains(&facet) {
+            continue;
+        }
+        // FacetCollector panics on nested facets, so reject them up front
+        if let Some(other) = facets
+            .iter()
+            .find(|other| other.is_prefix_of(&facet) || facet.is_prefix_of(other))
+        {
+            return Err(rustler::Error::Term(Box::new(format!(
+                "Facet paths '{}' and '{}' overlap",
+                other, facet
+            ))));
+        }
+        facets.push(facet);
+    }
+    if facets.is_empty() {
+        facets.push(Facet::root());
+    }
+
+    let mut facet_collector = FacetCollector::for_field(&facet_field);
+    for facet in &facets {
+        facet_collector.add_facet(facet.clone());
+    }
+
+    let mut multi_collector = MultiCollector::new();
+    let top_docs_handle = multi_collector.add_collector(TopDocs::with_limit(limit as usize));
+    let facet_handle = multi_collector.add_collector(facet_collector);
+
+    let mut fruits = searcher
+        .search(&*query_res.query, &multi_collector)
+        .map_err(|e| rustler::Error::Term(Box::new(format!("Search failed: {}", e))))?;
+    let top_docs = top_docs_handle.extract(&mut fruits);
+    let facet_counts = facet_handle.extract(&mut fruits);
+
+    let hits = scored_hits_to_json(searcher, top_docs, true);
+
+    let mut facet_results = serde_json::Map::new();
+    for facet in &facets {
+        let children: serde_json::Map<String, serde_json::Value> = facet_counts
+            .get(facet.clone())
+            .map(|(child, count)| (child.to_string(), serde_json::Value::from(count)))
+            .collect();
+        facet_results.insert(facet.to_string(), serde_json::Value::Object(children));
+    }
+
+    let response = serde_json::json!({
+        "hits": hits,
+        "facets": facet_results
+    });
+
+    Ok(response.to_string())
+}
+
+/// Document and segment statistics of a searcher
+#[rustler::nif]
+pub fn searcher_stats(searcher_res: ResourceArc<SearcherResource>) -> NifResult<String> {
+    let searcher = &searcher_res.searcher;
+
+    let segments: Vec<serde_json::Value> = searcher
+        .segment_readers()
+        .iter()
+        .map(|segment_reader| {
+            serde_json::json!({
+                "segment_id": segment_reader.segment_id().uuid_string(),
+                "doc_count": segment_reader.num_docs(),
+                "deleted": segment_reader.num_deleted_docs()
+            })
+        })
+        .collect();
+
+    let stats = serde_json::json!({
+        "num_docs": searcher.num_docs(),
+        "segment_count": segments.len(),
+        "segments": segments
+    });
+
+    Ok(stats.to_string())
+}
+
+/// Fast field column of a segment whose values are read as JSON
+enum FastFieldColumn {
+    U64(Column<u64>),
+    I64(Column<i64>),
+    F64(Column<f64>),
+    Bool(Column<bool>),
+    Date(Column<DateTime>),
+    Str(StrColumn),
+    Missing,
+}
+
+impl FastFieldColumn {
+    fn open(segment_reader: &SegmentReader, field_name: &str) -> tantivy::Result<Self> {
+        let field = segment_reader.schema().get_field(field_name)?;
+        let fast_fields = segment_reader.fast_fields();
+        let column = match segment_reader.schema().get_field_entry(field).field_type() {
+            FieldType::U64(_) => FastFieldColumn::U64(fast_fields.u64(field_name)?),
+            FieldType::I64(_) => FastFieldColumn::I64(fast_fields.i64(field_name)?),
+            FieldType::F64(_) => FastFieldColumn::F64(fast_fields.f64(field_name)?),
+            FieldType::Bool(_) => FastFieldColumn::Bool(fast_fields.bool(field_name)?),
+            FieldType::Date(_) => FastFieldColumn::Date(fast_fields.date(field_name)?),
+            FieldType::Str(_) => fast_fields
+                .str(field_name)?
+                .map(FastFieldColumn::Str)
+                .unwrap_or(FastFieldColumn::Missing),
+            _ => FastFieldColumn::Missing,
+        };
+        Ok(column)
+    }
+
+    /// First value of the document, as a JSON value
+    fn first_value(&self, doc: DocId) -> serde_json::Value {
+        let value = match self {
+            FastFieldColumn::U64(column) => column.first(doc).map(serde_json::Value::from),
+            FastFieldColumn::I64(column) => column.first(doc).map(serde_json::Value::from),
+            FastFieldColumn::F64(column) => column.first(doc).map(serde_json::Value::from),
+            FastFieldColumn::Bool(column) => column.first(doc).map(serde_json::Value::from),
+            FastFieldColumn::Date(column) => column
+                .first(doc)
+                .map(|date| serde_json::Value::from(date.into_timestamp_micros())),
+            FastFieldColumn::Str(column) => column.term_ords(doc).next().and_then(|ord| {
+                let mut text = String::new();
+                match column.ord_to_str(ord, &mut text) {
+                    Ok(true) => Some(serde_json::Value::String(text)),
+                    _ => None,
+                }
+            }),
+            FastFieldColumn::Missing => None,
+        };
+        value.unwrap_or(serde_json::Value::Null)
+    }
+}
+
+/// Whether a field is a fast field that `FastFieldColumn` can read
+fn is_fast_value_field(field_entry: &FieldEntry) -> bool {
+    let supported_type = matches!(
+        field_entry.field_type(),
+        FieldType::U64(_)
+            | FieldType::I64(_)
+            | FieldType::F64(_)
+            | FieldType::Bool(_)
+            | FieldType::Date(_)
+            | FieldType::Str(_)
+    );
+    field_entry.is_fast() && supported_type
+}
+
+/// Best document per distinct collapse key, keyed by the JSON encoding of the key
+type CollapsedHits = HashMap<String, (Score, DocAddress, serde_json::Value)>;
+
+/// Collector keeping only the highest-scoring document per value of a fast field
+struct CollapseCollector {
+    field_name: String,
+}
+
+struct CollapseSegmentCollector {
+    segment_ord: SegmentOrdinal,
+    column: FastFieldColumn,
+    best: CollapsedHits,
+}
+
+fn keep_best(
+    best: &mut CollapsedHits,
+    score: Score,
+    doc_address: DocAddress,
+    key: serde_json::Value,
+) {
+    let entry_key = key.to_string();
+    match best.get(&entry_key) {
+        Some((best_score, _, _)) if *best_score >= score => {}
+        _ => {
+            best.insert(entry_key, (score, doc_address, key));
+        }
+    }
+}
+
+impl Collector for CollapseCollector {
+    type Fruit = CollapsedHits;
+    type Child = CollapseSegmentCollector;
+
+    fn for_segment(
+        &self,
+        segment_local_id: SegmentOrdinal,
+        segment: &SegmentReader,
+    ) -> tantivy::Result<Self::Child> {
+        Ok(CollapseSegmentCollector {
+            segment_ord: segment_local_id,
+            column: FastFieldColumn::open(segment, &self.field_name)?,
+            best: HashMap::new(),
+        })
+    }
+
+    fn requires_scoring(&self) -> bool {
+        true
+    }
+
+    fn merge_fruits(&self, segment_fruits: Vec<CollapsedHits>) -> tantivy::Result<CollapsedHits> {
+        let mut merged = HashMap::new();
+        for fruit in segment_fruits {
+            for (_, (score, doc_address, key)) in fruit {
+                keep_best(&mut merged, score, doc_address, key);
+            }
+        }
+        Ok(merged)
+    }
+}
+
+impl SegmentCollector for CollapseSegmentCollector {
+    type Fruit = CollapsedHits;
+
+    fn collect(&mut self, doc: DocId, score: Score) {
+        let key = self.column.first_value(doc);
+        keep_best(
+            &mut self.best,
+            score,
+            DocAddress::new(self.segment_ord, doc),
+            key,
+        );
+    }
+
+    fn harvest(self) -> CollapsedHits {
+        self.best
+    }
+}
+
+/// Search keeping only the highest-scoring document per distinct value of a fast field.
+///
+/// Documents without a value for the collapse field are collapsed together under `null`.
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn searcher_search_collapsed(
+    searcher_res: ResourceArc<SearcherResource>,
+    query_res: ResourceArc<QueryResource>,
+    limit: u64,
+    collapse_field: String,
+) -> NifResult<String> {
+    let searcher = &searcher_res.searcher;
+    let schema = searcher.schema();
+
+    let field = schema.get_field(&collapse_field).map_err(|_| {
+        rustler::Error::Term(Box::new(format!("Field '{}' not found", collapse_field)))
+    })?;
+    if !is_fast_value_field(schema.get_field_entry(field)) {
+        return Err(rustler::Error::Term(Box::new(format!(
+            "Field '{}' must be a fast text, numeric, boolean or date field to collapse on",
+            collapse_field
+        ))));
+    }
+
+    let collector = CollapseCollector {
+        field_name: collapse_field,
+    };
+    let collapsed = searcher
+        .search(&*query_res.query, &collector)
+        .map_err(|e| rustler::Error::Term(Box::new(format!("Search failed: {}", e))))?;
+
+    let mut best_hits: Vec<(Score, DocAddress, serde_json::Value)> =
+        collapsed.into_values().collect();
+    best_hits.sort_by(|a, b| {
+        b.0.partial_cmp(&a.0)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.1.cmp(&b.1))
+    });
+    best_hits.truncate(limit as usize);
+
+    let mut results = Vec::with_capacity(best_hits.len());
+    for (score, doc_address, key) in best_hits {
+        for mut hit in scored_hits_to_json(searcher, vec![(score, doc_address)], true) {
+            if let serde_json::Value::Object(doc_map) = &mut hit {
+                doc_map.insert("collapse_key".to_string(), key.clone());
+            }
+            results.push(hit);
+        }
+    }
+
+    serde_json::to_string(&results)
+        .map_err(|e| rustler::Error::Term(Box::new(format!("Failed to serialize results: {}", e))))
+}
+
+/// Read the first fast field value of each `(segment_ord, doc_id)` address.
+///
+/// Returns a JSON array in the order of the addresses; documents without a
+/// value yield `null`. Much cheaper than loading stored documents when only
+/// one column is needed, e.g. for re-ranking.
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn searcher_fast_field_values(
+    searcher_res: ResourceArc<SearcherResource>,
+    field_name: String,
+    doc_addresses: Vec<(u32, u32)>,
+) -> NifResult<String> {
+    let searcher = &searcher_res.searcher;
+    let schema = searcher.schema();
+
+    let field = schema
+        .get_field(&field_name)
+        .map_err(|_| rustler::Error::Term(Box::new(format!("Field '{}' not found", field_name))))?;
+    if !is_fast_value_field(schema.get_field_entry(field)) {
+        return Err(rustler::Error::Term(Box::new(format!(
+            "Field '{}' must be a fast text, numeric, boolean or date field",
+            field_name
+        ))));
+    }
+
+    let segment_readers = searcher.segment_readers();
+    let mut columns: Vec<Option<FastFieldColumn>> =
+        (0..segment_readers.len()).map(|_| None).collect();
+
+    let mut values = Vec::with_capacity(doc_addresses.len());
+    for (segment_ord, doc_id) in doc_addresses {
+        let segment_reader = segment_readers.get(segment_ord as usize).ok_or_else(|| {
+            rustler::Error::Term(Box::new(format!(
+                "Invalid segment ordinal: {}",
+                segment_ord
+            )))
+        })?;
+        if doc_id >= segment_reader.max_doc() {
+            return Err(rustler::Error::Term(Box::new(format!(
+                "Invalid doc id {} for segment {}",
+                doc_id, segment_ord
+            ))));
+        }
+
+        let slot = &mut columns[segment_ord as usize];
+        if slot.is_none() {
+            let column = FastFieldColumn::open(segment_reader, &field_name).map_err(|e| {
+                rustler::Error::Term(Box::new(format!(
+                    "Failed to open fast field '{}': {}",
+                    field_name, e
+                )))
+            })?;
+            *slot = Some(column);
+        }
+        if let Some(column) = slot {
+            values.push(column.first_value(doc_id));
+        }
+    }
+
+    serde_json::to_string(&values)
+        .map_err(|e| rustler::Error::Term(Box::new(format!("Failed to serialize values: {}", e))))
+}
+
+/// Min and max of a numeric or date fast field across all segments.
+///
+/// Read from the column statistics, so no document is visited. Values of deleted
+/// documents still count until their segment is merged. Returns a JSON object
+/// with `min` and `max` (dates as timestamps in microseconds), or `null` when
+/// no document has a value.
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn searcher_field_range(
+    searcher_res: ResourceArc<SearcherResource>,
+    field_name: String,
+) -> NifResult<String> {
+    let searcher = &searcher_res.searcher;
+    let schema = searcher.schema();
+
+    let field = schema
+        .get_field(&field_name)
+        .map_err(|_| rustler::Error::Term(Box::new(format!("Field '{}' not found", field_name))))?;
+    let field_entry = schema.get_field_entry(field);
+    let numeric_or_date = matches!(
+        field_entry.field_type(),
+        FieldType::U64(_) | FieldType::I64(_) | FieldType::F64(_) | FieldType::Date(_)
+    );
+    if !field_entry.is_fast() || !numeric_or_date {
+        return Err(rustler::Error::Term(Box::new(format!(
+            "Field '{}' must be a fast numeric or date field",
+            field_name
+        ))));
+    }
+
+    let to_json = |range: Option<(serde_json::Value, serde_json::Value)>| match range {
+        Some((min, max)) => serde_json::json!({ "min": min, "max": max }),
+        None => serde_json::Value::Null,
+    };
+    let range = match field_entry.field_type() {
+        FieldType::U64(_) => fast_field_min_max(searcher, |s| s.fast_fields().u64(&field_name))
+            .map(|r| to_json(r.map(|(min, max)| (min.into(), max.into())))),
+        FieldType::I64(_) => fast_field_min_max(searcher, |s| s.fast_fields().i64(&field_name))
+            .map(|r| to_json(r.map(|(min, max)| (min.into(), max.into())))),
+        FieldType::F64(_) => fast_field_min_max(searcher, |s| s.fast_fields().f64(&field_name))
+            .map(|r| to_json(r.map(|(min, max)| (min.into(), max.into())))),
+        _ => fast_field_min_max(searcher, |s| s.fast_fields().date(&field_name)).map(|r| {
+            to_json(r.map(|(min, max)| {
+                (
+                    min.into_timestamp_micros().into(),
+                    max.into_timestamp_micros().into(),
+                )
+            }))
+        }),
+    }
+    .map_err(|e| {
+        rustler::Error::Term(Box::new(format!(
+            "Failed to read fast field '{}': {}",
+            field_name, e
+        )))
+    })?;
+
+    Ok(range.to_string())
+}
+
+/// Combine the column statistics of every segment, `None` when no segment has a value
+fn fast_field_min_max<T, F>(
+    searcher: &tantivy::Searcher,
+    open_column: F,
+) -> tantivy::Result<Option<(T, T)>>
+where
+    T: PartialOrd + Copy + std::fmt::Debug + Send + Sync + 'static,
+    F: Fn(&SegmentReader) -> tantivy::Result<Column<T>>,
+{
+    let mut range: Option<(T, T)> = None;
+    for segment_reader in searcher.segment_readers() {
+        let column = open_column(segment_reader)?;
+        if column.values.num_vals() == 0 {
+            continue;
+        }
+        let (min, max) = (column.min_value(), column.max_value());
+        range = Some(match range {
+            Some((lo, hi)) => (
+                if min < lo { min } else { lo },
+                if max > hi { max } else { hi },
+            ),
+            None => (min, max),
+        });
+    }
+    Ok(range)
+}
+
+/// Build highlighted snippets for several fields of one document.
+///
+/// Returns a JSON object mapping each field name to its HTML snippet. Fields
+/// that are not stored text, or whose snippet has no matching fragment, are
+/// left out, so result pages need one call per hit instead of one per field.
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn searcher_highlight_multi(
+    searcher_res: ResourceArc<SearcherResource>,
+    query_res: ResourceArc<QueryResource>,
+    field_names: Vec<String>,
+    doc_address: (u32, u32),
+    max_chars: usize,
+) -> NifResult<String> {
+    let searcher = &searcher_res.searcher;
+    let schema = searcher.schema();
+    let doc = load_doc_at(searcher, doc_address)?;
+
+    let mut highlights = serde_json::Map::new();
+    for field_name in field_names {
+        let field = schema.get_field(&field_name).map_err(|_| {
+            rustler::Error::Term(Box::new(format!("Field '{}' not found", field_name)))
+        })?;
+        let field_entry = schema.get_field_entry(field);
+        if !field_entry.is_stored() || !matches!(field_entry.field_type(), FieldType::Str(_)) {
+            continue;
+        }
+
+        let mut generator =
+            match SnippetGenerator::create(searcher, query_res.query.as_ref(), field) {
+                Ok(generator) => generator,
+                Err(_) => continue,
+            };
+        generator.set_max_num_chars(max_chars);
+
+        let snippet = generator.snippet_from_doc(&doc);
+        if !snippet.is_empty() {
+            highlights.insert(field_name, serde_json::Value::String(snippet.to_html()));
+        }
+    }
+
+    serde_json::to_string(&highlights).map_err(|e| {
+        rustler::Error::Term(Box::new(format!("Failed to serialize highlights: {}", e)))
+    })
+}
+
+/// Byte offsets of the tokens of a stored text field that match the query terms.
+///
+/// The field is tokenized with its indexing tokenizer, as `SnippetGenerator` does,
+/// and every token equal to a term of the query on that field is returned as a
+/// `(start, end)` pair. Several values of the field are joined with a space.
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn searcher_match_offsets(
+    searcher_res: ResourceArc<SearcherResource>,
+    query_res: ResourceArc<QueryResource>,
+    field_name: String,
+    doc_address: (u32, u32),
+) -> NifResult<Vec<(usize, usize)>> {
+    let searcher = &searcher_res.searcher;
+    let schema = searcher.schema();
+    let field = schema
+        .get_field(&field_name)
+        .map_err(|_| rustler::Error::Term(Box::new(format!("Field '{}' not found", field_name))))?;
+    let field_entry = schema.get_field_entry(field);
+    if !field_entry.is_stored() || !matches!(field_entry.field_type(), FieldType::Str(_)) {
+        return Err(rustler::Error::Term(Box::new(format!(
+            "Field '{}' must be a stored text field",
+            field_name
+        ))));
+    }
+
+    let mut terms: HashSet<String> = HashSet::new();
+    query_res.query.query_terms(&mut |term, _| {
+        if term.field() == field {
+            if let Some(text) = term.value().as_str() {
+                terms.insert(text.to_string());
+            }
+        }
+    });
+
+    let doc = load_doc_at(searcher, doc_address)?;
+    let text = doc
+        .get_all(field)
+        .filter_map(|value| value.as_str())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let mut tokenizer = searcher
+        .index()
+        .tokenizer_for_field(field)
+        .map_err(|e| rustler::Error::Term(Box::new(format!("Failed to load tokenizer: {}", e))))?;
+    let mut offsets = Vec::new();
+    if !terms.is_empty() {
+        let mut token_stream = tokenizer.token_stream(&text);
+        while let Some(token) = token_stream.next() {
+            if terms.contains(&token.text) {
+                offsets.push((token.offset_from, token.offset_to));
+            }
+        }
+    }
+
+    Ok(offsets)
+}
+
+/// Load the stored document at a `(segment_ord, doc_id)` address, checking both parts
+fn load_doc_at(
+    searcher: &tantivy::Searcher,
+    doc_address: (u32, u32),
+) -> NifResult<TantivyDocument> {
+    let (segment_ord, doc_id) = doc_address;
+
+    let segment_reader = searcher
+        .segment_readers()
+        .get(segment_ord as usize)
+        .ok_or_else(|| {
+            rustler::Error::Term(Box::new(format!(
+                "Invalid segment ordinal: {}",
+                segment_ord
+            )))
+        })?;
+    if doc_id >= segment_reader.max_doc() {
+        return Err(rustler::Error::Term(Box::new(format!(
+            "Invalid doc id {} for segment {}",
+            doc_id, segment_ord
+        ))));
+    }
+
+    searcher
+        .doc(DocAddress::new(segment_ord, doc_id))
+        .map_err(|e| rustler::Error::Term(Box::new(format!("Failed to load document: {}", e))))
+}
+
+/// Sum the document frequencies of the terms of `field_name` starting with `prefix`.
+///
+/// At most `per_segment_limit` matching terms are read from each segment, in
+/// lexicographic order. Frequencies still count deleted documents until their
+/// segments are merged, like in tantivy itself.
+fn prefixed_term_frequencies(
+    searcher: &tantivy::Searcher,
+    field_name: &str,
+    prefix: &str,
+    per_segment_limit: usize,
+) -> NifResult<BTreeMap<Vec<u8>, u64>> {
+    let schema = searcher.schema();
+    let field = schema
+        .get_field(field_name)
+        .map_err(|_| rustler::Error::Term(Box::new(format!("Field '{}' not found", field_name))))?;
+    let field_entry = schema.get_field_entry(field);
+    if !field_entry.is_indexed() || !matches!(field_entry.field_type(), FieldType::Str(_)) {
+        return Err(rustler::Error::Term(Box::new(format!(
+            "Field '{}' must be an indexed text field",
+            field_name
+        ))));
+    }
+
+    let prefix = prefix.as_bytes();
+    let mut terms: BTreeMap<Vec<u8>, u64> = BTreeMap::new();
+    for segment_reader in searcher.segment_readers() {
+        let inverted_index = segment_reader.inverted_index(field).map_err(|e| {
+            rustler::Error::Term(Box::new(format!("Failed to open inverted index: {}", e)))
+        })?;
+        let mut stream = inverted_index
+            .terms()
+            .range()
+            .ge(prefix)
+            .into_stream()
+            .map_err(|e| {
+                rustler::Error::Term(Box::new(format!("Failed to stream terms: {}", e)))
+            })?;
+
+        let mut taken = 0;
+        while taken < per_segment_limit && stream.advance() {
+            if !stream.key().starts_with(prefix) {
+                break;
+            }
+            *terms.entry(stream.key().to_vec()).or_insert(0) += stream.value().doc_freq as u64;
+            taken += 1;
+        }
+    }
+
+    Ok(terms)
+}
+
+fn term_frequencies_to_json<I>(terms: I) -> NifResult<String>
+where
+    I: IntoIterator<Item = (Vec<u8>, u64)>,
+{
+    let terms: Vec<serde_json::Value> = terms
+        .into_iter()
+        .map(|(term, doc_freq)| {
+            serde_json::json!({
+                "term": String::from_utf8_lossy(&term),
+                "doc_freq": doc_freq,
+            })
+        })
+        .collect();
+
+    serde_json::to_string(&terms)
+        .map_err(|e| rustler::Error::Term(Box::new(format!("Failed to serialize terms: {}", e))))
+}
+
+/// List the distinct terms of a text field, optionally restricted to a prefix.
+///
+/// Terms are streamed from each segment's term dictionary and merged in
+/// lexicographic order, with `doc_freq` summed across segments.
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn searcher_field_terms(
+    searcher_res: ResourceArc<SearcherResource>,
+    field_name: String,
+    prefix: String,
+    limit: usize,
+) -> NifResult<String> {
+    // Any of the first `limit` terms overall is among the first `limit` of its segment
+    let terms = prefixed_term_frequencies(&searcher_res.searcher, &field_name, &prefix, limit)?;
+    term_frequencies_to_json(terms.into_iter().take(limit))
+}
+
+/// Complete `prefix` with the most frequent terms of a text field.
+///
+/// Walks every matching term of the term dictionary, so it is much cheaper
+/// than a wildcard query, and returns them by descending `doc_freq` (ties in
+/// lexicographic order).
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn searcher_autocomplete(
+    searcher_res: ResourceArc<SearcherResource>,
+    field_name: String,
+    prefix: String,
+    limit: usize,
+) -> NifResult<String> {
+    let terms =
+        prefixed_term_frequencies(&searcher_res.searcher, &field_name, &prefix, usize::MAX)?;
+
+    let mut completions: Vec<(Vec<u8>, u64)> = terms.into_iter().collect();
+    // Stable sort keeps the lexicographic order among terms of equal frequency
+    completions.sort_by(|a, b| b.1.cmp(&a.1));
+    completions.truncate(limit);
+
+    term_frequencies_to_json(completions)
+}
+
+/// Suggest dictionary terms within `max_distance` edits of `term` ("did you mean").
+///
+/// `term` is normalized like a term query, then a Levenshtein automaton walks
+/// each segment's term dictionary so only candidate terms are visited.
+/// Suggestions are ordered by descending `doc_freq`, then by distance and
+/// lexicographically. The input term itself is included when it exists.
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn searcher_suggest(
+    searcher_res: ResourceArc<SearcherResource>,
+    field_name: String,
+    term: String,
+    max_distance: u8,
+    limit: usize,
+) -> NifResult<String> {
+    let searcher = &searcher_res.searcher;
+
+    if max_distance > 2 {
+        return Err(rustler::Error::Term(Box::new(format!(
+            "Max distance must be between 0 and 2, got {}",
+            max_distance
+        ))));
+    }
+
+    let schema = searcher.schema();
+    let field = schema
+        .get_field(&field_name)
+        .map_err(|_| rustler::Error::Term(Box::new(format!("Field '{}' not found", field_name))))?;
+    let field_entry = schema.get_field_entry(field);
+    if !field_entry.is_indexed() || !matches!(field_entry.field_type(), FieldType::Str(_)) {
+        return Err(rustler::Error::Term(Box::new(format!(
+            "Field '{}' must be an indexed text field",
+            field_name
+        ))));
+    }
+
+    // Normalize the input exactly like a term query would
+    let query = build_term_query(schema, &field_name, &term)?;
+    let mut terms = Vec::new();
+    query.query_terms(&mut |term, _| terms.push(term.clone()));
+    let normalized = match terms.as_slice() {
+        [query_term] => query_term.value().as_str().unwrap_or_default().to_string(),
+        _ => {
+            return Err(rustler::Error::Term(Box::new(format!(
+                "Suggestions need a single term, got {:?}",
+                term
+            ))))
+        }
+    };
+
+    let dfa = LevenshteinAutomatonBuilder::new(max_distance, true).build_dfa(&normalized);
+    let automaton = LevenshteinDfa(dfa);
+
+    let mut doc_freqs: BTreeMap<Vec<u8>, u64> = BTreeMap::new();
+    for segment_reader in searcher.segment_readers() {
+        let inverted_index = segment_reader.inverted_index(field).map_err(|e| {
+            rustler::Error::Term(Box::new(format!("Failed to open inverted index: {}", e)))
+        })?;
+        let mut stream = inverted_index
+            .terms()
+            .search(&automaton)
+            .into_stream()
+            .map_err(|e| {
+                rustler::Error::Term(Box::new(format!("Failed to stream terms: {}", e)))
+            })?;
+
+        while stream.advance() {
+            *doc_freqs.entry(stream.key().to_vec()).or_insert(0) += stream.value().doc_freq as u64;
+        }
+    }
+
+    let mut suggestions: Vec<(Vec<u8>, u64, u8)> = doc_freqs
+        .into_iter()
+        .map(|(term, doc_freq)| {
+            let distance = automaton.0.eval(&term).to_u8();
+            (term, doc_freq, distance)
+        })
+        .collect();
+    // Stable sort keeps the lexicographic order among otherwise equal suggestions
+    suggestions.sort_by(|a, b| b.1.cmp(&a.1).then(a.2.cmp(&b.2)));
+    suggestions.truncate(limit);
+
+    let suggestions: Vec<serde_json::Value> = suggestions
+        .into_iter()
+        .map(|(term, doc_freq, distance)| {
+            serde_json::json!({
+                "term": String::from_utf8_lossy(&term),
+                "doc_freq": doc_freq,
+                "distance": distance,
+            })
+        })
+        .collect();
+
+    serde_json::to_string(&suggestions).map_err(|e| {
+        rustler::Error::Term(Box::new(format!("Failed to serialize suggestions: {}", e)))
+    })
+}
+
+/// Levenshtein DFA usable to search a term dictionary
+struct LevenshteinDfa(DFA);
+
+impl Automaton for LevenshteinDfa {
+    type State = u32;
+
+    fn start(&self) -> u32 {
+        self.0.initial_state()
+    }
+
+    fn is_match(&self, state: &u32) -> bool {
+        matches!(self.0.distance(*state), Distance::Exact(_))
+    }
+
+    fn can_match(&self, state: &u32) -> bool {
+        *state != SINK_STATE
+    }
+
+    fn accept(&self, state: &u32, byte: u8) -> u32 {
+        self.0.transition(*state, byte)
+    }
+}
+
+/// Corpus statistics for a single term: the inputs to IDF and BM25.
+///
+/// `doc_freq` counts the documents containing the term and `total_term_freq`
+/// its occurrences across all of them. Both include documents that are
+/// deleted but not yet merged away, matching what tantivy scores with.
+/// Fields indexed without term frequencies report `total_term_freq` as null.
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn searcher_term_stats(
+    searcher_res: ResourceArc<SearcherResource>,
+    field_name: String,
+    term_value: String,
+) -> NifResult<String> {
+    let searcher = &searcher_res.searcher;
+
+    // Parse and normalize the value exactly like a term query would
+    let query = build_term_query(searcher.schema(), &field_name, &term_value)?;
+    let mut terms = Vec::new();
+    query.query_terms(&mut |term, _| terms.push(term.clone()));
+    let term = match terms.as_slice() {
+        [term] => term.clone(),
+        _ => {
+            return Err(rustler::Error::Term(Box::new(format!(
+                "Term statistics need a single term, got {:?}",
+                term_value
+            ))))
+        }
+    };
+
+    let doc_freq = searcher
+        .doc_freq(&term)
+        .map_err(|e| rustler::Error::Term(Box::new(format!("Failed to read doc freq: {}", e))))?;
+
+    let has_freqs = searcher
+        .schema()
+        .get_field_entry(term.field())
+        .field_type()
+        .get_index_record_option()
+        .is_some_and(|option| option.has_freq());
+    if !has_freqs {
+        let stats = serde_json::json!({
+            "doc_freq": doc_freq,
+            "total_term_freq": null,
+        });
+        return Ok(stats.to_string());
+    }
+
+    let mut total_term_freq: u64 = 0;
+    for segment_reader in searcher.segment_readers() {
+        let inverted_index = segment_reader.inverted_index(term.field()).map_err(|e| {
+            rustler::Error::Term(Box::new(format!("Failed to open inverted index: {}", e)))
+        })?;
+        let postings = inverted_index
+            .read_postings(&term, IndexRecordOption::WithFreqs)
+            .map_err(|e| {
+                rustler::Error::Term(Box::new(format!("Failed to read postings: {}", e)))
+            })?;
+        if let Some(mut postings) = postings {
+            while postings.doc() != TERMINATED {
+                total_term_freq += postings.term_freq() as u64;
+                postings.advance();
+            }
+        }
+    }
+
+    let stats = serde_json::json!({
+        "doc_freq": doc_freq,
+        "total_term_freq": total_term_freq,
+    });
+    Ok(stats.to_string())
+}
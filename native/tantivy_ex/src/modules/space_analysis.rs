@@ -2,7 +2,8 @@ use rustler::{Error, NifResult, ResourceArc};
 use serde_json;
 use std::collections::{BTreeMap, HashMap};
 use std::sync::{Arc, Mutex};
-use tantivy::{Index, Segment};
+use tantivy::index::SegmentId;
+use tantivy::{Index, IndexReader, ReloadPolicy, Segment};
 
 use crate::modules::resources::IndexResource;
 
@@ -371,15 +372,21 @@ fn estimate_index_size(_index: &Index) -> u64 {
 
 fn analyze_segments(index: &Index, config: &AnalysisConfig) -> NifResult<Vec<SegmentAnalysis>> {
     let mut segments = Vec::new();
+    let doc_counts = segment_doc_counts(index)?;
 
     if let Ok(searchable_segments) = index.searchable_segments() {
         for (i, segment) in searchable_segments.iter().enumerate() {
+            let (doc_count, deleted_docs) = doc_counts
+                .get(&segment.id())
+                .copied()
+                .unwrap_or((segment.meta().num_docs(), segment.meta().num_deleted_docs()));
+
             let segment_analysis = SegmentAnalysis {
                 segment_id: format!("segment_{}", i),
                 size_bytes: 1024 * 1024, // Placeholder
-                doc_count: 1000,         // Placeholder - would need segment reader
-                deleted_docs: 0,         // Placeholder - would need segment reader
-                compression_ratio: 0.8,  // Placeholder
+                doc_count,
+                deleted_docs,
+                compression_ratio: 0.8, // Placeholder
                 files: if config.include_file_details {
                     analyze_segment_files(segment)
                 } else {
@@ -393,6 +400,27 @@ fn analyze_segments(index: &Index, config: &AnalysisConfig) -> NifResult<Vec<Seg
     Ok(segments)
 }
 
+/// Live and deleted document counts of each searchable segment, read from its segment reader
+fn segment_doc_counts(index: &Index) -> NifResult<HashMap<SegmentId, (u32, u32)>> {
+    let reader: IndexReader = index
+        .reader_builder()
+        .reload_policy(ReloadPolicy::Manual)
+        .try_into()
+        .map_err(|_| Error::BadArg)?;
+
+    Ok(reader
+        .searcher()
+        .segment_readers()
+        .iter()
+        .map(|segment_reader| {
+            (
+                segment_reader.segment_id(),
+                (segment_reader.num_docs(), segment_reader.num_deleted_docs()),
+            )
+        })
+        .collect())
+}
+
 fn analyze_segment_files(_segment: &Segment) -> Vec<SegmentFile> {
     // Placeholder implementation
     vec![
@@ -436,9 +464,15 @@ fn analyze_index_metadata(index: &Index) -> NifResult<IndexMetadata> {
     let reader = index.reader().map_err(|_| Error::BadArg)?;
     let searcher = reader.searcher();
 
+    let deleted_docs: u64 = searcher
+        .segment_readers()
+        .iter()
+        .map(|segment_reader| segment_reader.num_deleted_docs() as u64)
+        .sum();
+
     let metadata = IndexMetadata {
         total_docs: searcher.num_docs() as u64,
-        deleted_docs: 0,         // Simplified
+        deleted_docs,
         schema_size_bytes: 1024, // Placeholder
         num_fields: schema.fields().count(),
         index_settings: BTreeMap::new(), // Placeholder
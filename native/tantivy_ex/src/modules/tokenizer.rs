@@ -3,9 +3,10 @@ use rustler::{NifResult, ResourceArc};
 use std::collections::HashSet;
 use std::sync::{Arc, Mutex};
 use tantivy::tokenizer::{
-    Language, LowerCaser, NgramTokenizer, PreTokenizedString, RegexTokenizer, RemoveLongFilter,
-    SimpleTokenizer, Stemmer, StopWordFilter, TextAnalyzer, Token, TokenizerManager,
-    WhitespaceTokenizer,
+    AlphaNumOnlyFilter, AsciiFoldingFilter, Language, LowerCaser, NgramTokenizer,
+    PreTokenizedString, RawTokenizer, RegexTokenizer, RemoveLongFilter, SimpleTokenizer, Stemmer,
+    StopWordFilter, TextAnalyzer, TextAnalyzerBuilder, Token, TokenStream, Tokenizer,
+    TokenizerManager, WhitespaceTokenizer,
 };
 
 use crate::modules::resources::TokenizerManagerResource;
@@ -30,6 +31,16 @@ where
     registry.insert(name.to_string());
 }
 
+/// Resolve an analyzer by tokenizer name the way a field would be analyzed.
+///
+/// Tantivy's built-in analyzers win, since those are what indexes use; other
+/// names fall back to the globally registered tokenizers.
+pub fn analyzer_for_tokenizer(name: &str) -> Option<TextAnalyzer> {
+    TokenizerManager::default()
+        .get(name)
+        .or_else(|| GLOBAL_TOKENIZER_MANAGER.lock().unwrap().get(name))
+}
+
 /// Create a new tokenizer manager
 #[rustler::nif]
 pub fn tokenizer_manager_new() -> ResourceArc<TokenizerManagerResource> {
@@ -77,6 +88,41 @@ pub fn register_regex_tokenizer(name: String, pattern: String) -> NifResult<Stri
     }
 }
 
+/// Register a lowercasing tokenizer that splits on the given delimiter characters
+///
+/// Every character of `delimiters` separates tokens, e.g. `",;"` for tag lists
+/// like `"Red, Green;blue"`. Whitespace around each token is trimmed.
+#[rustler::nif]
+pub fn register_delimiter_tokenizer(name: String, delimiters: String) -> NifResult<String> {
+    if delimiters.is_empty() {
+        return Err(rustler::Error::Term(Box::new(
+            "At least one delimiter character is required".to_string(),
+        )));
+    }
+
+    let class: String = delimiters
+        .chars()
+        .map(|c| regex::escape(&c.to_string()))
+        .collect();
+    // A token starts and ends with a character that is neither a delimiter nor whitespace
+    let pattern = format!("[^{class}\\s](?:[^{class}]*[^{class}\\s])?");
+
+    match RegexTokenizer::new(&pattern) {
+        Ok(tokenizer) => {
+            let analyzer = TextAnalyzer::builder(tokenizer).filter(LowerCaser).build();
+            register_tokenizer_with_tracking(&name, analyzer);
+            Ok(format!(
+                "Delimiter tokenizer '{}' registered successfully",
+                name
+            ))
+        }
+        Err(e) => Err(rustler::Error::Term(Box::new(format!(
+            "Failed to create delimiter tokenizer: {}",
+            e
+        )))),
+    }
+}
+
 /// Register an N-gram tokenizer
 #[rustler::nif]
 pub fn register_ngram_tokenizer(
@@ -392,6 +438,320 @@ pub fn register_text_analyzer(
     Ok(format!("Text analyzer '{}' registered successfully", name))
 }
 
+/// Register a text analyzer from an ordered list of filter specs
+///
+/// Filters are applied in the given order. Supported specs are `lowercase`,
+/// `ascii_fold`, `alpha_num_only`, `remove_long:<limit>`, `stop:<lang>` and
+/// `stem:<lang>`.
+#[rustler::nif]
+pub fn register_custom_analyzer(
+    name: String,
+    base_tokenizer: String,
+    filters: Vec<String>,
+) -> NifResult<String> {
+    let mut builder: TextAnalyzerBuilder = match base_tokenizer.as_str() {
+        "simple" => TextAnalyzer::builder(SimpleTokenizer::default()).dynamic(),
+        "whitespace" => TextAnalyzer::builder(WhitespaceTokenizer::default()).dynamic(),
+        "raw" => TextAnalyzer::builder(RawTokenizer::default()).dynamic(),
+        _ => {
+            return Err(rustler::Error::Term(Box::new(format!(
+                "Unsupported base tokenizer: {}. Use 'simple', 'whitespace' or 'raw'",
+                base_tokenizer
+            ))))
+        }
+    };
+
+    for filter in &filters {
+        builder = apply_filter_spec(builder, filter)?;
+    }
+
+    register_tokenizer_with_tracking(&name, builder.build());
+    Ok(format!(
+        "Custom analyzer '{}' registered successfully",
+        name
+    ))
+}
+
+// Helper function to append a single filter spec such as "stem:en" to a builder
+fn apply_filter_spec(builder: TextAnalyzerBuilder, spec: &str) -> NifResult<TextAnalyzerBuilder> {
+    let (kind, arg) = match spec.split_once(':') {
+        Some((kind, arg)) => (kind.trim(), Some(arg.trim())),
+        None => (spec.trim(), None),
+    };
+
+    let missing_arg = || {
+        rustler::Error::Term(Box::new(format!(
+            "Filter '{}' requires an argument, e.g. '{}:<value>'",
+            kind, kind
+        )))
+    };
+
+    match (kind, arg) {
+        ("lowercase", None) => Ok(builder.filter_dynamic(LowerCaser)),
+        ("ascii_fold", None) => Ok(builder.filter_dynamic(AsciiFoldingFilter)),
+        ("alpha_num_only", None) => Ok(builder.filter_dynamic(AlphaNumOnlyFilter)),
+        ("remove_long", Some(limit)) => {
+            let limit = limit
+                .parse::<usize>()
+                .ok()
+                .filter(|l| *l > 0)
+                .ok_or_else(|| {
+                    rustler::Error::Term(Box::new(format!(
+                        "Invalid remove_long limit '{}': expected a positive integer",
+                        limit
+                    )))
+                })?;
+            Ok(builder.filter_dynamic(RemoveLongFilter::limit(limit)))
+        }
+        ("stop", Some(lang)) => {
+            let language = parse_language(lang).ok_or_else(|| {
+                rustler::Error::Term(Box::new(format!(
+                    "Unsupported stop words language: {}",
+                    lang
+                )))
+            })?;
+            let stop_filter = StopWordFilter::new(language).ok_or_else(|| {
+                rustler::Error::Term(Box::new(format!(
+                    "No stop words available for language: {}",
+                    lang
+                )))
+            })?;
+            Ok(builder.filter_dynamic(stop_filter))
+        }
+        ("stem", Some(lang)) => {
+            let language = parse_language(lang).ok_or_else(|| {
+                rustler::Error::Term(Box::new(format!("Unsupported stemming language: {}", lang)))
+            })?;
+            Ok(builder.filter_dynamic(Stemmer::new(language)))
+        }
+        ("remove_long" | "stop" | "stem", None) => Err(missing_arg()),
+        ("lowercase" | "ascii_fold" | "alpha_num_only", Some(_)) => Err(rustler::Error::Term(
+            Box::new(format!("Filter '{}' does not take an argument", kind)),
+        )),
+        _ => Err(rustler::Error::Term(Box::new(format!(
+            "Unknown filter '{}'. Supported filters: lowercase, ascii_fold, alpha_num_only, \
+             remove_long:<limit>, stop:<lang>, stem:<lang>",
+            spec
+        )))),
+    }
+}
+
+/// Register a tokenizer that strips HTML markup before running a base tokenizer
+///
+/// Tags, comments and the contents of `<script>`/`<style>` elements are
+/// dropped and common entities are decoded. Token offsets still point into
+/// the original HTML, so highlighting works on the unstripped text.
+#[rustler::nif]
+pub fn register_html_tokenizer(
+    name: String,
+    base_tokenizer: String,
+    lowercase: bool,
+) -> NifResult<String> {
+    let builder: TextAnalyzerBuilder = match base_tokenizer.as_str() {
+        "simple" => {
+            TextAnalyzer::builder(HtmlStripTokenizer::new(SimpleTokenizer::default())).dynamic()
+        }
+        "whitespace" => {
+            TextAnalyzer::builder(HtmlStripTokenizer::new(WhitespaceTokenizer::default())).dynamic()
+        }
+        "raw" => TextAnalyzer::builder(HtmlStripTokenizer::new(RawTokenizer::default())).dynamic(),
+        _ => {
+            return Err(rustler::Error::Term(Box::new(format!(
+                "Unsupported base tokenizer: {}. Use 'simple', 'whitespace' or 'raw'",
+                base_tokenizer
+            ))))
+        }
+    };
+
+    let builder = if lowercase {
+        builder.filter_dynamic(LowerCaser)
+    } else {
+        builder
+    };
+
+    register_tokenizer_with_tracking(&name, builder.build());
+    Ok(format!("HTML tokenizer '{}' registered successfully", name))
+}
+
+/// Tokenizer that runs `inner` over HTML-stripped text
+///
+/// For every byte of the stripped text, `spans` holds the byte range of the
+/// original text it came from, which is used to map token offsets back.
+#[derive(Clone)]
+struct HtmlStripTokenizer<T> {
+    inner: T,
+    stripped: String,
+    spans: Vec<(usize, usize)>,
+}
+
+impl<T> HtmlStripTokenizer<T> {
+    fn new(inner: T) -> Self {
+        HtmlStripTokenizer {
+            inner,
+            stripped: String::new(),
+            spans: Vec::new(),
+        }
+    }
+}
+
+impl<T: Tokenizer> Tokenizer for HtmlStripTokenizer<T> {
+    type TokenStream<'a> = HtmlStripTokenStream<'a, T::TokenStream<'a>>;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> Self::TokenStream<'a> {
+        self.stripped.clear();
+        self.spans.clear();
+        strip_html(text, &mut self.stripped, &mut self.spans);
+
+        HtmlStripTokenStream {
+            inner: self.inner.token_stream(&self.stripped),
+            spans: &self.spans,
+            text_len: text.len(),
+        }
+    }
+}
+
+struct HtmlStripTokenStream<'a, S> {
+    inner: S,
+    spans: &'a [(usize, usize)],
+    text_len: usize,
+}
+
+impl<S: TokenStream> TokenStream for HtmlStripTokenStream<'_, S> {
+    fn advance(&mut self) -> bool {
+        if !self.inner.advance() {
+            return false;
+        }
+
+        let token = self.inner.token_mut();
+        let offset_from = self
+            .spans
+            .get(token.offset_from)
+            .map_or(self.text_len, |span| span.0);
+        let offset_to = match token.offset_to.checked_sub(1) {
+            Some(last) if token.offset_to > token.offset_from => {
+                self.spans.get(last).map_or(self.text_len, |span| span.1)
+            }
+            _ => offset_from,
+        };
+        token.offset_from = offset_from;
+        token.offset_to = offset_to;
+        true
+    }
+
+    fn token(&self) -> &Token {
+        self.inner.token()
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        self.inner.token_mut()
+    }
+}
+
+// Strip markup from `text` into `stripped`, recording the source range of every output byte
+fn strip_html(text: &str, stripped: &mut String, spans: &mut Vec<(usize, usize)>) {
+    let bytes = text.as_bytes();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let rest = &text[i..];
+
+        if bytes[i] == b'<' {
+            if let Some(end) = html_markup_end(text, i) {
+                // Markup separates words, so it becomes a single space
+                stripped.push(' ');
+                spans.push((i, end));
+                i = end;
+                continue;
+            }
+        }
+
+        if bytes[i] == b'&' {
+            if let Some((decoded, consumed)) = decode_html_entity(rest) {
+                let start = stripped.len();
+                stripped.push(decoded);
+                spans.extend((start..stripped.len()).map(|_| (i, i + consumed)));
+                i += consumed;
+                continue;
+            }
+        }
+
+        let ch = rest.chars().next().expect("index is on a char boundary");
+        let end = i + ch.len_utf8();
+        stripped.push(ch);
+        spans.extend((i..end).map(|_| (i, end)));
+        i = end;
+    }
+}
+
+// End of the tag, comment or script/style element starting at `start`, if it is markup at all
+fn html_markup_end(text: &str, start: usize) -> Option<usize> {
+    let rest = &text[start..];
+
+    if rest.starts_with("<!--") {
+        return Some(
+            rest[4..]
+                .find("-->")
+                .map_or(text.len(), |pos| start + 4 + pos + 3),
+        );
+    }
+
+    // A lone `<`, as in `a < b`, is text rather than markup
+    let next = rest[1..].chars().next()?;
+    if !(next.is_ascii_alphabetic() || matches!(next, '/' | '!' | '?')) {
+        return None;
+    }
+
+    let tag_end = start + rest.find('>')? + 1;
+    let tag_name: String = rest[1..]
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_ascii_lowercase();
+
+    if tag_name == "script" || tag_name == "style" {
+        // Skip the element's contents up to and including its closing tag
+        let closing = format!("</{}", tag_name);
+        let contents = text[tag_end..].to_ascii_lowercase();
+        return Some(match contents.find(&closing) {
+            Some(pos) => {
+                let close_start = tag_end + pos;
+                text[close_start..]
+                    .find('>')
+                    .map_or(text.len(), |end| close_start + end + 1)
+            }
+            None => text.len(),
+        });
+    }
+
+    Some(tag_end)
+}
+
+// Decode a named or numeric entity at the start of `text`, returning the char and bytes consumed
+fn decode_html_entity(text: &str) -> Option<(char, usize)> {
+    let semicolon = text.bytes().take(12).position(|b| b == b';')?;
+    let name = &text[1..semicolon];
+
+    let decoded = match name {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => ' ',
+        _ => {
+            let code =
+                if let Some(hex) = name.strip_prefix("#x").or_else(|| name.strip_prefix("#X")) {
+                    u32::from_str_radix(hex, 16).ok()?
+                } else {
+                    name.strip_prefix('#')?.parse::<u32>().ok()?
+                };
+            char::from_u32(code)?
+        }
+    };
+
+    Some((decoded, semicolon + 1))
+}
+
 /// Get list of registered tokenizers
 #[rustler::nif]
 pub fn list_tokenizers() -> Vec<String> {
@@ -422,12 +782,44 @@ pub fn tokenize_text(tokenizer_name: String, text: String) -> NifResult<Vec<Stri
     }
 }
 
+/// Tokenize many texts with one registered tokenizer
+///
+/// The manager is locked and the analyzer looked up once for the whole batch.
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn tokenize_texts(tokenizer_name: String, texts: Vec<String>) -> NifResult<Vec<Vec<String>>> {
+    let mut tokenizer = {
+        let manager = GLOBAL_TOKENIZER_MANAGER.lock().unwrap();
+        manager.get(&tokenizer_name).ok_or_else(|| {
+            rustler::Error::Term(Box::new(format!(
+                "Tokenizer '{}' not found. Register it first.",
+                tokenizer_name
+            )))
+        })?
+    };
+
+    let mut results = Vec::with_capacity(texts.len());
+    for text in &texts {
+        let mut token_stream = tokenizer.token_stream(text);
+        let mut tokens = Vec::new();
+
+        while let Some(token) = token_stream.next() {
+            tokens.push(token.text.clone());
+        }
+
+        results.push(tokens);
+    }
+
+    Ok(results)
+}
+
 /// Tokenize text and return detailed token information
+///
+/// Each token is returned as `(text, offset_from, offset_to, position, position_length)`.
 #[rustler::nif(schedule = "DirtyCpu")]
 pub fn tokenize_text_detailed(
     tokenizer_name: String,
     text: String,
-) -> NifResult<Vec<(String, usize, usize)>> {
+) -> NifResult<Vec<(String, usize, usize, usize, usize)>> {
     let manager = GLOBAL_TOKENIZER_MANAGER.lock().unwrap();
 
     match manager.get(&tokenizer_name) {
@@ -436,7 +828,13 @@ pub fn tokenize_text_detailed(
             let mut tokens = Vec::new();
 
             while let Some(token) = token_stream.next() {
-                tokens.push((token.text.clone(), token.offset_from, token.offset_to));
+                tokens.push((
+                    token.text.clone(),
+                    token.offset_from,
+                    token.offset_to,
+                    token.position,
+                    token.position_length,
+                ));
             }
 
             Ok(tokens)
@@ -151,6 +151,45 @@ defmodule TantivyEx.AggregationTest do
         assert bucket["doc_count"] >= 5
       end)
     end
+
+    test "terms aggregation with min_doc_count 0 keeps empty categories", %{
+      schema: schema,
+      searcher: searcher
+    } do
+      {:ok, query} = Query.term(schema, "category", "books")
+
+      aggregations = %{
+        "categories" => Aggregation.terms("category", min_doc_count: 0)
+      }
+
+      {:ok, %{"categories" => %{"buckets" => buckets}}} =
+        Aggregation.run(searcher, query, aggregations)
+
+      counts = Map.new(buckets, &{&1["key"], &1["doc_count"]})
+      assert counts == %{"books" => 3, "electronics" => 0}
+
+      assert {:error, _} =
+               Aggregation.run(searcher, query, %{
+                 "categories" => Aggregation.terms("category", min_doc_count: "0")
+               })
+    end
+
+    test "terms aggregation with shard_size", %{searcher: searcher, query: query} do
+      aggregations = %{
+        "categories" => Aggregation.terms("category", size: 1, shard_size: 1)
+      }
+
+      {:ok, %{"categories" => result}} = Aggregation.run(searcher, query, aggregations)
+
+      assert [%{"doc_count" => 3}] = result["buckets"]
+      assert is_integer(result["doc_count_error_upper_bound"])
+      assert result["sum_other_doc_count"] == 3
+
+      assert {:error, _} =
+               Aggregation.run(searcher, query, %{
+                 "categories" => Aggregation.terms("category", shard_size: 0)
+               })
+    end
   end
 
   describe "metric aggregations" do
@@ -233,6 +272,22 @@ defmodule TantivyEx.AggregationTest do
       assert is_float(avg_val)
     end
 
+    test "native results match the decoded JSON results", %{searcher: searcher, query: query} do
+      aggregations = %{
+        "price_stats" => Aggregation.metric(:stats, "price"),
+        "categories" => Aggregation.terms("category", size: 10)
+      }
+
+      {:ok, result} = Aggregation.run(searcher, query, aggregations)
+
+      json = TantivyEx.Native.run_aggregations(searcher, query, Jason.encode!(aggregations))
+      assert result == Jason.decode!(json)
+
+      assert is_integer(result["price_stats"]["count"])
+      assert is_float(result["price_stats"]["avg"])
+      assert Enum.all?(result["categories"]["buckets"], &is_integer(&1["doc_count"]))
+    end
+
     test "percentiles aggregation", %{searcher: searcher, query: query} do
       aggregations = %{
         "price_percentiles" => Aggregation.metric(:percentiles, "price", percents: [50.0, 95.0])
@@ -246,6 +301,19 @@ defmodule TantivyEx.AggregationTest do
       assert is_float(percentile_values["50.0"])
       assert is_float(percentile_values["95.0"])
     end
+
+    test "percentile ranks aggregation", %{searcher: searcher, query: query} do
+      aggregations = %{
+        "price_ranks" => Aggregation.metric(:percentile_ranks, "price", values: [10, 49, 700])
+      }
+
+      {:ok, result} = Aggregation.run(searcher, query, aggregations)
+
+      assert %{"price_ranks" => %{"values" => ranks}} = result
+      assert ranks["10.0"] == 0.0
+      assert ranks["49.0"] == 50.0
+      assert_in_delta ranks["700.0"], 83.33, 0.01
+    end
   end
 
   describe "histogram aggregations" do
@@ -283,6 +351,104 @@ defmodule TantivyEx.AggregationTest do
         assert bucket["doc_count"] >= 2
       end)
     end
+
+    test "histogram with min_doc_count 0 emits empty buckets", %{
+      searcher: searcher,
+      query: query
+    } do
+      aggregations = %{
+        "price_histogram" => Aggregation.histogram("price", 250.0, min_doc_count: 0)
+      }
+
+      {:ok, %{"price_histogram" => %{"buckets" => buckets}}} =
+        Aggregation.run(searcher, query, aggregations)
+
+      assert Enum.map(buckets, &{&1["key"], &1["doc_count"]}) == [
+               {0.0, 3},
+               {250.0, 1},
+               {500.0, 1},
+               {750.0, 1}
+             ]
+
+      aggregations = %{
+        "price_histogram" => Aggregation.histogram("price", 200.0, min_doc_count: 0)
+      }
+
+      {:ok, %{"price_histogram" => %{"buckets" => buckets}}} =
+        Aggregation.run(searcher, query, aggregations)
+
+      assert Enum.any?(buckets, &(&1["doc_count"] == 0))
+    end
+
+    test "histogram with extended and hard bounds", %{searcher: searcher, query: query} do
+      aggregations = %{
+        "extended" =>
+          Aggregation.histogram("price", 500.0,
+            min_doc_count: 0,
+            extended_bounds: %{min: 0, max: 2000}
+          ),
+        "hard" => Aggregation.histogram("price", 50.0, hard_bounds: %{min: 0, max: 100})
+      }
+
+      {:ok, result} = Aggregation.run(searcher, query, aggregations)
+
+      extended_keys = Enum.map(result["extended"]["buckets"], & &1["key"])
+      assert hd(extended_keys) == 0.0
+      assert 1500.0 in extended_keys
+      assert Enum.any?(result["extended"]["buckets"], &(&1["doc_count"] == 0))
+
+      assert Enum.all?(result["hard"]["buckets"], &(&1["key"] <= 100))
+    end
+
+    test "date histogram distinguishes calendar and fixed intervals", %{
+      searcher: searcher,
+      query: query
+    } do
+      aggregations = %{
+        "weekly" => Aggregation.date_histogram("published_date", "week"),
+        "fixed" => %{
+          "date_histogram" => %{"field" => "published_date", "fixed_interval" => "7d"}
+        }
+      }
+
+      {:ok, result} = Aggregation.run(searcher, query, aggregations)
+
+      weekly_keys = Enum.map(result["weekly"]["buckets"], & &1["key"])
+      fixed_keys = Enum.map(result["fixed"]["buckets"], & &1["key"])
+
+      # Calendar weeks start on Monday 2024-01-15, fixed 7d buckets are epoch aligned
+      assert 1_705_276_800_000.0 in weekly_keys
+      refute 1_705_276_800_000.0 in fixed_keys
+
+      monthly = %{"monthly" => Aggregation.date_histogram("published_date", "1M")}
+      assert {:error, reason} = Aggregation.run(searcher, query, monthly)
+      assert reason =~ "calendar_interval"
+    end
+
+    test "histogram offsets shift bucket boundaries", %{searcher: searcher, query: query} do
+      aggregations = %{
+        "price_histogram" => Aggregation.histogram("price", 250.0, offset: 10.0),
+        "daily" => Aggregation.date_histogram("published_date", "day", offset: "+11h")
+      }
+
+      {:ok, result} = Aggregation.run(searcher, query, aggregations)
+
+      assert Enum.map(result["price_histogram"]["buckets"], &{&1["key"], &1["doc_count"]}) == [
+               {10.0, 3},
+               {260.0, 1},
+               {510.0, 1},
+               {760.0, 1}
+             ]
+
+      # 2024-01-15T10:00Z falls in the day starting 2024-01-14T11:00Z
+      daily_keys = Enum.map(result["daily"]["buckets"], & &1["key"])
+      assert 1_705_230_000_000.0 in daily_keys
+      refute 1_705_276_800_000.0 in daily_keys
+
+      invalid = %{"daily" => Aggregation.date_histogram("published_date", "day", offset: "4x")}
+      assert {:error, reason} = Aggregation.run(searcher, query, invalid)
+      assert reason =~ "offset"
+    end
   end
 
   describe "range aggregations" do
@@ -331,6 +497,33 @@ defmodule TantivyEx.AggregationTest do
       assert %{"price_ranges" => %{"buckets" => buckets}} = result
       assert length(buckets) == 3
     end
+
+    test "keyed range and histogram buckets are addressable by key", %{
+      searcher: searcher,
+      query: query
+    } do
+      ranges = [
+        %{"to" => 50, "key" => "cheap"},
+        %{"from" => 50, "to" => 500, "key" => "medium"},
+        %{"from" => 500, "key" => "expensive"}
+      ]
+
+      aggregations = %{
+        "price_ranges" => Aggregation.range("price", ranges, keyed: true),
+        "price_histogram" => Aggregation.histogram("price", 500.0, keyed: true)
+      }
+
+      {:ok, result} = Aggregation.run(searcher, query, aggregations)
+
+      assert %{"buckets" => range_buckets} = result["price_ranges"]
+      assert range_buckets["cheap"]["doc_count"] == 3
+      assert range_buckets["medium"]["doc_count"] == 1
+      assert range_buckets["expensive"]["doc_count"] == 2
+
+      assert %{"buckets" => histogram_buckets} = result["price_histogram"]
+      assert histogram_buckets["0"]["doc_count"] == 4
+      assert histogram_buckets["500"]["doc_count"] == 2
+    end
   end
 
   describe "nested aggregations" do
@@ -415,6 +608,52 @@ defmodule TantivyEx.AggregationTest do
       assert %{"categories" => _, "avg_price" => _} = aggs
     end
 
+    test "global aggregations ignore the hits filter", %{schema: schema, searcher: searcher} do
+      {:ok, cheap} = Query.range_u64(schema, "price", 0, 100)
+      aggregations = %{"categories" => Aggregation.terms("category")}
+      count_docs = fn result -> Enum.map(result["buckets"], & &1["doc_count"]) |> Enum.sum() end
+
+      {:ok, filtered} = Aggregation.search_with_aggregations(searcher, cheap, aggregations, 10)
+      assert length(filtered["hits"]["hits"]) == 3
+      assert count_docs.(filtered["aggregations"]["categories"]) == 3
+
+      {:ok, global} =
+        Aggregation.search_with_aggregations(searcher, cheap, aggregations, 10, global: true)
+
+      assert length(global["hits"]["hits"]) == 3
+      assert count_docs.(global["aggregations"]["categories"]) == 6
+
+      {:ok, books} = Query.range_u64(schema, "price", 0, 50)
+
+      {:ok, scoped} =
+        Aggregation.search_with_aggregations(searcher, cheap, aggregations, 10, agg_query: books)
+
+      assert count_docs.(scoped["aggregations"]["categories"]) == 3
+    end
+
+    test "hits can be ordered by a fast field", %{searcher: searcher, query: query} do
+      aggregations = %{"categories" => Aggregation.terms("category")}
+
+      {:ok, result} =
+        Aggregation.search_with_aggregations(searcher, query, aggregations, 3,
+          sort_field: "price",
+          order: :desc
+        )
+
+      assert Enum.map(result["hits"]["hits"], & &1["price"]) == [999, 699, 399]
+
+      assert {:error, _reason} =
+               Aggregation.search_with_aggregations(searcher, query, aggregations, 3,
+                 sort_field: "category"
+               )
+
+      assert {:error, "Invalid :order option: :newest"} =
+               Aggregation.search_with_aggregations(searcher, query, aggregations, 3,
+                 sort_field: "price",
+                 order: :newest
+               )
+    end
+
     test "search with aggregations respects search limit", %{searcher: searcher, query: query} do
       aggregations = %{
         "categories" => Aggregation.terms("category")
@@ -499,6 +738,39 @@ defmodule TantivyEx.AggregationTest do
       {:error, reason} = Aggregation.run(searcher, query, aggregations)
       assert reason =~ "Unknown aggregation type"
     end
+
+    test "validates aggregations against the schema without running them", %{schema: schema} do
+      valid = %{
+        "categories" => %{
+          "terms" => %{"field" => "category"},
+          "aggs" => %{"avg_price" => %{"avg" => %{"field" => "price"}}}
+        }
+      }
+
+      assert :ok = Aggregation.validate(schema, valid)
+
+      invalid = %{
+        "missing" => %{"terms" => %{"field" => "nope"}},
+        "categories" => %{
+          "terms" => %{"field" => "category"},
+          "aggs" => %{
+            "by_day" => %{"date_histogram" => %{"field" => "price", "fixed_interval" => "1d"}}
+          }
+        },
+        "titles" => %{"terms" => %{"field" => "title"}}
+      }
+
+      assert {:error, errors} = Aggregation.validate(schema, invalid)
+
+      assert errors |> Enum.map(&{&1["aggregation"], &1["error"]}) |> Enum.sort() == [
+               {"categories.by_day", "unsupported_field_type"},
+               {"missing", "unknown_field"},
+               {"titles", "not_fast"}
+             ]
+
+      assert {:error, [%{"error" => "invalid_request"}]} =
+               Aggregation.validate(schema, %{"bad" => %{"histogram" => %{"field" => "price"}}})
+    end
   end
 
   describe "error handling" do
@@ -511,6 +783,21 @@ defmodule TantivyEx.AggregationTest do
       assert reason =~ "not found" or reason =~ "Error"
     end
 
+    test "names the field and its type on a numeric aggregation over text", %{
+      searcher: searcher,
+      query: query
+    } do
+      aggregations = %{"total" => Aggregation.metric(:sum, "category")}
+
+      {:error, reason} = Aggregation.run(searcher, query, aggregations)
+      assert reason =~ "Field 'category' is text, not numeric"
+
+      aggregations = %{"by_day" => Aggregation.date_histogram("price", "1d")}
+
+      {:error, reason} = Aggregation.run(searcher, query, aggregations)
+      assert reason =~ "Field 'price' is u64, not date"
+    end
+
     test "handles malformed JSON gracefully", %{searcher: searcher, query: query} do
       # This test is more about the internal JSON encoding/decoding
       aggregations = %{
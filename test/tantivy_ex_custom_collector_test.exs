@@ -197,22 +197,76 @@ defmodule TantivyEx.CustomCollectorTest do
 
   describe "custom collector execution" do
     test "executes collector with query", %{searcher: _searcher, index: index} do
-      case CustomCollector.new() do
-        {:ok, collector} ->
-          collector_name = "test_collector"
-          query_string = "machine learning"
+      {:ok, collector} = CustomCollector.new()
 
-          case CustomCollector.execute(collector, index, collector_name, query_string) do
-            {:ok, result} ->
-              assert is_binary(result)
+      assert {:error, "Unknown collector: test_collector"} =
+               CustomCollector.execute(collector, index, "test_collector", "machine learning")
 
-            {:error, reason} ->
-              flunk("Failed to execute collector: #{inspect(reason)}")
-          end
+      :ok =
+        CustomCollector.create_filtering(collector, "published", [
+          {"published", "equals", "true"}
+        ])
 
-        {:error, reason} ->
-          flunk("Failed to create collector: #{inspect(reason)}")
+      {:ok, result} = CustomCollector.execute(collector, index, "published", "machine learning")
+
+      assert %{"total_hits" => 1, "metadata" => %{"query" => "machine learning"}} =
+               Jason.decode!(result)
+
+      {:ok, result} = CustomCollector.execute(collector, index, "published", "")
+      assert %{"total_hits" => 3} = Jason.decode!(result)
+
+      assert {:error, _} = CustomCollector.execute(collector, index, "published", "missing:value")
+    end
+
+    test "filtering collector only collects matching documents", %{index: index} do
+      {:ok, collector} = CustomCollector.new()
+
+      :ok =
+        CustomCollector.create_filtering(collector, "published_high_score", [
+          {"published", "equals", "true"},
+          {"score", "gt", "4.4"}
+        ])
+
+      {:ok, result} = CustomCollector.execute(collector, index, "published_high_score", "*")
+      {:ok, decoded} = Jason.decode(result)
+
+      assert decoded["result_type"] == "filtering"
+      assert decoded["total_hits"] == 2
+
+      :ok =
+        CustomCollector.create_filtering(collector, "tech_titles", [
+          {"category", "equals", "technology"},
+          {"title", "regex", "^Prog"}
+        ])
+
+      {:ok, result} = CustomCollector.execute(collector, index, "tech_titles", "*")
+      {:ok, decoded} = Jason.decode(result)
+
+      assert decoded["total_hits"] == 1
+    end
+
+    test "filtering collector reports the segment of every hit", %{schema: schema} do
+      {:ok, index} = Index.create_in_ram(schema)
+      {:ok, writer} = IndexWriter.new(index, 50_000_000)
+
+      # Two commits leave two segments whose first documents share doc_id 0
+      for id <- [1, 2] do
+        doc = %{"title" => "Doc #{id}", "id" => id, "published" => true}
+        :ok = IndexWriter.add_document(writer, doc)
+        :ok = IndexWriter.commit(writer)
       end
+
+      {:ok, collector} = CustomCollector.new()
+      :ok =
+        CustomCollector.create_filtering(collector, "published", [
+          {"published", "equals", "true"}
+        ])
+
+      {:ok, result} = CustomCollector.execute(collector, index, "published", "")
+      %{"total_hits" => 2, "top_documents" => documents} = Jason.decode!(result)
+
+      addresses = Enum.map(documents, &{&1["segment_ord"], &1["doc_id"]})
+      assert Enum.sort(addresses) == [{0, 0}, {1, 0}]
     end
 
     test "gets results from collector" do
@@ -35,4 +35,28 @@ defmodule TantivyExDocumentAddTest do
     found_doc = hd(results)
     assert found_doc["title"] == "Hello World"
   end
+
+  test "pre-tokenized text bypasses the field tokenizer" do
+    schema = Schema.add_text_field(Schema.new(), "title", :text_stored)
+    {:ok, index} = Index.create_in_ram(schema)
+    {:ok, writer} = IndexWriter.new(index, 50_000_000)
+
+    tokens = [{"new york", 0, 8, 0}, {"pizza", 9, 14, 1}]
+    doc = %{"title" => Document.pre_tokenized("New York pizza", tokens)}
+
+    assert {:ok, _} = Document.add(writer, doc, schema)
+    assert :ok = IndexWriter.commit(writer)
+    {:ok, searcher} = Searcher.new(index)
+
+    {:ok, query} = Query.term(schema, "title", "pizza")
+    assert {:ok, [%{"title" => "New York pizza"}]} = Searcher.search(searcher, query, 10)
+
+    # The single "new york" token was indexed instead of "new" and "york"
+    {:ok, query} = Query.term(schema, "title", "york")
+    assert {:ok, []} = Searcher.search(searcher, query, 10)
+
+    bad_doc = %{"title" => Document.pre_tokenized("short", [{"short", 0, 99, 0}])}
+    assert {:error, reason} = Document.add(writer, bad_doc, schema)
+    assert reason =~ "outside of the 5-byte text"
+  end
 end
@@ -43,6 +43,23 @@ defmodule TantivyExDocumentTest do
     assert {:error, _errors} = Document.validate(invalid_doc, schema)
   end
 
+  test "checks documents for missing required fields", %{schema: schema} do
+    assert :ok = Document.check(%{"title" => "Test", "id" => 1}, schema, required: ["title"])
+    assert :ok = Document.check(%{"id" => 1}, schema)
+
+    assert {:error, reason} =
+             Document.check(%{"id" => "oops", "content" => nil}, schema,
+               required: ["title", "content"]
+             )
+
+    assert reason =~ "Missing required field: 'title'"
+    assert reason =~ "Missing required field: 'content'"
+    assert reason =~ "Field 'id'"
+
+    assert {:error, reason} = Document.check(%{}, schema, required: ["nope"])
+    assert reason =~ "Unknown required field"
+  end
+
   test "adds single document with schema", %{writer: writer, schema: schema} do
     doc = %{
       "title" => "Single Document",
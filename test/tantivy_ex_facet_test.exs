@@ -92,4 +92,147 @@ defmodule TantivyExFacetTest do
     assert "Elixir Doc" in titles
     assert "Mixed Doc" in titles
   end
+
+  test "search with facets returns hits and facet counts", %{
+    schema: schema,
+    writer: writer,
+    index: index
+  } do
+    docs = [
+      %{"title" => "Elixir Doc", "tags" => ["/tag/elixir", "/tag/functional"]},
+      %{"title" => "Python Doc", "tags" => ["/tag/python", "/tag/scripting"]},
+      %{"title" => "Mixed Doc", "tags" => ["/tag/elixir", "/tag/python"]}
+    ]
+
+    Enum.each(docs, fn doc ->
+      :ok = IndexWriter.add_document(writer, doc)
+    end)
+
+    :ok = IndexWriter.commit(writer)
+
+    {:ok, searcher} = Searcher.new(index)
+    {:ok, query} = Query.term(schema, "title", "doc")
+
+    {:ok, %{"hits" => hits, "facets" => facets}} =
+      Searcher.search_with_facets(searcher, query, 2, "tags", ["/tag"])
+
+    assert length(hits) == 2
+    assert Enum.all?(hits, &Map.has_key?(&1, "title"))
+    assert facets["/tag"]["/tag/elixir"] == 2
+    assert facets["/tag"]["/tag/python"] == 2
+    assert facets["/tag"]["/tag/functional"] == 1
+
+    assert {:error, _} =
+             Searcher.search_with_facets(searcher, query, 2, "tags", ["/tag", "/tag/elixir"])
+  end
+
+  test "facet search limited by depth rolls deeper counts up", %{writer: writer, index: index} do
+    docs = [
+      %{"title" => "Phone", "tags" => ["/electronics/phones/android"]},
+      %{"title" => "Laptop", "tags" => ["/electronics/computers/laptops"]},
+      %{"title" => "Novel", "tags" => ["/books/fiction"]}
+    ]
+
+    Enum.each(docs, fn doc -> :ok = IndexWriter.add_document(writer, doc) end)
+    :ok = IndexWriter.commit(writer)
+
+    {:ok, searcher} = Searcher.new(index)
+    {:ok, query} = Query.all()
+    {:ok, collector} = TantivyEx.Facet.collector_for_field("tags")
+    :ok = TantivyEx.Facet.add_facet(collector, "/electronics")
+    :ok = TantivyEx.Facet.add_facet(collector, "/books")
+
+    assert {:ok, %{"/electronics" => 2, "/books" => 1}} =
+             TantivyEx.Facet.search_depth(searcher, query, collector, 1)
+
+    assert {:ok, two_levels} = TantivyEx.Facet.search_depth(searcher, query, collector, 2)
+
+    assert two_levels == %{
+             "/electronics/phones" => 1,
+             "/electronics/computers" => 1,
+             "/books/fiction" => 1
+           }
+  end
+
+  test "adds several facet paths to a collector at once", %{writer: writer, index: index} do
+    docs = [
+      %{"title" => "Phone", "tags" => ["/electronics/phones"]},
+      %{"title" => "Novel", "tags" => ["/books/fiction"]},
+      %{"title" => "Album", "tags" => ["/music/rock"]}
+    ]
+
+    Enum.each(docs, fn doc -> :ok = IndexWriter.add_document(writer, doc) end)
+    :ok = IndexWriter.commit(writer)
+
+    {:ok, searcher} = Searcher.new(index)
+    {:ok, query} = Query.all()
+    {:ok, collector} = TantivyEx.Facet.collector_for_field("tags")
+
+    assert {:error, _} = TantivyEx.Facet.add_facets(collector, ["/books", "no-slash"])
+    :ok = TantivyEx.Facet.add_facets(collector, ["/electronics", "/books"])
+
+    assert {:ok, %{"/electronics" => 1, "/books" => 1}} =
+             TantivyEx.Facet.search_depth(searcher, query, collector, 1)
+  end
+
+  test "counts children of a facet path in one call", %{writer: writer, index: index} do
+    docs = [
+      %{"title" => "Phone", "tags" => ["/electronics/phones"]},
+      %{"title" => "Tablet", "tags" => ["/electronics/phones"]},
+      %{"title" => "Laptop", "tags" => ["/electronics/computers"]},
+      %{"title" => "Novel", "tags" => ["/books/fiction"]}
+    ]
+
+    Enum.each(docs, fn doc -> :ok = IndexWriter.add_document(writer, doc) end)
+    :ok = IndexWriter.commit(writer)
+
+    {:ok, searcher} = Searcher.new(index)
+    {:ok, query} = Query.all()
+
+    assert {:ok, %{"/electronics" => 3, "/books" => 1}} =
+             TantivyEx.Facet.counts(searcher, query, "tags")
+
+    assert {:ok, %{"/electronics/phones" => 2, "/electronics/computers" => 1}} =
+             TantivyEx.Facet.counts(searcher, query, "tags", "/electronics")
+
+    assert {:error, _} = TantivyEx.Facet.counts(searcher, query, "title", "/")
+  end
+
+  test "encodes segments containing slashes as a single level", %{writer: writer, index: index} do
+    assert {:ok, "/music/AC\\/DC"} = TantivyEx.Facet.encode(["music", "AC/DC"])
+    assert {:ok, ["music", "AC/DC"]} = TantivyEx.Facet.decode("/music/AC\\/DC")
+    assert {:ok, ["a\\b"]} = TantivyEx.Facet.decode(elem(TantivyEx.Facet.encode(["a\\b"]), 1))
+    assert {:ok, []} = TantivyEx.Facet.decode("/")
+    assert {:error, _} = TantivyEx.Facet.encode(["music", ""])
+
+    {:ok, path} = TantivyEx.Facet.encode(["music", "AC/DC"])
+    :ok = IndexWriter.add_document(writer, %{"title" => "Back in Black", "tags" => [path]})
+    :ok = IndexWriter.commit(writer)
+
+    {:ok, searcher} = Searcher.new(index)
+    {:ok, query} = Query.all()
+
+    assert {:ok, %{"/music" => 1}} = TantivyEx.Facet.counts(searcher, query, "tags")
+    assert {:ok, counts} = TantivyEx.Facet.counts(searcher, query, "tags", "/music")
+    assert [{child, 1}] = Map.to_list(counts)
+    assert {:ok, ["music", "AC/DC"]} = TantivyEx.Facet.decode(child)
+  end
+
+  test "returns facet fields as a list of paths", %{writer: writer, index: index} do
+    docs = [
+      %{"title" => "Tablet", "tags" => ["/electronics/tablets", "/sale/clearance"]},
+      %{"title" => "Novel", "tags" => "/books/fiction"}
+    ]
+
+    Enum.each(docs, fn doc -> :ok = IndexWriter.add_document(writer, doc) end)
+    :ok = IndexWriter.commit(writer)
+
+    {:ok, searcher} = Searcher.new(index)
+    {:ok, query} = Query.all()
+    {:ok, results} = Searcher.search(searcher, query, 10)
+    tags = Map.new(results, &{&1["title"], &1["tags"]})
+
+    assert tags["Tablet"] == ["/electronics/tablets", "/sale/clearance"]
+    assert tags["Novel"] == ["/books/fiction"]
+  end
 end
@@ -101,6 +101,36 @@ defmodule TantivyEx.IndexWarmingTest do
       end
     end
 
+    test "warms fast field columns and records the operation" do
+      schema =
+        Schema.new()
+        |> Schema.add_u64_field("price", :fast_stored)
+        |> Schema.add_text_field("title", :text_stored)
+
+      {:ok, index} = Index.create_in_ram(schema)
+      {:ok, writer} = IndexWriter.new(index, 50_000_000)
+
+      for price <- 1..100 do
+        :ok = IndexWriter.add_document(writer, %{"price" => price, "title" => "item #{price}"})
+      end
+
+      :ok = IndexWriter.commit(writer)
+
+      {:ok, warming_resource} = IndexWarming.new()
+
+      assert {:ok, bytes_warmed} =
+               IndexWarming.warm_fast_fields(warming_resource, index, ["price"], "prices")
+
+      assert bytes_warmed > 0
+      assert {:ok, _searcher} = IndexWarming.get_searcher(warming_resource, "prices")
+
+      {:ok, stats_json} = IndexWarming.get_stats(warming_resource)
+      assert %{"warming_operations" => 1} = Jason.decode!(stats_json)
+
+      assert {:error, "Field 'title' is not a fast field"} =
+               IndexWarming.warm_fast_fields(warming_resource, index, ["title"], "titles")
+    end
+
     test "handles searcher retrieval" do
       case IndexWarming.new() do
         {:ok, warming_resource} ->
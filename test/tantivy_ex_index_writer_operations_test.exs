@@ -1,6 +1,6 @@
 defmodule TantivyExIndexWriterOperationsTest do
   use ExUnit.Case, async: true
-  alias TantivyEx.{Schema, Index, IndexWriter, Query, Searcher}
+  alias TantivyEx.{Schema, Index, IndexWriter, MergePolicy, Query, Searcher}
 
   setup do
     # Create a schema with various field types
@@ -121,7 +121,7 @@ defmodule TantivyExIndexWriterOperationsTest do
     {:ok, active_query} = Query.term(schema, "active", true)
 
     # Delete active documents
-    :ok = IndexWriter.delete_documents(writer, active_query)
+    assert {:ok, 2} = IndexWriter.delete_documents(writer, active_query)
     :ok = IndexWriter.commit(writer)
 
     # Verify only inactive documents remain
@@ -131,6 +131,63 @@ defmodule TantivyExIndexWriterOperationsTest do
     assert hd(all_results)["active"] == false
   end
 
+  test "delete_before removes documents older than a cutoff" do
+    schema =
+      Schema.new()
+      |> Schema.add_u64_field("id", :indexed_stored)
+      |> Schema.add_date_field("published_at", :indexed_stored)
+
+    {:ok, index} = Index.create_in_ram(schema)
+    {:ok, writer} = IndexWriter.new(index, 50_000_000)
+
+    for {id, published_at} <- [{1, 1_000}, {2, 2_000}, {3, 3_000}] do
+      :ok = IndexWriter.add_document(writer, %{"id" => id, "published_at" => published_at})
+    end
+
+    :ok = IndexWriter.commit(writer)
+
+    assert {:ok, 1} = IndexWriter.delete_before(writer, "published_at", 2_000)
+    # Counted against the last commit, so the document queued above is counted again
+    assert {:ok, 2} =
+             IndexWriter.delete_before(writer, "published_at", DateTime.from_unix!(2_500))
+    :ok = IndexWriter.commit(writer)
+
+    {:ok, searcher} = Searcher.new(index)
+    {:ok, all_query} = Query.all()
+    {:ok, remaining} = Searcher.search(searcher, all_query, 10)
+    assert Enum.map(remaining, & &1["id"]) == [3]
+
+    assert {:error, "Field 'id' is not a date field"} =
+             IndexWriter.delete_before(writer, "id", 2_000)
+  end
+
+  test "delete_regex removes documents whose terms match a pattern" do
+    schema =
+      Schema.new()
+      |> Schema.add_text_field_with_tokenizer("sku", :text_stored, "raw")
+      |> Schema.add_u64_field("id", :indexed_stored)
+
+    {:ok, index} = Index.create_in_ram(schema)
+    {:ok, writer} = IndexWriter.new(index, 50_000_000)
+
+    for {id, sku} <- [{1, "test-001"}, {2, "test-002"}, {3, "prod-001"}] do
+      :ok = IndexWriter.add_document(writer, %{"id" => id, "sku" => sku})
+    end
+
+    :ok = IndexWriter.commit(writer)
+
+    assert {:ok, 2} = IndexWriter.delete_regex(writer, "sku", "test-.*")
+    :ok = IndexWriter.commit(writer)
+
+    {:ok, searcher} = Searcher.new(index)
+    {:ok, all_query} = Query.all()
+    {:ok, remaining} = Searcher.search(searcher, all_query, 10)
+    assert Enum.map(remaining, & &1["sku"]) == ["prod-001"]
+
+    assert {:error, _reason} = IndexWriter.delete_regex(writer, "sku", "test-(")
+    assert {:error, _reason} = IndexWriter.delete_regex(writer, "id", "1.*")
+  end
+
   test "rollback should cancel pending operations", %{
     writer: writer,
     schema: _schema,
@@ -186,4 +243,274 @@ defmodule TantivyExIndexWriterOperationsTest do
     assert length(final_results) == 1
     assert hd(final_results)["id"] == 201
   end
+
+  test "new_with_threads creates a writer with a fixed thread count", %{index: index} do
+    # The setup writer holds the index lock, so use a fresh index here
+    schema = Schema.new() |> Schema.add_text_field("title", :text_stored)
+    {:ok, single_thread_index} = Index.create_in_ram(schema)
+
+    assert {:ok, writer} = IndexWriter.new_with_threads(single_thread_index, 1)
+    :ok = IndexWriter.add_document(writer, %{"title" => "Threaded"})
+    :ok = IndexWriter.commit(writer)
+
+    {:ok, searcher} = Searcher.new(single_thread_index)
+    {:ok, query} = Query.all()
+    {:ok, results} = Searcher.search(searcher, query, 10)
+    assert length(results) == 1
+
+    assert {:error, _} = IndexWriter.new_with_threads(index, 0)
+  end
+
+  test "garbage_collect_files should report deleted and pending files", %{writer: writer} do
+    :ok = IndexWriter.add_document(writer, %{"title" => "GC Document", "id" => 301})
+    :ok = IndexWriter.commit(writer)
+    :ok = IndexWriter.delete_all_documents(writer)
+    :ok = IndexWriter.commit(writer)
+
+    assert {:ok, result} = IndexWriter.garbage_collect_files(writer)
+    assert is_integer(result["deleted_count"])
+    assert length(result["deleted_files"]) == result["deleted_count"]
+    assert is_list(result["failed_to_delete_files"])
+  end
+
+  test "commit_with_opstamp and reload give read-your-writes", %{writer: writer, index: index} do
+    {:ok, all_query} = Query.all()
+    {:ok, stale_searcher} = Searcher.reload(index)
+    assert {:ok, 0} = Searcher.count(stale_searcher, all_query)
+
+    :ok = IndexWriter.add_document(writer, %{"title" => "First", "id" => 1})
+    {:ok, first_opstamp} = IndexWriter.commit_with_opstamp(writer)
+
+    :ok = IndexWriter.add_document(writer, %{"title" => "Second", "id" => 2})
+    {:ok, second_opstamp} = IndexWriter.commit_with_opstamp(writer)
+    assert second_opstamp > first_opstamp
+
+    assert {:ok, 0} = Searcher.count(stale_searcher, all_query)
+
+    {:ok, fresh_searcher} = Searcher.reload(index)
+    assert {:ok, 2} = Searcher.count(fresh_searcher, all_query)
+  end
+
+  test "wait_for_opstamp returns once the commit is visible", %{writer: writer, index: index} do
+    :ok = IndexWriter.add_document(writer, %{"title" => "Awaited", "id" => 1})
+    {:ok, opstamp} = IndexWriter.commit_with_opstamp(writer)
+
+    assert :ok = Searcher.wait_for_opstamp(index, opstamp, 1_000)
+    assert :ok = Searcher.wait_for_opstamp(index, opstamp, 0xFFFFFFFFFFFFFFFF)
+    assert {:error, :timeout} = Searcher.wait_for_opstamp(index, opstamp + 100, 50)
+  end
+
+  test "new retries a busy writer lock until lock_timeout_ms", %{schema: schema} do
+    {:ok, index} = Index.create_in_ram(schema)
+    parent = self()
+
+    holder =
+      spawn(fn ->
+        {:ok, _writer} = IndexWriter.new(index, 50_000_000)
+        send(parent, :locked)
+
+        receive do
+          :release -> :ok
+        end
+      end)
+
+    assert_receive :locked
+    assert {:error, _} = IndexWriter.new(index, 50_000_000)
+    assert {:error, _} = IndexWriter.new(index, 50_000_000, lock_timeout_ms: 20)
+    assert {:error, _} = IndexWriter.new(index, 50_000_000, lock_timeout_ms: -1)
+
+    # The lock is released once the holder exits and its writer is freed. A
+    # timeout too large for a deadline must wait rather than overflow.
+    max_timeout = 0xFFFFFFFFFFFFFFFF
+    waiter =
+      Task.async(fn -> IndexWriter.new(index, 50_000_000, lock_timeout_ms: max_timeout) end)
+    send(holder, :release)
+    assert {:ok, _writer} = Task.await(waiter, 10_000)
+  end
+
+  test "commit_soft reports the commit and its documents show after a reload", %{
+    writer: writer,
+    index: index
+  } do
+    {:ok, all_query} = Query.all()
+    {:ok, before_searcher} = Searcher.reload(index)
+
+    :ok = IndexWriter.add_document(writer, %{"title" => "Soft", "id" => 1})
+    {:ok, opstamp} = IndexWriter.commit_soft(writer)
+    assert {:ok, %{"pending_documents" => 0}} = IndexWriter.status(writer)
+
+    assert_receive {:tantivy_commit, ^opstamp, :ok}, 1_000
+
+    assert {:ok, 0} = Searcher.count(before_searcher, all_query)
+    {:ok, searcher} = Searcher.reload(index)
+    assert {:ok, 1} = Searcher.count(searcher, all_query)
+
+    {:ok, next_opstamp} = IndexWriter.commit_with_opstamp(writer)
+    assert next_opstamp > opstamp
+  end
+
+  test "acquire reuses one reader and picks up new commits", %{writer: writer, index: index} do
+    {:ok, all_query} = Query.all()
+
+    {:ok, searcher} = Searcher.acquire(index)
+    assert {:ok, 0} = Searcher.count(searcher, all_query)
+
+    :ok = IndexWriter.add_document(writer, %{"title" => "Acquired", "id" => 1})
+    :ok = IndexWriter.commit(writer)
+
+    {:ok, searcher} = Searcher.acquire(index)
+    assert {:ok, 1} = Searcher.count(searcher, all_query)
+
+    {:ok, again} = Searcher.acquire(index)
+    assert {:ok, 1} = Searcher.count(again, all_query)
+  end
+
+  test "commit_and_searcher returns a searcher over the new commit", %{
+    writer: writer,
+    index: index
+  } do
+    {:ok, all_query} = Query.all()
+
+    :ok = IndexWriter.add_document(writer, %{"title" => "First", "id" => 1})
+    assert {:ok, searcher} = IndexWriter.commit_and_searcher(writer, index)
+    assert {:ok, 1} = Searcher.count(searcher, all_query)
+
+    :ok = IndexWriter.add_document(writer, %{"title" => "Second", "id" => 2})
+    assert {:ok, searcher} = IndexWriter.commit_and_searcher(writer, index)
+    assert {:ok, 2} = Searcher.count(searcher, all_query)
+
+    assert {:ok, status} = IndexWriter.status(writer)
+    assert status["pending_documents"] == 0
+  end
+
+  test "delete_terms removes documents for every given value", %{writer: writer, index: index} do
+    Enum.each(1..5, fn id ->
+      :ok = IndexWriter.add_document(writer, %{"title" => "Document #{id}", "id" => id})
+    end)
+
+    :ok = IndexWriter.commit(writer)
+
+    assert :ok = IndexWriter.delete_terms(writer, "id", [1, "3", 5])
+    :ok = IndexWriter.commit(writer)
+
+    {:ok, searcher} = Searcher.new(index)
+    {:ok, all_query} = Query.all()
+    {:ok, results} = Searcher.search(searcher, all_query, 10)
+
+    assert results |> Enum.map(& &1["id"]) |> Enum.sort() == [2, 4]
+
+    assert {:error, _} = IndexWriter.delete_terms(writer, "missing", [1])
+    assert {:error, _} = IndexWriter.delete_terms(writer, "id", ["not-a-number"])
+  end
+
+  test "status reports the memory budget and pending documents", %{writer: writer} do
+    assert {:ok, status} = IndexWriter.status(writer)
+    assert status["memory_budget"] == 50_000_000
+    assert status["num_threads"] == nil
+    assert status["pending_documents"] == 0
+
+    :ok = IndexWriter.add_document(writer, %{"title" => "Pending", "id" => 1})
+    :ok = IndexWriter.add_document(writer, %{"title" => "Pending", "id" => 2})
+    assert {:ok, %{"pending_documents" => 2}} = IndexWriter.status(writer)
+
+    {:ok, opstamp} = IndexWriter.commit_with_opstamp(writer)
+    assert {:ok, %{"pending_documents" => 0, "commit_opstamp" => ^opstamp}} =
+             IndexWriter.status(writer)
+
+    :ok = IndexWriter.add_document(writer, %{"title" => "Discarded", "id" => 3})
+    :ok = IndexWriter.rollback(writer)
+    assert {:ok, %{"pending_documents" => 0}} = IndexWriter.status(writer)
+  end
+
+  test "optimize commits and merges down to the requested segment count", %{
+    writer: writer,
+    index: index
+  } do
+    Enum.each(1..3, fn id ->
+      :ok = IndexWriter.add_document(writer, %{"title" => "Segment #{id}", "id" => id})
+      :ok = IndexWriter.commit(writer)
+    end)
+
+    :ok = IndexWriter.add_document(writer, %{"title" => "Uncommitted", "id" => 4})
+
+    assert {:ok, summary} = IndexWriter.optimize(writer, 1)
+    assert summary["segments_before"] >= 2
+    assert summary["segments_after"] == 1
+    assert is_integer(summary["files_deleted"])
+
+    {:ok, searcher} = Searcher.reload(index)
+    {:ok, all_query} = Query.all()
+    assert {:ok, 4} = Searcher.count(searcher, all_query)
+  end
+
+  test "expunge_deleted rewrites only segments above the deleted ratio", %{
+    writer: writer,
+    schema: schema,
+    index: index
+  } do
+    Enum.each([1..4, 5..6], fn ids ->
+      Enum.each(ids, &(:ok = IndexWriter.add_document(writer, %{"title" => "Doc", "id" => &1})))
+      :ok = IndexWriter.commit(writer)
+    end)
+
+    {:ok, deleted} = Query.range_u64(schema, "id", 1, 3)
+    {:ok, 3} = IndexWriter.delete_documents(writer, deleted)
+
+    assert {:ok, summary} = IndexWriter.expunge_deleted(writer, 0.5)
+    assert summary["segments_expunged"] == 1
+    assert summary["deleted_docs_reclaimed"] == 3
+    assert summary["segments_after"] == summary["segments_before"]
+
+    assert {:ok, %{"deleted_docs_reclaimed" => 0}} = IndexWriter.expunge_deleted(writer)
+
+    {:ok, searcher} = Searcher.reload(index)
+    {:ok, all_query} = Query.all()
+    assert {:ok, 3} = Searcher.count(searcher, all_query)
+  end
+
+  test "segment_doc_counts reports live and deleted documents per segment", %{
+    writer: writer,
+    schema: schema,
+    index: index
+  } do
+    Enum.each([1..4, 5..6], fn ids ->
+      Enum.each(ids, &(:ok = IndexWriter.add_document(writer, %{"title" => "Doc", "id" => &1})))
+      :ok = IndexWriter.commit(writer)
+    end)
+
+    {:ok, deleted} = Query.range_u64(schema, "id", 2, 2)
+    {:ok, 1} = IndexWriter.delete_documents(writer, deleted)
+    :ok = IndexWriter.commit(writer)
+
+    assert {:ok, counts} = MergePolicy.segment_doc_counts(index)
+    assert length(counts) == 2
+
+    assert counts |> Enum.map(fn {_id, docs, deleted} -> {docs, deleted} end) |> Enum.sort() ==
+             [{2, 0}, {3, 1}]
+
+    assert Enum.all?(counts, fn {id, _, _} -> is_binary(id) end)
+  end
+
+  test "add_document_checked rejects or coerces mistyped values", %{
+    writer: writer,
+    schema: schema,
+    index: index
+  } do
+    doc = %{"title" => "Loose", "id" => " 42 ", "active" => "TRUE"}
+
+    assert {:error, reason} = IndexWriter.add_document_checked(writer, doc)
+    assert reason =~ "Field '"
+
+    assert :ok = IndexWriter.add_document_checked(writer, doc, coerce: true)
+
+    assert {:error, reason} =
+             IndexWriter.add_document_checked(writer, %{"id" => "forty-two"}, coerce: true)
+
+    assert reason =~ "Field 'id'"
+
+    :ok = IndexWriter.commit(writer)
+    {:ok, searcher} = Searcher.reload(index)
+    {:ok, query} = Query.term(schema, "id", "42")
+    assert {:ok, [%{"id" => 42, "active" => true}]} = Searcher.search(searcher, query, 10)
+  end
 end
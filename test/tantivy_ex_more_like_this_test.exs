@@ -83,5 +83,51 @@ defmodule TantivyEx.MoreLikeThisTest do
       assert is_binary(reason)
       assert String.contains?(reason, "No valid field values")
     end
+
+    test "builds a more-like-this query from an indexed document" do
+      schema =
+        Schema.new()
+        |> Schema.add_text_field("title", :text_stored)
+        |> Schema.add_text_field("content", :text_stored)
+
+      {:ok, index} = Index.create_in_ram(schema)
+      {:ok, writer} = IndexWriter.new(index, 50_000_000)
+
+      documents = [
+        %{"title" => "Rust Programming", "content" => "Rust is a systems programming language"},
+        %{"title" => "Elixir Guide", "content" => "Elixir is a functional programming language"},
+        %{"title" => "Gardening", "content" => "Tomatoes need plenty of sun"}
+      ]
+
+      Enum.each(documents, &IndexWriter.add_document(writer, &1))
+      IndexWriter.commit(writer)
+      {:ok, searcher} = Searcher.new(index)
+
+      {:ok, all_query} = Query.all()
+      {:ok, [source | _], _cursor} = Searcher.scroll(searcher, all_query, 1)
+
+      assert {:ok, query} =
+               Query.more_like_this_doc(
+                 searcher,
+                 source["segment_ord"],
+                 source["doc_id"],
+                 min_doc_frequency: 1,
+                 min_term_frequency: 1
+               )
+
+      assert {:ok, results} = Searcher.search(searcher, query, 10, true)
+      titles = Enum.map(results, & &1["title"])
+      assert source["title"] in titles
+      refute "Gardening" in titles
+    end
+
+    test "rejects a document address outside the index" do
+      schema = Schema.new() |> Schema.add_text_field("title", :text_stored)
+      {:ok, index} = Index.create_in_ram(schema)
+      {:ok, searcher} = Searcher.new(index)
+
+      assert {:error, reason} = Query.more_like_this_doc(searcher, 5, 0)
+      assert reason =~ "out of range"
+    end
   end
 end
@@ -78,6 +78,20 @@ defmodule TantivyEx.QueryParserTest do
       assert {:error, reason} = Query.parser(index, ["non_existent_field"])
       assert is_binary(reason)
     end
+
+    test "drops unknown fields when not strict", %{index: index, searcher: searcher} do
+      assert {:error, _reason} = Query.parser(index, ["title", "optional_field"])
+
+      log =
+        ExUnit.CaptureLog.capture_log(fn ->
+          assert {:ok, parser} = Query.parser(index, ["title", "optional_field"], strict: false)
+          assert {:ok, query} = Query.parse(parser, "elixir")
+          assert {:ok, _results} = Searcher.search(searcher, query, 10)
+        end)
+
+      assert log =~ "optional_field"
+      assert {:error, _reason} = Query.parser(index, ["optional_field"], strict: false)
+    end
   end
 
   describe "simple query parsing" do
@@ -219,6 +233,48 @@ defmodule TantivyEx.QueryParserTest do
     end
   end
 
+  describe "per-query field boosts" do
+    test "boosts change ranking without altering the parser" do
+      schema =
+        Schema.new()
+        |> Schema.add_text_field("title", :text_stored)
+        |> Schema.add_text_field("body", :text_stored)
+
+      {:ok, index} = Index.create_in_ram(schema)
+      {:ok, writer} = IndexWriter.new(index, 50_000_000)
+      :ok = IndexWriter.add_document(writer, %{"title" => "elixir", "body" => "rust"})
+      :ok = IndexWriter.add_document(writer, %{"title" => "rust", "body" => "elixir"})
+      :ok = IndexWriter.commit(writer)
+      {:ok, searcher} = Searcher.new(index)
+
+      {:ok, parser} = Query.parser(index, ["title", "body"])
+
+      {:ok, title_query} = Query.parse_with_boosts(parser, "elixir", %{"title" => 5})
+      {:ok, [first | _]} = Searcher.search(searcher, title_query, 10, true)
+      assert first["title"] == "elixir"
+
+      {:ok, body_query} = Query.parse_with_boosts(parser, "elixir", %{"body" => 5})
+      {:ok, [first | _]} = Searcher.search(searcher, body_query, 10, true)
+      assert first["body"] == "elixir"
+
+      assert {:error, _} = Query.parse_with_boosts(parser, "elixir", %{"missing" => 2.0})
+    end
+  end
+
+  describe "parsing on ad-hoc field sets" do
+    test "switches search scope without creating parsers", %{index: index, searcher: searcher} do
+      {:ok, title_only} = Query.parse_on_fields(index, "guide", ["title"])
+      assert {:ok, []} = Searcher.search(searcher, title_only, 10)
+
+      {:ok, all_fields} = Query.parse_on_fields(index, "guide", ["title", "content"])
+      assert {:ok, [%{"title" => "Elixir in Action"}]} = Searcher.search(searcher, all_fields, 10)
+
+      assert {:error, reason} = Query.parse_on_fields(index, "guide", ["title", "missing"])
+      assert reason =~ "missing"
+      assert {:error, _} = Query.parse_on_fields(index, "guide", [])
+    end
+  end
+
   describe "error handling" do
     test "handles empty query string", %{index: index} do
       {:ok, parser} = Query.parser(index, ["title"])
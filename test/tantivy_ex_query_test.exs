@@ -82,4 +82,549 @@ defmodule TantivyExQueryTest do
 
     assert length(results) == 2
   end
+
+  test "counts matching documents without loading them", %{schema: schema, searcher: searcher} do
+    {:ok, all_query} = Query.all()
+    assert {:ok, 2} = Searcher.count(searcher, all_query)
+
+    {:ok, term_query} = Query.term(schema, "published", "true")
+    assert {:ok, 1} = Searcher.count(searcher, term_query)
+  end
+
+  test "reports searcher document and segment statistics", %{searcher: searcher} do
+    {:ok, stats} = Searcher.stats(searcher)
+
+    assert stats["num_docs"] == 2
+    assert stats["segment_count"] == length(stats["segments"])
+    assert stats["segments"] |> Enum.map(& &1["doc_count"]) |> Enum.sum() == 2
+    assert Enum.all?(stats["segments"], &(&1["deleted"] == 0))
+  end
+
+  test "scrolls through all matching documents in batches", %{searcher: searcher} do
+    {:ok, query} = Query.all()
+
+    {:ok, first_batch, cursor} = Searcher.scroll(searcher, query, 1)
+    assert length(first_batch) == 1
+    assert cursor != nil
+
+    {:ok, second_batch, cursor} = Searcher.scroll(searcher, query, 1, cursor)
+    assert length(second_batch) == 1
+    assert hd(first_batch)["id"] != hd(second_batch)["id"]
+
+    assert {:ok, [], nil} = Searcher.scroll(searcher, query, 1, cursor)
+
+    # A batch size far beyond the index size must not be reserved up front
+    assert {:ok, [_, _], _cursor} = Searcher.scroll(searcher, query, 1_000_000_000_000)
+  end
+
+  test "pages through sorted hits with a search_after cursor", %{searcher: searcher} do
+    {:ok, query} = Query.all()
+
+    assert {:ok, [first], 90.0} = Searcher.search_after(searcher, query, 1, "score", :desc)
+    assert first["id"] == 1002
+
+    assert {:ok, [second], 85.0} =
+             Searcher.search_after(searcher, query, 1, "score", :desc, 90.0)
+
+    assert second["id"] == 1001
+    assert {:ok, [], nil} = Searcher.search_after(searcher, query, 1, "score", :desc, 85.0)
+
+    assert {:ok, [_, _], nil} = Searcher.search_after(searcher, query, 5, "score", :asc, 80)
+    assert {:error, reason} = Searcher.search_after(searcher, query, 1, "title")
+    assert reason =~ "fast numeric or date field"
+  end
+
+  test "returns filter matches without scores", %{schema: schema, searcher: searcher} do
+    {:ok, query} = Query.term(schema, "published", true)
+
+    assert {:ok, [hit]} = Searcher.search_unscored(searcher, query, 10)
+    assert hit["id"] == 1001
+
+    # Without stored fields, as the fixture has a stored "score" field of its own
+    assert {:ok, [unscored]} = Searcher.search_unscored(searcher, query, 10, false)
+    refute Map.has_key?(unscored, "score")
+
+    {:ok, query} = Query.all()
+    assert {:ok, [bare]} = Searcher.search_unscored(searcher, query, 1, false)
+    assert Map.keys(bare) |> Enum.sort() == ["doc_id", "segment_ord"]
+  end
+
+  test "reads fast field values for document addresses", %{searcher: searcher} do
+    {:ok, query} = Query.all()
+    {:ok, docs, _cursor} = Searcher.scroll(searcher, query, 10)
+    addresses = Enum.map(docs, &{&1["segment_ord"], &1["doc_id"]})
+
+    assert {:ok, values} = Searcher.fast_field_values(searcher, "score", addresses)
+    assert values == Enum.map(docs, & &1["score"])
+
+    assert {:error, _} = Searcher.fast_field_values(searcher, "title", addresses)
+    assert {:error, _} = Searcher.fast_field_values(searcher, "score", [{99, 0}])
+  end
+
+  test "reads the value range of a fast field", %{index: index, writer: writer} do
+    {:ok, searcher} = Searcher.new(index)
+    assert {:ok, {85.0, 90.0}} = Searcher.field_range(searcher, "score")
+
+    :ok = IndexWriter.add_document(writer, %{"title" => "Low", "score" => 12.5})
+    :ok = IndexWriter.commit(writer)
+
+    {:ok, searcher} = Searcher.new(index)
+    assert {:ok, {12.5, 90.0}} = Searcher.field_range(searcher, "score")
+    assert {:error, _} = Searcher.field_range(searcher, "id")
+  end
+
+  test "collapses results to the best document per fast field value" do
+    schema =
+      Schema.new()
+      |> Schema.add_text_field("title", :text_stored)
+      |> Schema.add_text_field("domain", :fast_stored)
+
+    {:ok, index} = Index.create_in_ram(schema)
+    {:ok, writer} = IndexWriter.new(index, 50_000_000)
+
+    docs = [
+      %{"title" => "elixir elixir guide", "domain" => "a.com"},
+      %{"title" => "elixir notes", "domain" => "a.com"},
+      %{"title" => "elixir tutorial", "domain" => "b.com"}
+    ]
+
+    {:ok, _} = Document.add_batch(writer, docs, schema)
+    :ok = IndexWriter.commit(writer)
+    {:ok, searcher} = Searcher.new(index)
+
+    {:ok, query} = Query.term(schema, "title", "elixir")
+    {:ok, results} = Searcher.search_collapsed(searcher, query, 10, "domain")
+
+    assert length(results) == 2
+    assert results |> Enum.map(& &1["collapse_key"]) |> Enum.sort() == ["a.com", "b.com"]
+    # :text_stored indexes without frequencies, so the shorter a.com title scores higher
+    assert Enum.find(results, &(&1["domain"] == "a.com"))["title"] == "elixir notes"
+
+    assert {:error, _} = Searcher.search_collapsed(searcher, query, 10, "title")
+  end
+
+  test "highlights several fields of a hit in one call" do
+    schema =
+      Schema.new()
+      |> Schema.add_text_field("title", :text_stored)
+      |> Schema.add_text_field("body", :text_stored)
+      |> Schema.add_text_field("notes", :text)
+
+    {:ok, index} = Index.create_in_ram(schema)
+    {:ok, writer} = IndexWriter.new(index, 50_000_000)
+
+    doc = %{
+      "title" => "Learning Elixir",
+      "body" => "Pattern matching makes elixir code readable",
+      "notes" => "elixir everywhere"
+    }
+
+    {:ok, _} = Document.add(writer, doc, schema)
+    :ok = IndexWriter.commit(writer)
+    {:ok, searcher} = Searcher.new(index)
+
+    {:ok, query} = Query.term(schema, "title", "elixir")
+    {:ok, [hit], _cursor} = Searcher.scroll(searcher, query, 10)
+    address = {hit["segment_ord"], hit["doc_id"]}
+
+    assert {:ok, highlights} =
+             Searcher.highlight_multi(searcher, query, ["title", "body", "notes"], address)
+
+    assert highlights == %{"title" => "Learning <b>Elixir</b>"}
+
+    assert {:error, _} = Searcher.highlight_multi(searcher, query, ["missing"], address)
+  end
+
+  test "returns byte offsets of matched terms for client-side highlighting" do
+    schema =
+      Schema.new()
+      |> Schema.add_text_field("body", :text_stored)
+      |> Schema.add_text_field("notes", :text)
+
+    {:ok, index} = Index.create_in_ram(schema)
+    {:ok, writer} = IndexWriter.new(index, 50_000_000)
+
+    body = "Élixir makes elixir code readable"
+    {:ok, _} = Document.add(writer, %{"body" => body, "notes" => "elixir"}, schema)
+    :ok = IndexWriter.commit(writer)
+    {:ok, searcher} = Searcher.new(index)
+
+    {:ok, query} = Query.term(schema, "body", "elixir")
+    {:ok, [hit], _cursor} = Searcher.scroll(searcher, query, 10)
+    address = {hit["segment_ord"], hit["doc_id"]}
+
+    assert {:ok, [{start, stop}]} = Searcher.match_offsets(searcher, query, "body", address)
+    assert binary_part(body, start, stop - start) == "elixir"
+
+    assert {:error, _} = Searcher.match_offsets(searcher, query, "notes", address)
+    assert {:error, _} = Searcher.match_offsets(searcher, query, "body", {0, 99})
+  end
+
+  test "lists the distinct terms of a field across segments" do
+    schema =
+      Schema.new()
+      |> Schema.add_text_field("title", :text_stored)
+      |> Schema.add_u64_field("id", :indexed_stored)
+
+    {:ok, index} = Index.create_in_ram(schema)
+    {:ok, writer} = IndexWriter.new(index, 50_000_000)
+
+    {:ok, _} = Document.add(writer, %{"title" => "Elixir in Action", "id" => 1}, schema)
+    :ok = IndexWriter.commit(writer)
+    {:ok, _} = Document.add(writer, %{"title" => "Elixir and Elm", "id" => 2}, schema)
+    :ok = IndexWriter.commit(writer)
+    {:ok, searcher} = Searcher.new(index)
+
+    assert {:ok, [%{term: "elixir", doc_freq: 2}, %{term: "elm", doc_freq: 1}]} =
+             Searcher.field_terms(searcher, "title", "el")
+
+    assert {:ok, [%{term: "action"}, %{term: "and"}]} =
+             Searcher.field_terms(searcher, "title", "", 2)
+
+    assert {:ok, []} = Searcher.field_terms(searcher, "title", "zz")
+    assert {:error, _} = Searcher.field_terms(searcher, "id")
+  end
+
+  test "autocompletes a prefix by term frequency" do
+    schema = Schema.add_text_field(Schema.new(), "title", :text_stored)
+    {:ok, index} = Index.create_in_ram(schema)
+    {:ok, writer} = IndexWriter.new(index, 50_000_000)
+
+    ["Elm basics", "Elixir in Action", "Elixir and Erlang", "Programming Elixir"]
+    |> Enum.each(&({:ok, _} = Document.add(writer, %{"title" => &1}, schema)))
+
+    :ok = IndexWriter.commit(writer)
+    {:ok, searcher} = Searcher.new(index)
+
+    assert {:ok, completions} = Searcher.autocomplete(searcher, "title", "e")
+
+    assert Enum.map(completions, &{&1.term, &1.doc_freq}) == [
+             {"elixir", 3},
+             {"elm", 1},
+             {"erlang", 1}
+           ]
+
+    assert {:ok, [%{term: "elixir"}]} = Searcher.autocomplete(searcher, "title", "e", 1)
+  end
+
+  test "suggests dictionary terms close to a misspelling" do
+    schema = Schema.add_text_field(Schema.new(), "title", :text_stored)
+    {:ok, index} = Index.create_in_ram(schema)
+    {:ok, writer} = IndexWriter.new(index, 50_000_000)
+
+    ["Elixir in Action", "Programming Elixir", "Elixer typo", "Python basics"]
+    |> Enum.each(&(:ok = IndexWriter.add_document(writer, %{"title" => &1})))
+
+    :ok = IndexWriter.commit(writer)
+    {:ok, searcher} = Searcher.new(index)
+
+    assert {:ok, suggestions} = Searcher.suggest(searcher, "title", "Elixr")
+
+    assert Enum.map(suggestions, &{&1.term, &1.doc_freq, &1.distance}) == [
+             {"elixir", 2, 1},
+             {"elixer", 1, 1}
+           ]
+
+    # "elixer" is two edits away from "elixi", so a distance of 1 leaves only "elixir"
+    assert {:ok, [%{term: "elixir", distance: 1}]} =
+             Searcher.suggest(searcher, "title", "elixi", 1)
+
+    assert {:ok, [_, _]} = Searcher.suggest(searcher, "title", "elixi", 2)
+    assert {:ok, []} = Searcher.suggest(searcher, "title", "elixr", 0)
+    assert {:error, _} = Searcher.suggest(searcher, "title", "elixr", 3)
+    assert {:error, _} = Searcher.suggest(searcher, "title", "two words")
+  end
+
+  test "parses fields with a query-time tokenizer override" do
+    schema = Schema.add_text_field_with_tokenizer(Schema.new(), "tag", :text_stored, "raw")
+    {:ok, index} = Index.create_in_ram(schema)
+    {:ok, writer} = IndexWriter.new(index, 50_000_000)
+    {:ok, _} = Document.add(writer, %{"tag" => "elixir"}, schema)
+    :ok = IndexWriter.commit(writer)
+    {:ok, searcher} = Searcher.new(index)
+
+    {:ok, raw_parser} = Query.parser(index, ["tag"])
+    {:ok, query} = Query.parse(raw_parser, "Elixir")
+    assert {:ok, 0} = Searcher.count(searcher, query)
+
+    {:ok, parser} = Query.parser(index, ["tag"], field_tokenizers: %{"tag" => "default"})
+    {:ok, query} = Query.parse(parser, "Elixir")
+    assert {:ok, 1} = Searcher.count(searcher, query)
+
+    assert {:error, reason} =
+             Query.parser(index, ["tag"], field_tokenizers: %{"tag" => "no_such_tokenizer"})
+
+    assert reason =~ "not registered"
+  end
+
+  test "returns doc and total term frequencies for a term" do
+    schema =
+      Schema.new()
+      |> Schema.add_text_field("body", :fast_stored)
+      |> Schema.add_text_field("summary", :text)
+
+    {:ok, index} = Index.create_in_ram(schema)
+    {:ok, writer} = IndexWriter.new(index, 50_000_000)
+
+    ["elixir elixir elixir", "Elixir and Erlang", "just erlang"]
+    |> Enum.each(&({:ok, _} = Document.add(writer, %{"body" => &1, "summary" => &1}, schema)))
+
+    :ok = IndexWriter.commit(writer)
+    {:ok, searcher} = Searcher.new(index)
+
+    assert {:ok, %{doc_freq: 2, total_term_freq: 4}} =
+             Searcher.term_stats(searcher, "body", "Elixir")
+
+    assert {:ok, %{doc_freq: 0, total_term_freq: 0}} =
+             Searcher.term_stats(searcher, "body", "phoenix")
+
+    assert {:error, _} = Searcher.term_stats(searcher, "body", "elixir erlang")
+
+    # Without indexed frequencies only the document count is known
+    assert {:ok, %{doc_freq: 2, total_term_freq: nil}} =
+             Searcher.term_stats(searcher, "summary", "elixir")
+  end
+
+  test "reports deleted documents alongside search totals" do
+    schema =
+      Schema.new()
+      |> Schema.add_text_field("title", :text_stored)
+      |> Schema.add_u64_field("id", :indexed_stored)
+
+    {:ok, index} = Index.create_in_ram(schema)
+    {:ok, writer} = IndexWriter.new(index, 50_000_000)
+
+    for id <- 1..3 do
+      {:ok, _} = Document.add(writer, %{"title" => "elixir #{id}", "id" => id}, schema)
+    end
+
+    :ok = IndexWriter.commit(writer)
+    {:ok, deleted} = Query.term(schema, "id", "2")
+    {:ok, _} = IndexWriter.delete_documents(writer, deleted)
+    :ok = IndexWriter.commit(writer)
+    {:ok, searcher} = Searcher.new(index)
+
+    {:ok, query} = Query.term(schema, "title", "elixir")
+    assert {:ok, result} = Searcher.search_with_totals(searcher, query, 1, false)
+
+    assert [%{"doc_id" => _, "score" => _}] = result["hits"]
+    assert result["total_hits"] == 2
+    assert result["num_docs"] == 2
+    assert result["max_doc"] == 3
+    assert result["deleted_docs"] == 1
+  end
+
+  test "runs several queries in one call", %{schema: schema, searcher: searcher} do
+    {:ok, published} = Query.term(schema, "published", "true")
+    {:ok, by_id} = Query.term(schema, "id", "1002")
+    {:ok, all} = Query.all()
+
+    assert {:ok, [{0, {:ok, [first]}}, {1, {:ok, [second]}}, {2, {:ok, everything}}]} =
+             Searcher.multi_search(searcher, [published, by_id, all], 10)
+
+    assert first["id"] == 1001
+    assert second["id"] == 1002
+    assert length(everything) == 2
+
+    assert {:ok, [{0, {:ok, [hit]}}]} = Searcher.multi_search(searcher, [all], 1, false)
+    assert Map.keys(hit) |> Enum.sort() == ["doc_id", "score"]
+    assert {:ok, []} = Searcher.multi_search(searcher, [])
+  end
+
+  test "returns bytes fields as base64 or raw binaries" do
+    schema =
+      Schema.new()
+      |> Schema.add_text_field("title", :text_stored)
+      |> Schema.add_bytes_field("thumbnail", :stored)
+
+    {:ok, index} = Index.create_in_ram(schema)
+    {:ok, writer} = IndexWriter.new(index, 50_000_000)
+
+    thumbnail = <<0, 137, 80, 78, 71, 255>>
+    doc = %{"title" => "with image", "thumbnail" => Base.encode64(thumbnail)}
+    {:ok, _} = Document.add(writer, doc, schema)
+    :ok = IndexWriter.commit(writer)
+    {:ok, searcher} = Searcher.new(index)
+    {:ok, query} = Query.all()
+
+    assert {:ok, [hit]} = Searcher.search(searcher, query, 10)
+    assert hit["thumbnail"] == Base.encode64(thumbnail)
+
+    assert {:ok, [hit]} = Searcher.search(searcher, query, 10, true, bytes_as: :binary)
+    assert hit["thumbnail"] == thumbnail
+    assert hit["title"] == "with image"
+
+    assert {:error, _} = Searcher.search(searcher, query, 10, true, bytes_as: :hex)
+  end
+
+  test "aborts a search once its timeout has elapsed", %{schema: schema, searcher: searcher} do
+    {:ok, query} = Query.regex(schema, "title", ".*test.*")
+
+    assert {:error, :timeout} = Searcher.search(searcher, query, 10, true, timeout_ms: 0)
+    assert {:ok, [_ | _]} = Searcher.search(searcher, query, 10, true, timeout_ms: 60_000)
+
+    assert {:ok, [_ | _]} =
+             Searcher.search(searcher, query, 10, true, timeout_ms: 0xFFFFFFFFFFFFFFFF)
+    assert {:error, _} = Searcher.search(searcher, query, 10, true, timeout_ms: -1)
+  end
+
+  test "attaches score explanations when asked to", %{schema: schema, searcher: searcher} do
+    {:ok, query} = Query.term(schema, "title", "test")
+
+    # Without stored fields, so the fixture's own "score" field doesn't replace the hit score
+    assert {:ok, [hit | _] = hits} = Searcher.search(searcher, query, 10, false, explain: true)
+    assert length(hits) == 2
+    assert %{"value" => value, "description" => _} = hit["explanation"]
+    assert_in_delta value, hit["score"], 1.0e-4
+
+    assert {:ok, [plain | _]} = Searcher.search(searcher, query, 10)
+    refute Map.has_key?(plain, "explanation")
+    assert {:error, _} = Searcher.search(searcher, query, 10, true, explain: :yes)
+  end
+
+  test "fetches a document by its unique key", %{searcher: searcher} do
+    assert {:ok, doc} = Searcher.get_by_term(searcher, "id", 1001)
+    assert doc["title"] == "Term Test Document"
+
+    assert {:ok, doc} = Searcher.get_by_term(searcher, "id", "1001", ["title"])
+    assert doc == %{"title" => "Term Test Document"}
+
+    assert {:error, :not_found} = Searcher.get_by_term(searcher, "id", 9999)
+    assert {:error, reason} = Searcher.get_by_term(searcher, "title", "test")
+    assert reason =~ "Multiple documents"
+    assert {:error, _} = Searcher.get_by_term(searcher, "missing", "x")
+  end
+
+  test "stored JSON objects come back as real JSON" do
+    schema =
+      Schema.new()
+      |> Schema.add_text_field("title", :text_stored)
+      |> Schema.add_json_field("metadata", :stored)
+
+    {:ok, index} = Index.create_in_ram(schema)
+    {:ok, writer} = IndexWriter.new(index, 50_000_000)
+
+    metadata = %{
+      "category" => "test",
+      "priority" => 1,
+      "tags" => ["a", "b"],
+      "owner" => %{"name" => "jane", "active" => true}
+    }
+
+    {:ok, _} = Document.add(writer, %{"title" => "JSON Doc", "metadata" => metadata}, schema)
+    :ok = IndexWriter.commit(writer)
+
+    {:ok, searcher} = Searcher.new(index)
+    {:ok, query} = Query.all()
+    {:ok, [result]} = Searcher.search(searcher, query, 10)
+
+    assert result["metadata"] == metadata
+  end
+
+  test "json_path queries match values inside JSON objects" do
+    schema =
+      Schema.new()
+      |> Schema.add_text_field("title", :text_stored)
+      |> Schema.add_json_field("metadata", :text_stored)
+
+    {:ok, index} = Index.create_in_ram(schema)
+    {:ok, writer} = IndexWriter.new(index, 50_000_000)
+
+    docs = [
+      %{"title" => "first", "metadata" => %{"status" => "active", "stats" => %{"views" => 42}}},
+      %{"title" => "second", "metadata" => %{"status" => "archived", "stats" => %{"views" => 7}}}
+    ]
+
+    Enum.each(docs, &({:ok, _} = Document.add(writer, &1, schema)))
+    :ok = IndexWriter.commit(writer)
+    {:ok, searcher} = Searcher.new(index)
+
+    {:ok, query} = Query.json_path(schema, "metadata", "status", "Active")
+    assert {:ok, [%{"title" => "first"}]} = Searcher.search(searcher, query, 10)
+
+    {:ok, query} = Query.json_path(schema, "metadata", "stats.views", 7)
+    assert {:ok, [%{"title" => "second"}]} = Searcher.search(searcher, query, 10)
+
+    assert {:error, _} = Query.json_path(schema, "title", "status", "active")
+  end
+
+  test "describes queries as text and structured JSON", %{schema: schema} do
+    {:ok, id_query} = Query.term(schema, "id", "1001")
+    {:ok, all_query} = Query.all()
+    {:ok, query} = Query.boolean([id_query], [all_query], [])
+
+    {:ok, text} = Query.to_debug_string(query)
+    assert text =~ "BooleanQuery"
+    # Fields are identified by their position in the schema, "id" being the third
+    assert text =~ "TermQuery(Term(field=2, type=U64, 1001))"
+    assert text =~ "AllQuery"
+
+    assert {:ok,
+            %{
+              "type" => "boolean",
+              "clauses" => [
+                %{
+                  "occur" => "must",
+                  "query" => %{"type" => "term", "field" => "id", "value" => "1001"}
+                },
+                %{"occur" => "should", "query" => %{"type" => "all"}}
+              ]
+            }} = Query.to_json(query, schema)
+  end
+
+  test "normalized keys ignore boolean clause order", %{schema: schema} do
+    {:ok, id_query} = Query.term(schema, "id", "1001")
+    {:ok, title_query} = Query.term(schema, "title", "elixir")
+    {:ok, published_query} = Query.term(schema, "published", "true")
+
+    {:ok, first} = Query.boolean([id_query, title_query], [published_query], [])
+    {:ok, second} = Query.boolean([title_query, id_query], [published_query], [])
+    {:ok, negated} = Query.boolean([title_query, id_query], [], [published_query])
+
+    {:ok, first_key} = Query.normalized_key(first, schema)
+    assert {:ok, ^first_key} = Query.normalized_key(second, schema)
+    assert {:ok, negated_key} = Query.normalized_key(negated, schema)
+    assert negated_key != first_key
+
+    # Boolean clauses nested in a disjunction are sorted too
+    {:ok, first_max} = Query.disjunction_max([first, title_query], 0.1)
+    {:ok, second_max} = Query.disjunction_max([title_query, second], 0.1)
+    {:ok, first_max_key} = Query.normalized_key(first_max, schema)
+    assert {:ok, ^first_max_key} = Query.normalized_key(second_max, schema)
+  end
+
+  test "extracts terms with their field names", %{schema: schema} do
+    {:ok, id_query} = Query.term(schema, "id", "1001")
+    {:ok, published_query} = Query.term(schema, "published", "true")
+    {:ok, query} = Query.boolean([id_query], [published_query], [])
+
+    assert {:ok, terms} = Query.extract_terms(query, schema)
+    assert {"id", "1001"} in terms
+    assert {"published", "true"} in terms
+
+    {:ok, all_query} = Query.all()
+    assert {:ok, []} = Query.extract_terms(all_query, schema)
+  end
+
+  test "term query accepts RFC3339 strings for date fields", %{
+    schema: schema,
+    searcher: searcher
+  } do
+    {:ok, query} = Query.term(schema, "created_at", "2023-01-02T00:00:00Z")
+    {:ok, results} = Searcher.search(searcher, query, 10)
+    assert Enum.map(results, & &1["id"]) == [1002]
+
+    {:ok, datetime, 0} = DateTime.from_iso8601("2023-01-01T00:00:00Z")
+    {:ok, query} = Query.term(schema, "created_at", datetime)
+    {:ok, results} = Searcher.search(searcher, query, 10)
+    assert Enum.map(results, & &1["id"]) == [1001]
+
+    assert {:error, reason} = Query.term(schema, "created_at", "yesterday")
+    assert reason =~ "RFC3339"
+  end
+
+  test "term query rejects non-numeric values for numeric fields", %{schema: schema} do
+    assert {:error, reason} = Query.term(schema, "id", "abc")
+    assert reason =~ "Invalid u64 value"
+  end
 end
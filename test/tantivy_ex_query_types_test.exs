@@ -88,6 +88,24 @@ defmodule TantivyEx.QueryTypesTest do
       assert {:error, reason} = Query.term(schema, "invalid_field", "test")
       assert is_binary(reason)
     end
+
+    test "matches raw-tokenized fields on the verbatim value" do
+      schema =
+        Schema.new()
+        |> Schema.add_text_field_with_tokenizer("sku", :text_stored, "raw")
+
+      {:ok, index} = Index.create_in_ram(schema)
+      {:ok, writer} = IndexWriter.new(index, 50_000_000)
+      :ok = IndexWriter.add_document(writer, %{"sku" => "ABC-123 Blue"})
+      :ok = IndexWriter.commit(writer)
+      {:ok, searcher} = Searcher.new(index)
+
+      {:ok, query} = Query.term(schema, "sku", "ABC-123 Blue")
+      assert {:ok, [%{"sku" => "ABC-123 Blue"}]} = Searcher.search(searcher, query, 10, true)
+
+      {:ok, query} = Query.term(schema, "sku", "abc-123 blue")
+      assert {:ok, []} = Searcher.search(searcher, query, 10, true)
+    end
   end
 
   describe "phrase queries" do
@@ -103,10 +121,37 @@ defmodule TantivyEx.QueryTypesTest do
       assert is_list(results)
     end
 
+    test "analyzes raw phrase text with the field tokenizer", %{
+      schema: schema,
+      searcher: searcher
+    } do
+      assert {:ok, query} = Query.phrase_text(schema, "content", "Programming LANGUAGE")
+      assert {:ok, [%{"title" => "Rust Programming Guide"}]} =
+               Searcher.search(searcher, query, 10)
+
+      {:ok, query} = Query.phrase_text(schema, "content", "rust language")
+      assert {:ok, []} = Searcher.search(searcher, query, 10)
+
+      {:ok, query} = Query.phrase_text(schema, "content", "rust language", 1)
+      assert {:ok, [_]} = Searcher.search(searcher, query, 10)
+
+      assert {:error, _} = Query.phrase_text(schema, "price", "299")
+    end
+
     test "handles empty phrase list", %{schema: schema} do
       assert {:error, reason} = Query.phrase(schema, "title", [])
       assert is_binary(reason)
     end
+
+    test "rejects phrase queries on fields without positions" do
+      schema = Schema.new() |> Schema.add_text_field("body", :text)
+
+      assert {:error, reason} = Query.phrase(schema, "body", ["hello", "world"])
+      assert reason =~ "has no positions"
+      assert {:error, _} = Query.phrase_prefix(schema, "body", ["hello", "wor"])
+      assert {:error, _} = Query.phrase_text(schema, "body", "hello world")
+      assert {:ok, _} = Query.phrase_text(schema, "body", "hello")
+    end
   end
 
   describe "range queries" do
@@ -142,6 +187,69 @@ defmodule TantivyEx.QueryTypesTest do
           assert is_binary(reason)
       end
     end
+
+    test "creates geo bounding box query" do
+      schema =
+        Schema.new()
+        |> Schema.add_text_field("city", :text_stored)
+        |> Schema.add_f64_field("lat", :fast_stored)
+        |> Schema.add_f64_field("lon", :fast_stored)
+
+      {:ok, index} = Index.create_in_ram(schema)
+      {:ok, writer} = IndexWriter.new(index, 50_000_000)
+
+      cities = [
+        {"Paris", 48.8566, 2.3522},
+        {"Versailles", 48.8049, 2.1204},
+        {"Lyon", 45.764, 4.8357},
+        {"Suva", -18.1248, 178.4501},
+        {"Apia", -13.8333, -171.7667}
+      ]
+
+      for {city, lat, lon} <- cities do
+        :ok = IndexWriter.add_document(writer, %{"city" => city, "lat" => lat, "lon" => lon})
+      end
+
+      :ok = IndexWriter.commit(writer)
+      {:ok, searcher} = Searcher.new(index)
+
+      cities_in = fn lat_range, lon_range ->
+        {:ok, query} = Query.geo_bounding_box(schema, "lat", "lon", lat_range, lon_range)
+        {:ok, results} = Searcher.search(searcher, query, 10, true)
+        results |> Enum.map(& &1["city"]) |> Enum.sort()
+      end
+
+      assert cities_in.({48.7, 49}, {2, 2.5}) == ["Paris", "Versailles"]
+      assert cities_in.({48.7, 49}, {2.2, 2.5}) == ["Paris"]
+      assert cities_in.({-20, -10}, {170, -170}) == ["Apia", "Suva"]
+
+      assert {:error, _} = Query.geo_bounding_box(schema, "lat", "lon", {49, 48}, {2, 3})
+      assert {:error, _} = Query.geo_bounding_box(schema, "lat", "lon", {48, 49}, {2, 200})
+      assert {:error, _} = Query.geo_bounding_box(schema, "city", "lon", {48, 49}, {2, 3})
+    end
+
+    test "creates lexicographic text range query" do
+      schema = Schema.add_text_field_with_tokenizer(Schema.new(), "sku", :text_stored, "raw")
+      {:ok, index} = Index.create_in_ram(schema)
+      {:ok, writer} = IndexWriter.new(index, 50_000_000)
+
+      Enum.each(["A099", "A100", "A150", "A200", "B001"], fn sku ->
+        :ok = IndexWriter.add_document(writer, %{"sku" => sku})
+      end)
+
+      :ok = IndexWriter.commit(writer)
+      {:ok, searcher} = Searcher.new(index)
+
+      {:ok, query} = Query.range_text(schema, "sku", "A100", "A200", {true, false})
+      {:ok, results} = Searcher.search(searcher, query, 10)
+      assert results |> Enum.map(& &1["sku"]) |> Enum.sort() == ["A100", "A150"]
+
+      {:ok, query} = Query.range_text(schema, "sku", "A200", nil)
+      {:ok, results} = Searcher.search(searcher, query, 10)
+      assert results |> Enum.map(& &1["sku"]) |> Enum.sort() == ["A200", "B001"]
+
+      assert {:error, _} = Query.range_text(schema, "missing", "A", "B")
+    end
   end
 
   describe "boolean queries" do
@@ -195,6 +303,47 @@ defmodule TantivyEx.QueryTypesTest do
     end
   end
 
+  describe "disjunction max queries" do
+    test "matches documents from any sub-query", %{schema: schema, searcher: searcher} do
+      {:ok, price_query} = Query.range_u64(schema, "price", 200, 300)
+      {:ok, rating_query} = Query.range_f64(schema, "rating", 4.4, 5.0)
+
+      assert {:ok, query} = Query.disjunction_max([price_query, rating_query], 0.1)
+      assert {:ok, results} = Searcher.search(searcher, query, 10, true)
+      assert length(results) == 3
+    end
+
+    test "rejects an empty query list or invalid tie breaker", %{schema: schema} do
+      {:ok, price_query} = Query.range_u64(schema, "price", 200, 300)
+
+      assert {:error, _} = Query.disjunction_max([])
+      assert {:error, _} = Query.disjunction_max([price_query], 1.5)
+    end
+  end
+
+  describe "weighted terms queries" do
+    test "ranks matches by the boost of their terms", %{schema: schema, searcher: searcher} do
+      {:ok, query} =
+        Query.weighted_terms(schema, [{"title", "rust", 1.0}, {"title", "elixir", 5}])
+
+      assert {:ok, [first, second]} = Searcher.search(searcher, query, 10, true)
+      assert first["title"] == "Elixir Cookbook"
+      assert second["title"] == "Rust Programming Guide"
+
+      {:ok, query} =
+        Query.weighted_terms(schema, [{"title", "rust", 5.0}, {"title", "elixir", 1.0}])
+
+      assert {:ok, [%{"title" => "Rust Programming Guide"}, _]} =
+               Searcher.search(searcher, query, 10, true)
+    end
+
+    test "rejects empty, negatively boosted or unknown terms", %{schema: schema} do
+      assert {:error, _} = Query.weighted_terms(schema, [])
+      assert {:error, _} = Query.weighted_terms(schema, [{"title", "rust", -1.0}])
+      assert {:error, _} = Query.weighted_terms(schema, [{"missing", "rust", 1.0}])
+    end
+  end
+
   describe "fuzzy queries" do
     test "creates fuzzy query with default parameters", %{schema: schema, searcher: searcher} do
       # Misspelled
@@ -214,6 +363,26 @@ defmodule TantivyEx.QueryTypesTest do
       assert {:ok, results} = Searcher.search(searcher, query, 10, true)
       assert is_list(results)
     end
+
+    test "counts a transposition as one edit unless disabled" do
+      schema = Schema.add_text_field(Schema.new(), "title", :text_stored)
+      {:ok, index} = Index.create_in_ram(schema)
+      {:ok, writer} = IndexWriter.new(index, 50_000_000)
+      :ok = IndexWriter.add_document(writer, %{"title" => "the quick fox"})
+      :ok = IndexWriter.commit(writer)
+      {:ok, searcher} = Searcher.new(index)
+
+      {:ok, query} = Query.fuzzy(schema, "title", "teh", 1)
+      assert {:ok, [_]} = Searcher.search(searcher, query, 10)
+
+      {:ok, query} = Query.fuzzy(schema, "title", "teh", 1, false, transposition_cost_one: false)
+      assert {:ok, []} = Searcher.search(searcher, query, 10)
+
+      {:ok, query} = Query.fuzzy(schema, "title", "quik", 1, true)
+      assert {:ok, [_]} = Searcher.search(searcher, query, 10)
+
+      assert {:error, _} = Query.fuzzy(schema, "title", "teh", 3)
+    end
   end
 
   describe "wildcard queries" do
@@ -228,6 +397,34 @@ defmodule TantivyEx.QueryTypesTest do
       assert {:ok, results} = Searcher.search(searcher, query, 10, true)
       assert is_list(results)
     end
+
+    test "treats regex metacharacters in the pattern literally" do
+      schema =
+        Schema.new()
+        |> Schema.add_text_field_with_tokenizer("sku", :text_stored, "raw")
+
+      {:ok, index} = Index.create_in_ram(schema)
+      {:ok, writer} = IndexWriter.new(index, 50_000_000)
+
+      for sku <- ["AB-12.5", "AB-1205", "ABX12", "AB-13"] do
+        IndexWriter.add_document(writer, %{"sku" => sku})
+      end
+
+      IndexWriter.commit(writer)
+      {:ok, searcher} = Searcher.new(index)
+
+      {:ok, prefix_query} = Query.wildcard(schema, "sku", "AB-12*")
+      {:ok, results} = Searcher.search(searcher, prefix_query, 10, true)
+      assert results |> Enum.map(& &1["sku"]) |> Enum.sort() == ["AB-12.5", "AB-1205"]
+
+      {:ok, dotted_query} = Query.wildcard(schema, "sku", "*12.5")
+      {:ok, results} = Searcher.search(searcher, dotted_query, 10, true)
+      assert Enum.map(results, & &1["sku"]) == ["AB-12.5"]
+
+      {:ok, single_char_query} = Query.wildcard(schema, "sku", "AB?12")
+      {:ok, results} = Searcher.search(searcher, single_char_query, 10, true)
+      assert Enum.map(results, & &1["sku"]) == ["ABX12"]
+    end
   end
 
   describe "regex queries" do
@@ -251,6 +448,17 @@ defmodule TantivyEx.QueryTypesTest do
           assert is_binary(reason)
       end
     end
+
+    test "reports the position of an invalid regex", %{schema: schema} do
+      assert {:error, reason} = Query.regex(schema, "title", "ab(cd")
+      assert reason =~ "position 3"
+    end
+
+    test "supports case-insensitive matching", %{schema: schema, searcher: searcher} do
+      {:ok, query} = Query.regex(schema, "title", "RUST.*", case_insensitive: true)
+      assert {:ok, results} = Searcher.search(searcher, query, 10, true)
+      assert length(results) == 1
+    end
   end
 
   describe "phrase prefix queries" do
@@ -284,6 +492,43 @@ defmodule TantivyEx.QueryTypesTest do
       assert {:error, reason} = Query.exists(schema, "non_existent_field")
       assert is_binary(reason)
     end
+
+    test "checks sub-paths of JSON fields" do
+      schema =
+        Schema.new()
+        |> Schema.add_text_field("title", :text_stored)
+        |> Schema.add_json_field("metadata", :fast_stored)
+
+      {:ok, index} = Index.create_in_ram(schema)
+      {:ok, writer} = IndexWriter.new(index, 50_000_000)
+
+      docs = [
+        %{"title" => "promoted", "metadata" => %{"promoted" => true, "tags" => %{"a" => 1}}},
+        %{"title" => "tagged", "metadata" => %{"tags" => %{"b" => 2}}},
+        %{"title" => "plain", "metadata" => %{"color" => "red"}}
+      ]
+
+      Enum.each(docs, fn doc -> :ok = IndexWriter.add_document(writer, doc) end)
+      :ok = IndexWriter.commit(writer)
+      {:ok, searcher} = Searcher.new(index)
+
+      titles = fn query ->
+        {:ok, results} = Searcher.search(searcher, query, 10, true)
+        results |> Enum.map(& &1["title"]) |> Enum.sort()
+      end
+
+      assert {:ok, promoted} = Query.exists(schema, "metadata.promoted")
+      assert titles.(promoted) == ["promoted"]
+
+      assert {:ok, tags} = Query.exists(schema, "metadata.tags")
+      assert titles.(tags) == []
+
+      assert {:ok, nested_tags} = Query.exists(schema, "metadata.tags", json_subpaths: true)
+      assert titles.(nested_tags) == ["promoted", "tagged"]
+
+      assert {:error, "Field 'title' must be a fast field for exists queries"} =
+               Query.exists(schema, "title")
+    end
   end
 
   describe "special queries" do
@@ -2,7 +2,7 @@ defmodule TantivyEx.ReaderManagerTest do
   use ExUnit.Case, async: false
   doctest TantivyEx.ReaderManager
 
-  alias TantivyEx.{Schema, Index, IndexWriter, ReaderManager}
+  alias TantivyEx.{Schema, Index, IndexWriter, ReaderManager, Native}
 
   setup do
     # Create a test schema
@@ -263,4 +263,32 @@ defmodule TantivyEx.ReaderManagerTest do
       end
     end
   end
+
+  describe "reader manager events" do
+    test "sends lifecycle events to the subscribed process", %{index: index} do
+      {:ok, manager} = ReaderManager.new()
+      :ok = ReaderManager.subscribe(manager)
+
+      :ok =
+        Native.reader_manager_create_policy(manager, "p", "manual", 3600, 60, false, false, false)
+
+      :ok = Native.reader_manager_create_reader(manager, index, "r1", "p")
+      assert_receive {:tantivy_reader_event, %{event: :created, reader_id: "r1"}}
+
+      _ = Native.reader_manager_reload_reader(manager, "r1", true)
+      assert_receive {:tantivy_reader_event, %{event: :reloaded, reader_id: "r1"}}
+
+      :ok = Native.reader_manager_record_search(manager, "r1", 12)
+
+      assert_receive {:tantivy_reader_event,
+                      %{event: :search_performed, reader_id: "r1", duration_ms: 12}}
+
+      :ok = Native.reader_manager_dispose_reader(manager, "r1")
+      assert_receive {:tantivy_reader_event, %{event: :disposed, reader_id: "r1"}}
+
+      :ok = ReaderManager.unsubscribe(manager)
+      :ok = Native.reader_manager_create_reader(manager, index, "r2", "p")
+      refute_receive {:tantivy_reader_event, _}
+    end
+  end
 end
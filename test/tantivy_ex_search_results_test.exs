@@ -502,7 +502,9 @@ defmodule TantivyEx.SearchResultsTest do
       assert "programming" in terms
     end
 
-    test "extracts terms from phrase queries", %{schema: schema} do
+    test "extracts terms from phrase queries" do
+      # Phrase queries need positions, which only :fast_stored text fields index
+      schema = Schema.new() |> Schema.add_text_field("title", :fast_stored)
       {:ok, phrase_query} = Query.phrase(schema, "title", ["functional", "programming"])
       {:ok, terms} = SearchResults.extract_query_terms(phrase_query, schema)
 
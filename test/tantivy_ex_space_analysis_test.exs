@@ -2,7 +2,7 @@ defmodule TantivyEx.SpaceAnalysisTest do
   use ExUnit.Case, async: true
   doctest TantivyEx.SpaceAnalysis
 
-  alias TantivyEx.{SpaceAnalysis, Schema, Index, IndexWriter}
+  alias TantivyEx.{SpaceAnalysis, Schema, Index, IndexWriter, Query}
 
   setup do
     # Create a simple test index for analysis
@@ -39,7 +39,7 @@ defmodule TantivyEx.SpaceAnalysisTest do
 
     IndexWriter.commit(writer)
 
-    {:ok, index: index}
+    {:ok, index: index, schema: schema, writer: writer}
   end
 
   describe "space analysis lifecycle" do
@@ -68,6 +68,24 @@ defmodule TantivyEx.SpaceAnalysisTest do
       {:ok, _analysis} = SpaceAnalysis.analyze_index(analyzer, index, "test_snapshot")
     end
 
+    test "reports live and deleted document counts", %{
+      index: index,
+      schema: schema,
+      writer: writer
+    } do
+      {:ok, query} = Query.term(schema, "id", 2)
+      {:ok, _} = IndexWriter.delete_documents(writer, query)
+      :ok = IndexWriter.commit(writer)
+
+      {:ok, analyzer} = SpaceAnalysis.new()
+      {:ok, analysis} = SpaceAnalysis.analyze_index(analyzer, index, "with_deletes")
+
+      assert Enum.sum(Enum.map(analysis.segments, & &1.doc_count)) == 2
+      assert Enum.sum(Enum.map(analysis.segments, & &1.deleted_docs)) == 1
+      assert analysis.index_metadata.total_docs == 2
+      assert analysis.index_metadata.deleted_docs == 1
+    end
+
     test "handles optimization recommendations" do
       {:ok, analyzer} = SpaceAnalysis.new()
 
@@ -27,6 +27,44 @@ defmodule TantivyExTest do
       schema = Schema.add_u64_field(schema, "quantity", :indexed)
       assert is_reference(schema)
     end
+
+    test "removes a field from schema" do
+      schema =
+        Schema.new()
+        |> Schema.add_text_field("title", :text_stored)
+        |> Schema.add_u64_field("price", :indexed_stored)
+        |> Schema.add_text_field("body", :text)
+
+      schema = Schema.remove_field(schema, "price")
+      assert Schema.get_field_names(schema) == ["title", "body"]
+      assert {:ok, "text"} = Schema.get_field_type(schema, "body")
+
+      assert_raise RuntimeError, ~r/not found/, fn ->
+        Schema.remove_field(schema, "price")
+      end
+    end
+
+    test "builds a schema from a field list in one call" do
+      schema =
+        Schema.build([
+          {"title", :text, :text_stored},
+          %{name: "body", type: :text, options: :text, tokenizer: "en_stem"},
+          {"price", :f64, :fast_stored},
+          {"tags", :facet}
+        ])
+
+      assert Schema.get_field_names(schema) == ["title", "body", "price", "tags"]
+      assert {:ok, "f64"} = Schema.get_field_type(schema, "price")
+      assert {:ok, "facet"} = Schema.get_field_type(schema, "tags")
+
+      assert_raise RuntimeError, ~r/Duplicate field 'title'/, fn ->
+        Schema.build([{"title", :text}, {"title", :u64}])
+      end
+
+      assert_raise RuntimeError, ~r/Unknown type 'vector'/, fn ->
+        Schema.build([{"embedding", :vector}])
+      end
+    end
   end
 
   describe "index operations" do
@@ -52,6 +90,139 @@ defmodule TantivyExTest do
       # Clean up
       File.rm_rf!(tmp_dir)
     end
+
+    test "creates index in directory with docstore settings", %{schema: schema} do
+      tmp_dir = System.tmp_dir!() <> "/tantivy_settings_test_#{:rand.uniform(1_000_000)}"
+
+      assert {:ok, index} =
+               Index.create_in_dir(tmp_dir, schema,
+                 docstore_compression: {:zstd, 5},
+                 docstore_blocksize: 32_768
+               )
+
+      {:ok, writer} = IndexWriter.new(index)
+      :ok = IndexWriter.add_document(writer, %{"title" => "Compressed", "body" => "zstd"})
+      :ok = IndexWriter.commit(writer)
+
+      {:ok, searcher} = Searcher.new(index)
+      {:ok, query} = TantivyEx.Query.all()
+      assert {:ok, [%{"title" => "Compressed"}]} = Searcher.search(searcher, query, 10)
+
+      assert {:error, _} =
+               Index.create_in_dir(tmp_dir <> "_bad", schema, docstore_compression: :brotli)
+
+      assert {:error, reason} =
+               Index.create_in_dir(tmp_dir <> "_sorted", schema, sort_by_field: {"id", :asc})
+
+      assert reason =~ "sort_by_field"
+
+      assert {:error, _} =
+               TantivyEx.Native.index_create_in_dir_with_settings(
+                 tmp_dir <> "_sorted",
+                 schema,
+                 ~s({"sort_by_field": {"field": "id", "order": "asc"}})
+               )
+
+      File.rm_rf!(tmp_dir)
+    end
+
+    test "checks whether an index exists in a directory", %{schema: schema} do
+      tmp_dir = System.tmp_dir!() <> "/tantivy_exists_test_#{:rand.uniform(1_000_000)}"
+
+      refute Index.exists?(tmp_dir)
+
+      File.mkdir_p!(tmp_dir)
+      refute Index.exists?(tmp_dir)
+
+      {:ok, _index} = Index.create_in_dir(tmp_dir, schema)
+      assert Index.exists?(tmp_dir)
+
+      File.rm_rf!(tmp_dir)
+    end
+
+    test "round-trips an index through bytes", %{schema: schema} do
+      {:ok, index} = Index.create_in_ram(schema)
+      {:ok, writer} = IndexWriter.new(index)
+      :ok = IndexWriter.add_document(writer, %{"title" => "Snapshot", "body" => "stored in ram"})
+      :ok = IndexWriter.commit(writer)
+
+      assert {:ok, bytes} = Index.save_to_bytes(index)
+      assert {:ok, restored} = Index.open_from_bytes(bytes, schema)
+
+      {:ok, searcher} = Searcher.new(restored)
+      {:ok, query} = TantivyEx.Query.all()
+      {:ok, results} = Searcher.search(searcher, query, 10)
+      assert [%{"title" => "Snapshot"}] = results
+
+      assert {:error, _} = Index.open_from_bytes("not an index", schema)
+
+      # A well-formed snapshot without meta.json is not an index
+      assert {:error, _} = Index.open_from_bytes("TEXIDX01" <> <<0::little-32>>, schema)
+
+      # A huge file count must fail on the missing entries, not on allocation
+      assert {:error, _} = Index.open_from_bytes("TEXIDX01" <> <<0xFFFFFFFF::little-32>>, schema)
+    end
+
+    test "snapshots the committed index into another directory", %{schema: schema} do
+      dest = System.tmp_dir!() <> "/tantivy_snapshot_test_#{:rand.uniform(1_000_000)}"
+      {:ok, index} = Index.create_in_ram(schema)
+      {:ok, writer} = IndexWriter.new(index)
+      :ok = IndexWriter.add_document(writer, %{"title" => "Backup", "body" => "committed"})
+      :ok = IndexWriter.commit(writer)
+      :ok = IndexWriter.add_document(writer, %{"title" => "Pending", "body" => "not committed"})
+
+      assert {:ok, snapshot} = Index.snapshot(index, dest)
+      assert snapshot["segment_count"] == 1
+      assert snapshot["files"] != []
+
+      {:ok, backup} = Index.open(dest)
+      {:ok, searcher} = Searcher.new(backup)
+      {:ok, query} = TantivyEx.Query.all()
+      assert {:ok, [%{"title" => "Backup"}]} = Searcher.search(searcher, query, 10)
+
+      assert {:error, _} = Index.snapshot(index, dest)
+
+      File.rm_rf!(dest)
+    end
+
+    test "reports index metadata", %{schema: schema} do
+      {:ok, index} = Index.create_in_ram(schema)
+      {:ok, writer} = IndexWriter.new(index)
+      :ok = IndexWriter.add_document(writer, %{"title" => "Meta", "body" => "data"})
+      :ok = IndexWriter.commit(writer)
+
+      assert {:ok, metadata} = Index.metadata(index)
+      assert metadata["num_docs"] == 1
+      assert metadata["opstamp"] > 0
+      assert metadata["segment_count"] == 1
+      assert metadata["directory_type"] == "ram"
+      assert metadata["settings"]["docstore_compression"] == "lz4"
+
+      assert [
+               %{"name" => "title", "type" => "text", "stored" => true},
+               %{"name" => "body", "type" => "text", "stored" => false}
+             ] = metadata["fields"]
+    end
+
+    test "loads committed metas to detect new commits", %{schema: schema} do
+      {:ok, index} = Index.create_in_ram(schema)
+      {:ok, writer} = IndexWriter.new(index)
+
+      assert {:ok, %{"opstamp" => 0, "segment_count" => 0, "segment_ids" => []}} =
+               Index.load_metas(index)
+
+      :ok = IndexWriter.add_document(writer, %{"title" => "First", "body" => "commit"})
+      :ok = IndexWriter.commit(writer)
+      {:ok, first} = Index.load_metas(index)
+
+      :ok = IndexWriter.add_document(writer, %{"title" => "Second", "body" => "commit"})
+      :ok = IndexWriter.commit(writer)
+      {:ok, second} = Index.load_metas(index)
+
+      assert second["opstamp"] > first["opstamp"]
+      assert second["segment_count"] == length(second["segment_ids"])
+      assert Enum.all?(first["segment_ids"], &(&1 in second["segment_ids"]))
+    end
   end
 
   describe "indexing operations" do
@@ -129,6 +300,18 @@ defmodule TantivyExTest do
       assert is_reference(searcher)
     end
 
+    test "creates searcher with an explicit reload policy", %{index: index} do
+      {:ok, query} = TantivyEx.Query.all()
+
+      for policy <- [:manual, :on_commit] do
+        assert {:ok, searcher} = Searcher.new_with_policy(index, policy)
+        assert {:ok, results} = Searcher.search(searcher, query, 10)
+        assert length(results) == 3
+      end
+
+      assert {:error, _} = Searcher.new_with_policy(index, :sometimes)
+    end
+
     test "performs search", %{index: index} do
       {:ok, searcher} = Searcher.new(index)
 
@@ -241,6 +424,28 @@ defmodule TantivyExTest do
       File.rm_rf!(test_dir)
     end
 
+    test "opens an index read-only while another writer holds the lock" do
+      schema = Schema.new()
+      schema = Schema.add_text_field(schema, "title", :text_stored)
+
+      test_dir = "/tmp/test_tantivy_read_only_#{System.system_time(:millisecond)}"
+
+      {:ok, index} = Index.create_in_dir(test_dir, schema)
+      {:ok, writer} = IndexWriter.new(index)
+      :ok = IndexWriter.add_document(writer, %{"title" => "Replica"})
+      :ok = IndexWriter.commit(writer)
+
+      assert {:ok, replica} = Index.open_read_only(test_dir)
+      {:ok, searcher} = Searcher.new(replica)
+      {:ok, query} = TantivyEx.Query.all()
+      assert {:ok, [%{"title" => "Replica"}]} = Searcher.search(searcher, query, 10)
+
+      assert {:error, _} = IndexWriter.new(replica)
+      assert {:error, _} = Index.open_read_only(test_dir <> "_missing")
+
+      File.rm_rf!(test_dir)
+    end
+
     test "open_or_create creates new index when directory doesn't exist" do
       schema = Schema.new()
       schema = Schema.add_text_field(schema, "title", :text_stored)
@@ -165,6 +165,80 @@ defmodule TantivyExTokenizerTest do
     end
   end
 
+  describe "delimiter tokenizer registration" do
+    test "register_delimiter_tokenizer/2 splits on delimiters and lowercases" do
+      assert {:ok, _} = TantivyEx.Tokenizer.register_delimiter_tokenizer("csv_tags", ",;")
+
+      assert Native.tokenize_text("csv_tags", "Red, Dark Green;blue,,") == [
+               "red",
+               "dark green",
+               "blue"
+             ]
+
+      assert {:ok, _} = TantivyEx.Tokenizer.register_delimiter_tokenizer("pipe_tags", ["|", "."])
+      assert Native.tokenize_text("pipe_tags", "a.b|C") == ["a", "b", "c"]
+
+      assert {:error, _} = Native.register_delimiter_tokenizer("no_delims", "")
+    end
+  end
+
+  describe "custom analyzer registration" do
+    test "register_custom_analyzer/3 applies filters in the given order" do
+      assert {:ok, _} =
+               TantivyEx.Tokenizer.register_custom_analyzer("custom_folded", "simple", [
+                 "lowercase",
+                 "remove_long:10",
+                 "ascii_fold"
+               ])
+
+      assert Native.tokenize_text("custom_folded", "Café Supercalifragilistic") == ["cafe"]
+
+      assert {:ok, _} =
+               TantivyEx.Tokenizer.register_custom_analyzer("custom_long_only", "whitespace", [
+                 "remove_long:5"
+               ])
+
+      assert Native.tokenize_text("custom_long_only", "Tiny Enormous") == ["Tiny"]
+    end
+
+    test "register_custom_analyzer/3 rejects unknown or malformed filters" do
+      assert {:error, _} = Native.register_custom_analyzer("bad_filter", "simple", ["sparkle"])
+      assert {:error, _} =
+               Native.register_custom_analyzer("bad_limit", "simple", ["remove_long:x"])
+      assert {:error, _} = Native.register_custom_analyzer("bad_stem", "simple", ["stem"])
+      assert {:error, _} = Native.register_custom_analyzer("bad_lang", "simple", ["stem:klingon"])
+      assert {:error, _} = Native.register_custom_analyzer("bad_base", "ngram", ["lowercase"])
+    end
+  end
+
+  describe "HTML tokenizer registration" do
+    test "register_html_tokenizer/3 strips markup and keeps original offsets" do
+      assert {:ok, _} = TantivyEx.Tokenizer.register_html_tokenizer("html_simple", "simple")
+
+      html = "<p>Fish &amp; <b>Chips</b></p><!-- note --><script>var hidden = 1;</script>"
+      assert TantivyEx.Tokenizer.tokenize_text("html_simple", html) == ["fish", "chips"]
+
+      {chips_start, 5} = :binary.match(html, "Chips")
+
+      assert [{"fish", 3, 7, 0, 1}, {"chips", ^chips_start, chips_end, 1, 1}] =
+               TantivyEx.Tokenizer.tokenize_text_detailed("html_simple", html)
+
+      assert chips_end == chips_start + 5
+
+      # An entity maps back to its full source range
+      assert [{"café", 0, 9, 0, 1}] =
+               TantivyEx.Tokenizer.tokenize_text_detailed("html_simple", "caf&#233;")
+
+      assert {:ok, _} =
+               TantivyEx.Tokenizer.register_html_tokenizer("html_ws", "whitespace", false)
+
+      assert TantivyEx.Tokenizer.tokenize_text("html_ws", "a < b <em>Bold</em>") ==
+               ["a", "<", "b", "Bold"]
+
+      assert {:error, _} = TantivyEx.Tokenizer.register_html_tokenizer("html_bad", "ngram")
+    end
+  end
+
   describe "text tokenization" do
     setup do
       # Ensure we have some tokenizers available
@@ -216,6 +290,19 @@ defmodule TantivyExTokenizerTest do
     test "tokenize_text/2 with nonexistent tokenizer" do
       assert {:error, _reason} = Native.tokenize_text("nonexistent", "test text")
     end
+
+    test "tokenize_texts/2 tokenizes a batch in order" do
+      texts = ["word1 word2", "", "word3\tword4"]
+
+      assert Native.tokenize_texts("test_whitespace", texts) == [
+               ["word1", "word2"],
+               [],
+               ["word3", "word4"]
+             ]
+
+      assert TantivyEx.Tokenizer.tokenize_texts("test_whitespace", []) == []
+      assert {:error, _reason} = Native.tokenize_texts("nonexistent", texts)
+    end
   end
 
   describe "detailed text tokenization" do
@@ -234,12 +321,17 @@ defmodule TantivyExTokenizerTest do
 
       # Each token should have detailed information as tuples
       for token <- detailed_tokens do
-        assert is_tuple(token) and tuple_size(token) == 3
-        {text, offset_from, offset_to} = token
+        assert is_tuple(token) and tuple_size(token) == 5
+        {text, offset_from, offset_to, position, position_length} = token
         assert is_binary(text)
         assert is_integer(offset_from)
         assert is_integer(offset_to)
+        assert is_integer(position)
+        assert position_length >= 1
       end
+
+      positions = Enum.map(detailed_tokens, fn {_, _, _, position, _} -> position end)
+      assert positions == Enum.to_list(0..(length(detailed_tokens) - 1))
     end
   end
 
@@ -588,7 +680,9 @@ defmodule TantivyExTokenizerTest do
       assert is_list(detailed_tokens)
       assert length(detailed_tokens) == 2
 
-      for {token, start_pos, end_pos} <- detailed_tokens do
+      assert [{_, 0, 5, 0, 1}, {_, 6, 11, 1, 1}] = detailed_tokens
+
+      for {token, start_pos, end_pos, _position, _position_length} <- detailed_tokens do
         assert is_binary(token)
         assert is_integer(start_pos)
         assert is_integer(end_pos)